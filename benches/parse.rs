@@ -0,0 +1,130 @@
+//!
+//! Throughput of the parser chain on the shared [ParseBuffer]
+//! compared with the copying of the bytes on each stage, as it was done before
+//!
+//! Run with: `cargo bench --bench parse`
+//!
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion, Throughput};
+use api_tools::{
+    api::message::{
+        fields::{FieldId, FieldKind, FieldSize, FieldSyn},
+        message::MessageParse, message_kind::MessageKind,
+        parse_data::ParseData, parse_id::ParseId, parse_kind::ParseKind, parse_size::ParseSize, parse_syn::ParseSyn,
+    },
+    debug::dbg_id::DbgId,
+};
+///
+/// Number of the messages parsed in the single iteration
+const COUNT: u32 = 10_000;
+///
+/// Length of the `Data` field of each message
+const DATA_LEN: usize = 64;
+///
+/// Bytes received from the socket at once
+const CHUNK_LEN: usize = 4096;
+///
+/// Returns `COUNT` messages with the `Data` of `DATA_LEN` bytes
+fn messages() -> Vec<u8> {
+    let data: Vec<u8> = (0..DATA_LEN as u8).collect();
+    (0..COUNT).flat_map(|id| {
+        [
+            FieldSyn::default().0.to_be_bytes().as_slice(),
+            FieldId(id).to_be_bytes().as_slice(),
+            MessageKind::Bytes.to_bytes(),
+            (data.len() as u32).to_be_bytes().as_slice(),
+            data.as_slice(),
+        ].concat()
+    }).collect()
+}
+///
+/// Returns the parser chain `Syn | Id | Kind | Size | Data`
+fn parser(dbgid: &DbgId) -> ParseData {
+    ParseData::new(
+        dbgid,
+        ParseSize::new(
+            dbgid,
+            FieldSize(4),
+            ParseKind::new(
+                dbgid,
+                FieldKind(MessageKind::Any),
+                ParseId::new(
+                    dbgid,
+                    FieldId(4),
+                    ParseSyn::new(
+                        dbgid,
+                        FieldSyn::default(),
+                    ),
+                ),
+            ),
+        ),
+    )
+}
+///
+/// Returns the number of messages parsed by [MessageParse::parse_all] from the received chunks
+fn parse_shared(message: &mut ParseData, bytes: &[u8]) -> usize {
+    bytes.chunks(CHUNK_LEN)
+        .map(|chunk| message.parse_all(chunk).len())
+        .sum()
+}
+///
+/// Returns the number of messages parsed after [MessageParse::read_from] the "socket" directly into the parser buffer
+fn parse_read_from(message: &mut ParseData, bytes: &[u8]) -> usize {
+    let mut input = bytes;
+    let mut parsed = 0;
+    while let Ok(1..) = message.read_from(CHUNK_LEN, &mut |buf| std::io::Read::read(&mut input, buf)) {
+        parsed += message.parse_all(&[]).len();
+    }
+    parsed
+}
+///
+/// Returns the number of messages parsed with the copying of the bytes following each field,
+/// as it was done by the parser chain before the shared buffer
+fn parse_copying(bytes: &[u8]) -> usize {
+    let mut parsed = 0;
+    let mut remains = vec![];
+    for chunk in bytes.chunks(CHUNK_LEN) {
+        let mut bytes = [std::mem::take(&mut remains), chunk.to_vec()].concat();
+        while let Some(pos) = bytes.iter().position(|b| *b == FieldSyn::default().0) {
+            let syn = bytes[(pos + 1)..].to_vec();
+            if syn.len() < 9 {
+                remains = bytes[pos..].to_vec();
+                break;
+            }
+            let id = syn[4..].to_vec();
+            let kind = id[1..].to_vec();
+            let size = u32::from_be_bytes([kind[0], kind[1], kind[2], kind[3]]) as usize;
+            let size_bytes = kind[4..].to_vec();
+            if size_bytes.len() < size {
+                remains = bytes[pos..].to_vec();
+                break;
+            }
+            let _data = size_bytes[..size].to_vec();
+            bytes = size_bytes[size..].to_vec();
+            parsed += 1;
+        }
+    }
+    parsed
+}
+///
+/// Parsing of the same input by the shared buffer chain & by the copying stages
+fn parse(c: &mut Criterion) {
+    let dbgid = DbgId("bench".to_owned());
+    let bytes = messages();
+    assert_eq!(parse_shared(&mut parser(&dbgid), &bytes), COUNT as usize);
+    assert_eq!(parse_read_from(&mut parser(&dbgid), &bytes), COUNT as usize);
+    assert_eq!(parse_copying(&bytes), COUNT as usize);
+    let mut group = c.benchmark_group("parse");
+    group.throughput(Throughput::Bytes(bytes.len() as u64));
+    group.bench_function("copying", |b| {
+        b.iter(|| parse_copying(&bytes))
+    });
+    group.bench_function("shared", |b| {
+        b.iter_batched_ref(|| parser(&dbgid), |message| parse_shared(message, &bytes), BatchSize::SmallInput)
+    });
+    group.bench_function("read_from", |b| {
+        b.iter_batched_ref(|| parser(&dbgid), |message| parse_read_from(message, &bytes), BatchSize::SmallInput)
+    });
+    group.finish();
+}
+criterion_group!(benches, parse);
+criterion_main!(benches);
@@ -1,3 +1,4 @@
+use crate::error::str_err::StrErr;
 use super::message_kind::MessageKind;
 ///
 /// Start field
@@ -74,6 +75,127 @@ impl FieldSize {
     }
 }
 ///
+/// Width of the encoded `Id` / `Size` field
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldWidth {
+    U8,
+    U16,
+    U32,
+    ///
+    /// LEB128 unsigned varint, 1..=5 bytes, least significant group first
+    Varint,
+}
+///
+/// Byte order of the encoded `Id` / `Size` field
+/// - Not applicable to the [FieldWidth::Varint]
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldOrder {
+    Be,
+    Le,
+}
+///
+/// Encoding of the `Id` / `Size` field
+/// - By default 4 bytes big-endian
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldCodec {
+    pub width: FieldWidth,
+    pub order: FieldOrder,
+}
+impl FieldCodec {
+    ///
+    /// Returns [FieldCodec] new instance
+    pub fn new(width: FieldWidth, order: FieldOrder) -> Self {
+        Self { width, order }
+    }
+    ///
+    /// Returns [FieldCodec] of the fixed `len` in bytes, big-endian
+    /// - 1 - u8, 2 - u16, 4 - u32
+    /// - Returns error for any other `len`
+    pub fn with_len(len: u32) -> Result<Self, StrErr> {
+        match len {
+            1 => Ok(Self::new(FieldWidth::U8, FieldOrder::Be)),
+            2 => Ok(Self::new(FieldWidth::U16, FieldOrder::Be)),
+            4 => Ok(Self::new(FieldWidth::U32, FieldOrder::Be)),
+            _ => Err(format!("FieldCodec.with_len | Unsupported length {}, expected 1, 2 or 4 bytes", len).into()),
+        }
+    }
+    ///
+    /// Returns the length in bytes of the value type encoded
+    /// - 4 for the [FieldWidth::Varint], which encodes u32
+    pub fn value_len(&self) -> u32 {
+        match self.width {
+            FieldWidth::U8 => 1,
+            FieldWidth::U16 => 2,
+            FieldWidth::U32 | FieldWidth::Varint => 4,
+        }
+    }
+    ///
+    /// Returns maximum value can be encoded
+    pub fn max(&self) -> u32 {
+        match self.width {
+            FieldWidth::U8 => u8::MAX as u32,
+            FieldWidth::U16 => u16::MAX as u32,
+            FieldWidth::U32 | FieldWidth::Varint => u32::MAX,
+        }
+    }
+    ///
+    /// Returns encoded `value`
+    /// - Bits of the `value` exceeding the [FieldCodec::max] are dropped
+    pub fn encode(&self, value: u32) -> Vec<u8> {
+        match (&self.width, &self.order) {
+            (FieldWidth::U8, _) => vec![value as u8],
+            (FieldWidth::U16, FieldOrder::Be) => (value as u16).to_be_bytes().to_vec(),
+            (FieldWidth::U16, FieldOrder::Le) => (value as u16).to_le_bytes().to_vec(),
+            (FieldWidth::U32, FieldOrder::Be) => value.to_be_bytes().to_vec(),
+            (FieldWidth::U32, FieldOrder::Le) => value.to_le_bytes().to_vec(),
+            (FieldWidth::Varint, _) => {
+                let mut value = value;
+                let mut bytes = Vec::with_capacity(5);
+                while value >= 0x80 {
+                    bytes.push((value as u8) | 0x80);
+                    value >>= 7;
+                }
+                bytes.push(value as u8);
+                bytes
+            }
+        }
+    }
+    ///
+    /// Returns decoded value & it's length in bytes from the beginning of the `bytes`
+    /// - Returns None if more bytes required
+    /// - Returns error if varint is longer then 5 bytes or overflows u32
+    pub fn decode(&self, bytes: &[u8]) -> Result<Option<(u32, usize)>, StrErr> {
+        fn take<const N: usize>(bytes: &[u8]) -> Option<[u8; N]> {
+            bytes.get(..N).map(|bytes| bytes.try_into().unwrap())
+        }
+        match (&self.width, &self.order) {
+            (FieldWidth::U8, _) => Ok(take::<1>(bytes).map(|b| (b[0] as u32, 1))),
+            (FieldWidth::U16, FieldOrder::Be) => Ok(take(bytes).map(|b| (u16::from_be_bytes(b) as u32, 2))),
+            (FieldWidth::U16, FieldOrder::Le) => Ok(take(bytes).map(|b| (u16::from_le_bytes(b) as u32, 2))),
+            (FieldWidth::U32, FieldOrder::Be) => Ok(take(bytes).map(|b| (u32::from_be_bytes(b), 4))),
+            (FieldWidth::U32, FieldOrder::Le) => Ok(take(bytes).map(|b| (u32::from_le_bytes(b), 4))),
+            (FieldWidth::Varint, _) => {
+                let mut value = 0u32;
+                for (i, byte) in bytes.iter().enumerate() {
+                    if i == 4 && *byte > 0x0f {
+                        return Err(format!("FieldCodec.decode | Varint overflows u32: {:?}", &bytes[..=i]).into());
+                    }
+                    value |= ((byte & 0x7f) as u32) << (7 * i);
+                    if byte & 0x80 == 0 {
+                        return Ok(Some((value, i + 1)));
+                    }
+                }
+                Ok(None)
+            }
+        }
+    }
+}
+impl Default for FieldCodec {
+    fn default() -> Self {
+        Self::new(FieldWidth::U32, FieldOrder::Be)
+    }
+}
+///
 /// Data field
 #[derive(Clone, PartialEq)]
 pub struct FieldData(pub Vec<u8>);
@@ -86,4 +208,58 @@ impl std::fmt::Debug for FieldData {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "FieldData({:?})", self.0)
     }
-}
\ No newline at end of file
+}
+///
+/// Checksum field
+/// - CRC-32 (IEEE 802.3, reflected, polynomial `0xEDB88320`)
+/// - Calculated over all bytes following the `Syn` up to the `Crc`, as they are encoded in the message
+/// - Follows the `Data` field
+/// - Marker only, the value is calculated by the builder and checked by the parser
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldCrc;
+impl FieldCrc {
+    ///
+    /// Lookup table of the CRC-32
+    const TABLE: [u32; 256] = Self::table();
+    ///
+    /// Returns CRC-32 of the `bytes`
+    pub fn checksum(bytes: &[u8]) -> u32 {
+        Self::update(0, bytes)
+    }
+    ///
+    /// Returns CRC-32 of the `bytes` continuing from the previously calculated `crc`
+    pub fn update(crc: u32, bytes: &[u8]) -> u32 {
+        let mut crc = !crc;
+        for byte in bytes {
+            crc = Self::TABLE[((crc ^ *byte as u32) & 0xff) as usize] ^ (crc >> 8);
+        }
+        !crc
+    }
+    ///
+    /// Return the memory representation of this `value` as a byte array in big-endian (network) byte order.
+    pub fn to_be_bytes(&self, value: u32) -> [u8; 4] {
+        value.to_be_bytes()
+    }
+    ///
+    /// Returns field size in bytes
+    pub fn len(&self) -> usize {
+        size_of::<u32>()
+    }
+    ///
+    /// Returns CRC-32 lookup table
+    const fn table() -> [u32; 256] {
+        let mut table = [0u32; 256];
+        let mut i = 0;
+        while i < 256 {
+            let mut crc = i as u32;
+            let mut bit = 0;
+            while bit < 8 {
+                crc = if crc & 1 == 1 { 0xEDB88320 ^ (crc >> 1) } else { crc >> 1 };
+                bit += 1;
+            }
+            table[i] = crc;
+            i += 1;
+        }
+        table
+    }
+}
@@ -0,0 +1,208 @@
+use std::{collections::HashMap, io::Read};
+use crate::{debug::dbg_id::DbgId, error::str_err::StrErr};
+use super::{fields::FieldId, message::Bytes, message_kind::MessageKind, msg_kind::MsgKind};
+///
+/// Fragment of the payload which is too large to be sent in the single message
+/// - All fragments of the payload are sent with the same `Id`
+/// - `index` - position of the fragment, starting from 0
+/// - `last` - true for the final fragment of the payload
+/// - `kind` - [MessageKind] of the reassembled payload
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fragment {
+    pub index: u32,
+    pub last: bool,
+    pub kind: MessageKind,
+    pub bytes: Bytes,
+}
+///
+/// Splits the payload read from the `reader` into [MsgKind::Fragment]'s
+/// - Payload is read by `len` bytes, never buffered completely
+/// ```ignore
+/// let file = std::fs::File::open(path)?;
+/// for fragment in Fragments::new(MessageKind::Bytes, file, 64 * 1024) {
+///     socket.send_kind(&fragment?, Some(id))?;
+/// }
+/// ```
+pub struct Fragments<R> {
+    kind: MessageKind,
+    reader: R,
+    len: usize,
+    index: u32,
+    ahead: Option<Bytes>,
+    done: bool,
+}
+//
+//
+impl<R: Read> Fragments<R> {
+    ///
+    /// Returns [Fragments] new instance
+    /// - `kind` - [MessageKind] of the payload
+    /// - `len` - maximum length of the fragment in bytes
+    pub fn new(kind: MessageKind, reader: R, len: usize) -> Self {
+        Self { kind, reader, len: len.max(1), index: 0, ahead: None, done: false }
+    }
+    ///
+    /// Returns the next chunk, shorter then `len` or empty at the end of the `reader`
+    fn chunk(&mut self) -> Result<Bytes, StrErr> {
+        let mut chunk = Vec::with_capacity(self.len);
+        (&mut self.reader).take(self.len as u64).read_to_end(&mut chunk)
+            .map(|_| chunk)
+            .map_err(|err| format!("Fragments.chunk | Read error: {:?}", err).into())
+    }
+}
+//
+//
+impl<R: Read> Iterator for Fragments<R> {
+    type Item = Result<MsgKind, StrErr>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let bytes = match self.ahead.take() {
+            Some(bytes) => bytes,
+            None => match self.chunk() {
+                Ok(bytes) => bytes,
+                Err(err) => {
+                    self.done = true;
+                    return Some(Err(err));
+                }
+            },
+        };
+        let last = bytes.len() < self.len || match self.chunk() {
+            Ok(ahead) => {
+                let last = ahead.is_empty();
+                self.ahead = Some(ahead);
+                last
+            }
+            Err(err) => {
+                self.done = true;
+                return Some(Err(err));
+            }
+        };
+        let index = self.index;
+        self.index += 1;
+        self.done = last;
+        Some(Ok(MsgKind::Fragment(Fragment { index, last, kind: self.kind.clone(), bytes })))
+    }
+}
+///
+/// Collects [Fragment]'s of the payloads, returns the payload when it's last fragment received
+/// - Fragments of the different `Id`'s may be interleaved
+/// - Fragments of the same `Id` must follow in order, otherwise the payload is discarded
+#[derive(Debug)]
+pub struct Reassembly {
+    dbgid: DbgId,
+    payloads: HashMap<u32, (Fragment, usize)>,
+    max: usize,
+}
+//
+//
+impl Reassembly {
+    ///
+    /// Returns [Reassembly] new instance
+    pub fn new(dbgid: &DbgId) -> Self {
+        Self {
+            dbgid: DbgId::with_parent(dbgid, "Reassembly"),
+            payloads: HashMap::new(),
+            max: usize::MAX,
+        }
+    }
+    ///
+    /// Returns [Reassembly] with specified maximum length of the reassembled payload in bytes
+    /// - Longer payload is discarded
+    pub fn with_max(mut self, max: usize) -> Self {
+        self.max = max;
+        self
+    }
+    ///
+    /// Appends the `fragment` of the payload with specified `id`
+    /// - Returns the decoded payload if the `fragment` is the last one
+    pub fn push(&mut self, id: &FieldId, fragment: Fragment) -> Result<Option<MsgKind>, StrErr> {
+        let (payload, count) = match self.payloads.remove(&id.0) {
+            Some((mut payload, count)) => {
+                if fragment.index as usize != count || fragment.kind != payload.kind {
+                    return Err(format!("{}.push | Payload {:?} discarded, expected fragment {} of kind {:?}, but received {} of kind {:?}",
+                        self.dbgid, id, count, payload.kind, fragment.index, fragment.kind).into());
+                }
+                payload.bytes.extend_from_slice(&fragment.bytes);
+                payload.last = fragment.last;
+                (payload, count + 1)
+            }
+            None => {
+                if fragment.index != 0 {
+                    return Err(format!("{}.push | Payload {:?} discarded, first fragment missed, received {}", self.dbgid, id, fragment.index).into());
+                }
+                (fragment, 1)
+            }
+        };
+        if payload.bytes.len() > self.max {
+            return Err(format!("{}.push | Payload {:?} discarded, length exceeds maximum {}", self.dbgid, id, self.max).into());
+        }
+        match payload.last {
+            true => MsgKind::from_be_bytes(&payload.kind, &payload.bytes)
+                .map(Some)
+                .map_err(|err| format!("{}.push | Payload {:?} decode error: {}", self.dbgid, id, err).into()),
+            false => {
+                self.payloads.insert(id.0, (payload, count));
+                Ok(None)
+            }
+        }
+    }
+    ///
+    /// Discards incomplete payload with specified `id`
+    pub fn discard(&mut self, id: &FieldId) {
+        self.payloads.remove(&id.0);
+    }
+}
+///
+/// Verifies [Fragment]'s of the single payload arriving in order
+/// - Used to hand out the chunks of the payload as they arrive, without buffering
+#[derive(Debug, Default)]
+pub struct FragmentStream {
+    id: Option<FieldId>,
+    kind: Option<MessageKind>,
+    index: u32,
+    len: u64,
+    done: bool,
+}
+//
+//
+impl FragmentStream {
+    ///
+    /// Returns [FragmentStream] new instance
+    pub fn new() -> Self {
+        Self::default()
+    }
+    ///
+    /// Returns the `fragment` if it's the next one of the payload
+    pub fn push(&mut self, id: &FieldId, fragment: Fragment) -> Result<Fragment, StrErr> {
+        if self.done {
+            return Err(format!("FragmentStream.push | Payload {:?} already completed", self.id).into());
+        }
+        match (&self.id, &self.kind) {
+            (Some(stream_id), Some(kind)) => if stream_id != id || *kind != fragment.kind || fragment.index != self.index {
+                return Err(format!("FragmentStream.push | Expected fragment {} of {:?} kind {:?}, but received {} of {:?} kind {:?}",
+                    self.index, stream_id, kind, fragment.index, id, fragment.kind).into());
+            }
+            _ => if fragment.index != 0 {
+                return Err(format!("FragmentStream.push | First fragment missed, received {} of {:?}", fragment.index, id).into());
+            }
+        }
+        self.id = Some(id.clone());
+        self.kind = Some(fragment.kind.clone());
+        self.index += 1;
+        self.len += fragment.bytes.len() as u64;
+        self.done = fragment.last;
+        Ok(fragment)
+    }
+    ///
+    /// Returns total length of the payload received so far in bytes
+    pub fn received(&self) -> u64 {
+        self.len
+    }
+    ///
+    /// Returns true if the last fragment received
+    pub fn is_done(&self) -> bool {
+        self.done
+    }
+}
@@ -4,15 +4,16 @@
 //! - Data can be encoded using varius data `Kind`, `Size` and payload Data
 //! 
 //! - Message format
-//!     Field name | Start | Kind |  Size  | Data |
-//!     ---       |  ---  | ---  |  ---   | ---  |
-//!     Data type |  u8   | u8   | u32    | [u8; Size] |
-//!     Value     |  22   | StringValue | xxx    | [..., ...]  |
+//!     Field name | Start | Kind |  Size  | Data | Crc |
+//!     ---       |  ---  | ---  |  ---   | ---  | --- |
+//!     Data type |  u8   | u8   | u32    | [u8; Size] | u32 |
+//!     Value     |  22   | StringValue | xxx    | [..., ...]  | xxx |
 //!     
 //!     - Start - Each message starts with SYN (22)
 //!     - Kind - The `Kind` of the data stored in the `Data` field, refer to
 //!     - Size - The length of the `Data` field in bytes
 //!     - Data - Data structured depending on it `Kind`
+//!     - Crc - Optional CRC-32 of all fields following the `Start`, see [FieldCrc]
 //! 
 //! - `Kind` of data
 //!     - 00, Any
@@ -27,26 +28,83 @@
 //!     - 26, Int64
 //!     - 32, F32
 //!     - 33, F64
+//!     - 34, Decimal
 //!     - 40, String
+//!     - 41, Uuid
 //!     - 48, Timestamp
 //!     - 49, Duration
+//!     - 50, TimestampNs
+//!     - 51, DurationNs
+//!     - 52, Date
+//!     - 53, DateTime
+//!     - 56, Json
+//!     - 57, MsgPack
+//!     - 58, Cbor
+//!     - 64, Array
+//!     - 65, Map
+//!     - 66, Struct
+//!     - 72, Fragment
 //!     - .., ...
 //! 
 use crate::{debug::dbg_id::DbgId, error::str_err::StrErr};
-use super::fields::{FieldData, FieldId, FieldKind, FieldSize, FieldSyn};
+use super::{fields::{FieldCodec, FieldCrc, FieldData, FieldId, FieldKind, FieldSize, FieldSyn}, message_kind::MessageKind, msg_kind::MsgKind, parse_buffer::ParseBuffer, parse_err::ParseErr};
 ///
 /// 
 pub type Bytes = Vec<u8>;
 ///
 /// Parse Message structure from bytes Interface 
-pub trait MessageParse<T> {
+/// - `Send`, so the [Message] can be moved into another thread or task
+pub trait MessageParse<T>: Send {
     ///
     /// Extracting some pattern from input `bytes`
-    fn parse(&mut self, bytes: Bytes) -> Result<T, StrErr>;
+    fn parse(&mut self, bytes: &[u8]) -> Result<T, ParseErr>;
+    ///
+    /// Extracting all complete messages from input `bytes` and bytes buffered by the previous calls
+    /// - Errors of the discarded corrupted messages are returned in place of them
+    /// - Incomplete message at the end is kept for the next call
+    fn parse_all(&mut self, bytes: &[u8]) -> Vec<Result<T, ParseErr>> {
+        let mut bytes = bytes;
+        let mut results = vec![];
+        loop {
+            match self.parse(std::mem::take(&mut bytes)) {
+                Ok(value) => results.push(Ok(value)),
+                Err(ParseErr::Pending(_) | ParseErr::Syn(_)) => break,
+                Err(err) => results.push(Err(err)),
+            }
+        }
+        results
+    }
+    ///
+    /// Reads up to `len` bytes by `read` directly into the parser buffer, without the intermediate copy
+    /// - Returns the number of bytes read, call [MessageParse::parse_all] with no bytes to extract the messages
+    fn read_from(&mut self, len: usize, read: &mut dyn FnMut(&mut [u8]) -> std::io::Result<usize>) -> std::io::Result<usize>;
+    ///
+    /// Returns the number of bytes skipped while searching for the `Syn` since the previous call
+    fn skipped(&mut self) -> usize;
     ///
     /// Resets state to the initial
     fn reset(&mut self);
 }
+///
+/// Parse Message field from the shared [ParseBuffer] Interface
+/// - Implemented by the stages of the parser chain
+/// - Stage holds the offset of the parsed field instead of the copy of it's bytes
+pub trait ParseField<T>: Send {
+    ///
+    /// Extracting the field from the beginning of the `buffer`
+    /// - returns the field value & the offset of the first byte following by the field
+    /// - bytes of the message are not consumed until the message is completely parsed
+    fn parse_field(&mut self, buffer: &mut ParseBuffer) -> Result<(T, usize), ParseErr>;
+    ///
+    /// Discards the current message, bytes following it's `Syn` will be searched for the next `Syn`
+    fn resync(&mut self, buffer: &mut ParseBuffer);
+    ///
+    /// Returns the number of bytes skipped while searching for the `Syn` since the previous call
+    fn skipped(&mut self) -> usize;
+    ///
+    /// Resets parsed fields, the `buffer` is not affected
+    fn reset(&mut self);
+}
 /// 
 /// 
 #[derive(Debug, Clone, PartialEq)]
@@ -56,12 +114,15 @@ pub enum MessageField {
     Kind(FieldKind),
     Size(FieldSize),
     Data(FieldData),
+    Crc(FieldCrc),
 }
 ///
 /// Socket Message
 pub struct Message<T> {
     dbgid: DbgId,
     build: Vec<MessageField>, 
+    id_codec: Option<FieldCodec>,
+    size_codec: Option<FieldCodec>,
     parse: Box<dyn MessageParse<T>>,
 }
 
@@ -88,25 +149,126 @@ impl<T> Message<T> {
         Self {
             dbgid: DbgId(format!("{}/Message", dbgid)),
             build,
+            id_codec: None,
+            size_codec: None,
             parse: Box::new(parse),
         }
     }
     ///
+    /// Returns `Message` with specified encoding of the built `Id` field
+    /// - By default [FieldCodec::with_len] of the configured [FieldId]
+    /// - Configured by the [MessageSchema](super::message_schema::MessageSchema::with_id_codec), which checks it against the [FieldId]
+    pub(crate) fn with_id_codec(mut self, codec: FieldCodec) -> Self {
+        self.id_codec = Some(codec);
+        self
+    }
+    ///
+    /// Returns `Message` with specified encoding of the built `Size` field
+    /// - By default [FieldCodec::with_len] of the configured [FieldSize]
+    /// - Configured by the [MessageSchema](super::message_schema::MessageSchema::with_size_codec), which checks it against the [FieldSize]
+    pub(crate) fn with_size_codec(mut self, codec: FieldCodec) -> Self {
+        self.size_codec = Some(codec);
+        self
+    }
+    ///
+    /// Returns the greatest `Id` can be built
+    /// - Depends on the codec of the `Id` field, see [MessageSchema::with_id_codec](super::message_schema::MessageSchema::with_id_codec)
+    /// - `u32::MAX` if the `Id` field isn't configured
+    pub fn max_id(&self) -> u32 {
+        self.build.iter()
+            .find_map(|field| match field {
+                MessageField::Id(field_id) => Self::codec(&self.id_codec, field_id.0).ok().map(|codec| codec.max()),
+                _ => None,
+            })
+            .unwrap_or(u32::MAX)
+    }
+    ///
+    /// Returns the `Kind` of the messages built by [Message::build]
+    /// - None if the `Kind` field isn't configured
+    pub fn kind(&self) -> Option<MessageKind> {
+        self.build.iter()
+            .find_map(|field| match field {
+                MessageField::Kind(field_kind) => Some(field_kind.0.clone()),
+                _ => None,
+            })
+    }
+    ///
+    /// Returns the configured `codec` or the default one of the field `len`
+    fn codec(codec: &Option<FieldCodec>, len: u32) -> Result<FieldCodec, StrErr> {
+        match codec {
+            Some(codec) => Ok(codec.clone()),
+            None => FieldCodec::with_len(len),
+        }
+    }
+    ///
     /// Returns message built according to specified fields and passed `bytes`
-    pub fn build(&mut self, bytes: &[u8], id: u32) -> Vec<u8> {
+    /// - `Kind` field is taken from the configured [FieldKind]
+    /// - Returns error if length of the `bytes` exceeds the maximum of the `Size` field
+    pub fn build(&mut self, bytes: &[u8], id: u32) -> Result<Vec<u8>, StrErr> {
+        self.build_fields(None, bytes, id)
+    }
+    ///
+    /// Returns message built according to specified fields and passed `value`
+    /// - `Kind` field and `Data` payload are taken from the `value`
+    /// - Returns error if the `value` can't be encoded, see [MsgKind::encode],
+    ///   or length of it's bytes exceeds the maximum of the `Size` field
+    pub fn build_kind(&mut self, value: &MsgKind, id: u32) -> Result<Vec<u8>, StrErr> {
+        let bytes = value.encode()
+            .map_err(|err| StrErr(format!("{}.build_kind | {}", self.dbgid, err)))?;
+        self.build_fields(Some(&value.kind()), &bytes, id)
+    }
+    ///
+    /// Returns message built according to specified fields and passed `bytes` already encoded by [MsgKind::encode]
+    /// - `kind` - the `Kind` field, overrides the configured [FieldKind]
+    /// - Returns error if length of the `bytes` exceeds the maximum of the `Size` field
+    pub fn build_encoded(&mut self, kind: &MessageKind, bytes: &[u8], id: u32) -> Result<Vec<u8>, StrErr> {
+        self.build_fields(Some(kind), bytes, id)
+    }
+    ///
+    /// Returns message built according to specified fields
+    /// - `kind` - overrides the configured [FieldKind] if specified
+    /// - Returns error if the length of the `Id` or `Size` field isn't supported, see [FieldCodec::with_len]
+    /// - Returns error if `id` exceeds the maximum of the `Id` codec
+    /// - Returns error if length of the `bytes` exceeds the maximum of the `Size` codec
+    fn build_fields(&self, kind: Option<&MessageKind>, bytes: &[u8], id: u32) -> Result<Vec<u8>, StrErr> {
         let mut message = vec![];
-        for field in &mut self.build {
+        let mut crc_from = 0;
+        for field in &self.build {
             match field {
-                MessageField::Syn(field_syn) => message.push(field_syn.0),
-                MessageField::Id(_) => message.extend(FieldId(id).to_be_bytes()),
-                MessageField::Kind(field_kind) => message.extend(field_kind.to_bytes()),
-                MessageField::Size(field_size) => message.extend(field_size.to_be_bytes(bytes.len() as u32)),
+                MessageField::Syn(field_syn) => {
+                    message.push(field_syn.0);
+                    crc_from = message.len();
+                }
+                MessageField::Id(field_id) => {
+                    let codec = Self::codec(&self.id_codec, field_id.0)
+                        .map_err(|err| StrErr(format!("{}.build | Id field: {}", self.dbgid, err)))?;
+                    if id > codec.max() {
+                        return Err(StrErr(format!("{}.build | Id {} exceeds maximum {} of the Id field", self.dbgid, id, codec.max())));
+                    }
+                    message.extend(codec.encode(id));
+                }
+                MessageField::Kind(field_kind) => match kind {
+                    Some(kind) => message.extend(kind.to_bytes()),
+                    None => message.extend(field_kind.to_bytes()),
+                },
+                MessageField::Size(field_size) => {
+                    let codec = Self::codec(&self.size_codec, field_size.0)
+                        .map_err(|err| StrErr(format!("{}.build | Size field: {}", self.dbgid, err)))?;
+                    if bytes.len() > codec.max() as usize {
+                        return Err(StrErr(format!("{}.build | Data length {} exceeds maximum {} of the Size field", self.dbgid, bytes.len(), codec.max())));
+                    }
+                    message.extend(codec.encode(bytes.len() as u32));
+                }
                 MessageField::Data(_) => {
                     message.extend_from_slice(bytes);
                 }
+                MessageField::Crc(field_crc) => {
+                    let crc = FieldCrc::checksum(&message[crc_from..]);
+                    message.extend(field_crc.to_be_bytes(crc));
+                }
             }
         }
-        message
+        Ok(message)
     }
 
 }
@@ -117,11 +279,26 @@ impl<T> MessageParse<T> for Message<T> {
     /// Extracting [Message] fields from the input bytes
     /// - returns `Id`, `Kind`, `Size` & `Bytes` following by the `Size`
     /// - call this method multiple times, until the end of message
-    fn parse(&mut self, bytes: Bytes) -> Result<T, StrErr> {
+    fn parse(&mut self, bytes: &[u8]) -> Result<T, ParseErr> {
         self.parse.parse(bytes)
     }
     //
     //
+    fn parse_all(&mut self, bytes: &[u8]) -> Vec<Result<T, ParseErr>> {
+        self.parse.parse_all(bytes)
+    }
+    //
+    //
+    fn read_from(&mut self, len: usize, read: &mut dyn FnMut(&mut [u8]) -> std::io::Result<usize>) -> std::io::Result<usize> {
+        self.parse.read_from(len, read)
+    }
+    //
+    //
+    fn skipped(&mut self) -> usize {
+        self.parse.skipped()
+    }
+    //
+    //
     fn reset(&mut self) {
         self.parse.reset()
     }
@@ -27,9 +27,22 @@
 //!     - 26, Int64
 //!     - 32, F32
 //!     - 33, F64
+//!     - 34, Decimal
 //!     - 40, String
+//!     - 41, Uuid
 //!     - 48, Timestamp
 //!     - 49, Duration
+//!     - 50, TimestampNs
+//!     - 51, DurationNs
+//!     - 52, Date
+//!     - 53, DateTime
+//!     - 56, Json
+//!     - 57, MsgPack
+//!     - 58, Cbor
+//!     - 64, Array
+//!     - 65, Map
+//!     - 66, Struct
+//!     - 72, Fragment
 //!     - .., ...
 //! 
 use crate::error::str_err::StrErr;
@@ -51,9 +64,22 @@ pub enum MessageKind {
     I64 = Self::INT64 as isize,
     F32 = Self::FLOAT32 as isize,
     F64 = Self::FLOAT64 as isize,
+    Decimal = Self::DECIMAL as isize,
     String = Self::STRING as isize,
+    Uuid = Self::UUID as isize,
     Timestamp = Self::TIMESTAMP as isize,
     Duration = Self::DURATION as isize,
+    TimestampNs = Self::TIMESTAMP_NS as isize,
+    DurationNs = Self::DURATION_NS as isize,
+    Date = Self::DATE as isize,
+    DateTime = Self::DATE_TIME as isize,
+    Json = Self::JSON as isize,
+    MsgPack = Self::MSGPACK as isize,
+    Cbor = Self::CBOR as isize,
+    Array = Self::ARRAY as isize,
+    Map = Self::MAP as isize,
+    Struct = Self::STRUCT as isize,
+    Fragment = Self::FRAGMENT as isize,
 }
 //
 //
@@ -70,9 +96,22 @@ impl MessageKind {
     const INT64: u8 = 26;
     const FLOAT32: u8 = 32;
     const FLOAT64: u8 = 33;
+    const DECIMAL: u8 = 34;
     const STRING: u8 = 40;
+    const UUID: u8 = 41;
     const TIMESTAMP: u8 = 48;
     const DURATION: u8 = 49;
+    const TIMESTAMP_NS: u8 = 50;
+    const DURATION_NS: u8 = 51;
+    const DATE: u8 = 52;
+    const DATE_TIME: u8 = 53;
+    const JSON: u8 = 56;
+    const MSGPACK: u8 = 57;
+    const CBOR: u8 = 58;
+    const ARRAY: u8 = 64;
+    const MAP: u8 = 65;
+    const STRUCT: u8 = 66;
+    const FRAGMENT: u8 = 72;
     ///
     /// Returns bytes of the `MessageKund` variant    
     pub fn to_bytes(&self) -> &[u8] {
@@ -89,9 +128,22 @@ impl MessageKind {
             MessageKind::I64 => &[Self::INT64],
             MessageKind::F32 => &[Self::FLOAT32],
             MessageKind::F64 => &[Self::FLOAT64],
+            MessageKind::Decimal => &[Self::DECIMAL],
             MessageKind::String => &[Self::STRING],
+            MessageKind::Uuid => &[Self::UUID],
             MessageKind::Timestamp => &[Self::TIMESTAMP],
             MessageKind::Duration => &[Self::DURATION],
+            MessageKind::TimestampNs => &[Self::TIMESTAMP_NS],
+            MessageKind::DurationNs => &[Self::DURATION_NS],
+            MessageKind::Date => &[Self::DATE],
+            MessageKind::DateTime => &[Self::DATE_TIME],
+            MessageKind::Json => &[Self::JSON],
+            MessageKind::MsgPack => &[Self::MSGPACK],
+            MessageKind::Cbor => &[Self::CBOR],
+            MessageKind::Array => &[Self::ARRAY],
+            MessageKind::Map => &[Self::MAP],
+            MessageKind::Struct => &[Self::STRUCT],
+            MessageKind::Fragment => &[Self::FRAGMENT],
         }
     }
 }
@@ -112,10 +164,26 @@ impl FromBytes for MessageKind {
             [Self::INT64] => Ok(MessageKind::I64),
             [Self::FLOAT32] => Ok(MessageKind::F32),
             [Self::FLOAT64] => Ok(MessageKind::F64),
+            [Self::DECIMAL] => Ok(MessageKind::Decimal),
             [Self::STRING] => Ok(MessageKind::String),
+            [Self::UUID] => Ok(MessageKind::Uuid),
             [Self::TIMESTAMP] => Ok(MessageKind::Timestamp),
             [Self::DURATION] => Ok(MessageKind::Duration),
-            [..] => Err(StrErr(format!("MessageKind.from_bytes | Wrong or Empty input: {:?}", &bytes[..16]))),
+            [Self::TIMESTAMP_NS] => Ok(MessageKind::TimestampNs),
+            [Self::DURATION_NS] => Ok(MessageKind::DurationNs),
+            [Self::DATE] => Ok(MessageKind::Date),
+            [Self::DATE_TIME] => Ok(MessageKind::DateTime),
+            [Self::JSON] => Ok(MessageKind::Json),
+            [Self::MSGPACK] => Ok(MessageKind::MsgPack),
+            [Self::CBOR] => Ok(MessageKind::Cbor),
+            [Self::ARRAY] => Ok(MessageKind::Array),
+            [Self::MAP] => Ok(MessageKind::Map),
+            [Self::STRUCT] => Ok(MessageKind::Struct),
+            [Self::FRAGMENT] => Ok(MessageKind::Fragment),
+            [..] => {
+                let dbg_bytes = if bytes.len() > 16 {format!("{:?}...", &bytes[..16])} else {format!("{:?}", bytes)};
+                Err(StrErr(format!("MessageKind.from_bytes | Wrong or Empty input: {}", dbg_bytes)))
+            }
         }
     }
 }
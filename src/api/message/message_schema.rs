@@ -0,0 +1,158 @@
+use crate::{api::socket::tcp_socket::TcpMessage, debug::dbg_id::DbgId, error::str_err::StrErr};
+use super::{
+    fields::FieldCodec, message::{Message, MessageField},
+    parse_crc::ParseCrc, parse_data::ParseData, parse_id::ParseId, parse_kind::ParseKind, parse_size::ParseSize, parse_syn::ParseSyn,
+};
+///
+/// Layout of the [Message] described once,
+/// used to produce both builder and parser matching each other
+/// - Fields must follow in the order: `Syn`, `Id`, `Kind`, `Size`, `Data`, optional `Crc`
+/// - Width of the `Id` & `Size` fields is their length in bytes, 1, 2 or 4,
+///   the codec can change the encoding of the value, but not it's width
+/// ```ignore
+/// let message = MessageSchema::new(&dbgid, vec![
+///     MessageField::Syn(FieldSyn::default()),
+///     MessageField::Id(FieldId(4)),
+///     MessageField::Kind(FieldKind(MessageKind::Bytes)),
+///     MessageField::Size(FieldSize(4)),
+///     MessageField::Data(FieldData(vec![])),
+/// ])?.with_size_codec(FieldCodec::new(FieldWidth::Varint, FieldOrder::Le))?.message();
+/// ```
+#[derive(Debug, Clone)]
+pub struct MessageSchema {
+    dbgid: DbgId,
+    fields: Vec<MessageField>,
+    max_size: Option<usize>,
+    id_codec: FieldCodec,
+    size_codec: FieldCodec,
+}
+//
+//
+impl MessageSchema {
+    ///
+    /// Returns [MessageSchema] new instance
+    /// - Returns error if `fields` are missing, duplicated or in the wrong order
+    /// - Returns error if the length of the `Id` or `Size` field isn't supported, see [FieldCodec::with_len]
+    pub fn new(dbgid: &DbgId, fields: Vec<MessageField>) -> Result<Self, StrErr> {
+        let dbgid = DbgId(format!("{}/MessageSchema", dbgid));
+        let mut prev: Option<&MessageField> = None;
+        for field in &fields {
+            match prev {
+                Some(prev) => if Self::order(field) == Self::order(prev) {
+                    return Err(format!("{}.new | Invalid layout, {} field is duplicated", dbgid, Self::name(field)).into());
+                } else if Self::order(field) < Self::order(prev) {
+                    return Err(format!("{}.new | Invalid layout, {} must precede {}", dbgid, Self::name(field), Self::name(prev)).into());
+                }
+                None => if !matches!(field, MessageField::Syn(_)) {
+                    return Err(format!("{}.new | Invalid layout, message must start with Syn, but starts with {}", dbgid, Self::name(field)).into());
+                }
+            }
+            prev = Some(field);
+        }
+        for required in ["Syn", "Id", "Kind", "Size", "Data"] {
+            if !fields.iter().any(|field| Self::name(field) == required) {
+                return Err(format!("{}.new | Invalid layout, {} field is missing", dbgid, required).into());
+            }
+        }
+        let mut id_codec = FieldCodec::default();
+        let mut size_codec = FieldCodec::default();
+        for field in &fields {
+            match field {
+                MessageField::Id(id) => id_codec = FieldCodec::with_len(id.0)
+                    .map_err(|err| StrErr(format!("{}.new | Invalid Id field: {}", dbgid, err)))?,
+                MessageField::Size(size) => size_codec = FieldCodec::with_len(size.0)
+                    .map_err(|err| StrErr(format!("{}.new | Invalid Size field: {}", dbgid, err)))?,
+                _ => {}
+            }
+        }
+        Ok(Self { dbgid, fields, max_size: None, id_codec, size_codec })
+    }
+    ///
+    /// Returns [MessageSchema] with specified maximum `Size` of the `Data` field in bytes
+    /// - See [ParseSize::with_max]
+    pub fn with_max_size(mut self, max: usize) -> Self {
+        self.max_size = Some(max);
+        self
+    }
+    ///
+    /// Returns [MessageSchema] with specified encoding of the `Id` field
+    /// - By default [FieldCodec::with_len] of the configured [FieldId](super::fields::FieldId)
+    /// - Returns error if the `codec` encodes the value of other length than the [FieldId](super::fields::FieldId),
+    ///   see [FieldCodec::value_len]
+    pub fn with_id_codec(mut self, codec: FieldCodec) -> Result<Self, StrErr> {
+        if codec.value_len() != self.id_codec.value_len() {
+            return Err(format!("{}.with_id_codec | Codec {:?} doesn't match the Id field of {} bytes", self.dbgid, codec, self.id_codec.value_len()).into());
+        }
+        self.id_codec = codec;
+        Ok(self)
+    }
+    ///
+    /// Returns [MessageSchema] with specified encoding of the `Size` field
+    /// - By default [FieldCodec::with_len] of the configured [FieldSize](super::fields::FieldSize)
+    /// - Returns error if the `codec` encodes the value of other length than the [FieldSize](super::fields::FieldSize),
+    ///   see [FieldCodec::value_len]
+    /// - Maximum `Size` accepted by the parser is limited by the [FieldCodec::max]
+    pub fn with_size_codec(mut self, codec: FieldCodec) -> Result<Self, StrErr> {
+        if codec.value_len() != self.size_codec.value_len() {
+            return Err(format!("{}.with_size_codec | Codec {:?} doesn't match the Size field of {} bytes", self.dbgid, codec, self.size_codec.value_len()).into());
+        }
+        self.size_codec = codec;
+        Ok(self)
+    }
+    ///
+    /// Returns fields of the message in the order of transmission
+    pub fn fields(&self) -> &[MessageField] {
+        &self.fields
+    }
+    ///
+    /// Returns [TcpMessage] with builder and parser matching the schema
+    pub fn message(&self) -> TcpMessage {
+        let dbgid = &self.dbgid;
+        let (syn, id, kind, size, crc) = match self.fields.as_slice() {
+            [MessageField::Syn(syn), MessageField::Id(id), MessageField::Kind(kind), MessageField::Size(size), MessageField::Data(_), crc @ ..] => {
+                let crc = match crc {
+                    [MessageField::Crc(crc)] => Some(crc.clone()),
+                    _ => None,
+                };
+                (syn.clone(), id.clone(), kind.clone(), size.clone(), crc)
+            }
+            _ => panic!("{}.message | Invalid layout: {:?}", dbgid, self.fields),
+        };
+        let (id_codec, size_codec) = (self.id_codec.clone(), self.size_codec.clone());
+        let max = self.max_size.unwrap_or(usize::MAX).min(size_codec.max() as usize);
+        let parse_id = ParseId::new(dbgid, id, ParseSyn::new(dbgid, syn)).with_codec(id_codec.clone());
+        let parse_size = ParseSize::new(dbgid, size, ParseKind::new(dbgid, kind, parse_id))
+            .with_codec(size_codec.clone())
+            .with_max(max);
+        let parse_data = ParseData::new(dbgid, parse_size);
+        let message = match crc {
+            Some(crc) => Message::new(dbgid, self.fields.clone(), ParseCrc::new(dbgid, crc, parse_data)),
+            None => Message::new(dbgid, self.fields.clone(), parse_data),
+        };
+        message.with_id_codec(id_codec).with_size_codec(size_codec)
+    }
+    ///
+    /// Returns position of the `field` in the message
+    fn order(field: &MessageField) -> usize {
+        match field {
+            MessageField::Syn(_) => 0,
+            MessageField::Id(_) => 1,
+            MessageField::Kind(_) => 2,
+            MessageField::Size(_) => 3,
+            MessageField::Data(_) => 4,
+            MessageField::Crc(_) => 5,
+        }
+    }
+    ///
+    /// Returns name of the `field`
+    fn name(field: &MessageField) -> &'static str {
+        match field {
+            MessageField::Syn(_) => "Syn",
+            MessageField::Id(_) => "Id",
+            MessageField::Kind(_) => "Kind",
+            MessageField::Size(_) => "Size",
+            MessageField::Data(_) => "Data",
+            MessageField::Crc(_) => "Crc",
+        }
+    }
+}
@@ -1,9 +1,15 @@
 pub mod fields;
+pub mod fragment;
 pub mod message_kind;
 pub mod message;
+pub mod message_schema;
 pub mod msg_kind;
+pub mod msg_kind_err;
 pub mod from_bytes;
+pub mod parse_buffer;
+pub mod parse_crc;
 pub mod parse_data;
+pub mod parse_err;
 pub mod parse_id;
 pub mod parse_kind;
 pub mod parse_size;
@@ -1,6 +1,9 @@
 use std::time::Duration;
-use chrono::DateTime;
-use super::message::Bytes;
+use bigdecimal::{num_bigint::BigInt, BigDecimal};
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDate, NaiveDateTime};
+use uuid::Uuid;
+use serde::{de::DeserializeOwned, Serialize};
+use super::{fragment::Fragment, from_bytes::FromBytes, message::Bytes, message_kind::MessageKind, msg_kind_err::MsgKindErr};
 ///
 /// Kind of a [Message]
 /// - The `Kind` of the data stored in the `Data` field
@@ -17,9 +20,22 @@ use super::message::Bytes;
 ///     - 26, Int64
 ///     - 32, F32
 ///     - 33, F64
+///     - 34, Decimal - arbitrary-precision decimal, exact
 ///     - 40, String
-///     - 48, Timestamp
-///     - 49, Duration
+///     - 41, Uuid - 128-bit UUID
+///     - 48, Timestamp - microseconds, nanoseconds are truncated
+///     - 49, Duration - f64 seconds, exact to the nanosecond only below 2^53 ns (about 104 days)
+///     - 50, TimestampNs - nanoseconds with the UTC offset, exact
+///     - 51, DurationNs - seconds & nanoseconds, exact
+///     - 52, Date - calendar date without time zone
+///     - 53, DateTime - calendar date & time without time zone, exact to the nanosecond
+///     - 56, Json - serde_json encoded payload
+///     - 57, MsgPack - MessagePack encoded payload
+///     - 58, Cbor - CBOR encoded payload
+///     - 64, Array - homogeneous elements of the single `Kind`
+///     - 65, Map - key / value pairs of any `Kind`
+///     - 66, Struct - named fields of any `Kind`
+///     - 72, Fragment - part of the payload sent in multiple messages, see [Fragment]
 ///     - .., ...
 #[derive(Debug, Clone, PartialEq)]
 pub enum MsgKind {
@@ -35,22 +51,60 @@ pub enum MsgKind {
     I64(i64),
     F32(f32),
     F64(f64),
+    Decimal(BigDecimal),
     String(String),
+    Uuid(Uuid),
     Timestamp(DateTime<chrono::Utc>),
     Duration(Duration),
+    TimestampNs(DateTime<FixedOffset>),
+    DurationNs(Duration),
+    Date(NaiveDate),
+    DateTime(NaiveDateTime),
+    Json(Bytes),
+    MsgPack(Bytes),
+    Cbor(Bytes),
+    Array(MessageKind, Vec<MsgKind>),
+    Map(Vec<(MsgKind, MsgKind)>),
+    Struct(Vec<(String, MsgKind)>),
+    Fragment(Fragment),
 }
 //
 //
 impl MsgKind {
+    ///
+    /// Days from 0001-01-01 (CE) to 1970-01-01
+    const UNIX_EPOCH_DAYS_CE: i32 = 719_163;
+    ///
+    /// Maximum nesting of the composite kinds accepted by [MsgKind::from_be_bytes]
+    pub const MAX_DEPTH: usize = 32;
     ///
     /// Returns be bytes of the `MsgKind` variant
     /// - `Any` - returns bytes as is
     /// - `Empty` - returns empty vec
     /// - `Bytes` - returns bytes as is  
     /// ...
+    /// - `Decimal` - returns be bytes of the scale (i64), followed by the unscaled value
+    ///    as the minimal big-endian two's complement bytes, `value = unscaled * 10^-scale`
     /// - `String` - returns utf8 bytes  
+    /// - `Uuid` - returns 16 bytes of the UUID in the RFC 4122 order
     /// - `Timestemp` - returns be bytes of the number of non-leap-microseconds since January 1, 1970 UTC.
     /// - `Duration` - returns be bytes of f64 seconds of duration value
+    /// - `TimestampNs` - returns be bytes of the number of non-leap-nanoseconds since January 1, 1970 UTC (i64),
+    ///    followed by the UTC offset in seconds (i32) if it is not zero
+    ///    - Representable range is 1677-09-21T00:12:43.145224192Z ..= 2262-04-11T23:47:16.854775807Z,
+    ///      values out of the range are rejected by [MsgKind::encode], here they are saturated to it's bounds
+    /// - `DurationNs` - returns be bytes of the whole seconds (u64) followed by the nanoseconds (u32),
+    ///    any [Duration] is representable
+    /// - `Date` - returns be bytes of the number of days since January 1, 1970 (i32)
+    /// - `DateTime` - returns be bytes of the number of non-leap-seconds since January 1, 1970 (i64),
+    ///    followed by the nanoseconds (u32), 1_000_000_000.. for the leap second
+    /// - `Json`, `MsgPack`, `Cbor` - returns encoded payload as is
+    /// - `Array` - returns element `Kind` (u8), count (u32), then `Size` (u32) & bytes of each element,
+    ///    all elements expected to be of the specified `Kind`, not verified, see [MsgKind::encode]
+    /// - `Map` - returns count (u32), then `Kind` (u8), `Size` (u32) & bytes of each key and value
+    /// - `Struct` - returns count (u32), then `Size` (u32) & utf8 bytes of the name,
+    ///    `Kind` (u8), `Size` (u32) & bytes of the value of each field
+    /// - `Fragment` - returns index (u32), last flag (u8), `Kind` of the payload (u8), then bytes of the fragment
     pub fn to_be_bytes<'a>(&'a self) -> Vec<u8> {
         match self {
             MsgKind::Any(value) => value.to_vec(),
@@ -65,9 +119,411 @@ impl MsgKind {
             MsgKind::I64(value) => value.to_be_bytes().to_vec(),
             MsgKind::F32(value) => value.to_be_bytes().to_vec(),
             MsgKind::F64(value) => value.to_be_bytes().to_vec(),
+            MsgKind::Decimal(value) => {
+                let (unscaled, scale) = value.as_bigint_and_exponent();
+                [scale.to_be_bytes().to_vec(), unscaled.to_signed_bytes_be()].concat()
+            }
             MsgKind::String(value) => value.as_bytes().to_vec(),
+            MsgKind::Uuid(value) => value.as_bytes().to_vec(),
             MsgKind::Timestamp(value) => value.timestamp_micros().to_be_bytes().to_vec(),
             MsgKind::Duration(value) => value.as_secs_f64().to_be_bytes().to_vec(),
+            MsgKind::TimestampNs(value) => {
+                let nanos = value.timestamp_nanos_opt().unwrap_or(if value.timestamp() < 0 { i64::MIN } else { i64::MAX });
+                match value.offset().local_minus_utc() {
+                    0 => nanos.to_be_bytes().to_vec(),
+                    offset => [nanos.to_be_bytes().as_slice(), &offset.to_be_bytes()].concat(),
+                }
+            }
+            MsgKind::DurationNs(value) => [value.as_secs().to_be_bytes().as_slice(), &value.subsec_nanos().to_be_bytes()].concat(),
+            MsgKind::Date(value) => (value.num_days_from_ce() - Self::UNIX_EPOCH_DAYS_CE).to_be_bytes().to_vec(),
+            MsgKind::DateTime(value) => {
+                let value = value.and_utc();
+                [value.timestamp().to_be_bytes().as_slice(), &value.timestamp_subsec_nanos().to_be_bytes()].concat()
+            }
+            MsgKind::Json(value) => value.to_vec(),
+            MsgKind::MsgPack(value) => value.to_vec(),
+            MsgKind::Cbor(value) => value.to_vec(),
+            MsgKind::Array(kind, values) => {
+                let mut bytes = [kind.to_bytes(), &(values.len() as u32).to_be_bytes()].concat();
+                for value in values {
+                    Self::put(&mut bytes, &value.to_be_bytes());
+                }
+                bytes
+            }
+            MsgKind::Map(values) => {
+                let mut bytes = (values.len() as u32).to_be_bytes().to_vec();
+                for (key, value) in values {
+                    Self::put_kind(&mut bytes, key);
+                    Self::put_kind(&mut bytes, value);
+                }
+                bytes
+            }
+            MsgKind::Struct(fields) => {
+                let mut bytes = (fields.len() as u32).to_be_bytes().to_vec();
+                for (name, value) in fields {
+                    Self::put(&mut bytes, name.as_bytes());
+                    Self::put_kind(&mut bytes, value);
+                }
+                bytes
+            }
+            MsgKind::Fragment(fragment) => [
+                fragment.index.to_be_bytes().as_slice(),
+                &[fragment.last as u8],
+                fragment.kind.to_bytes(),
+                &fragment.bytes,
+            ].concat(),
+        }
+    }
+    ///
+    /// Returns be bytes of the `MsgKind` variant, see [MsgKind::to_be_bytes]
+    /// - Returns [MsgKindErr::Element] if any `Array`, including the nested ones, isn't homogeneous
+    /// - Returns [MsgKindErr::TimestampNs] if any `TimestampNs` is out of the representable range
+    pub fn encode(&self) -> Result<Vec<u8>, MsgKindErr> {
+        self.check()?;
+        Ok(self.to_be_bytes())
+    }
+    ///
+    /// Returns error if any `Array` inside of the value isn't homogeneous
+    /// or any `TimestampNs` can't be encoded without the saturation
+    fn check(&self) -> Result<(), MsgKindErr> {
+        match self {
+            MsgKind::TimestampNs(value) => match value.timestamp_nanos_opt() {
+                Some(_) => Ok(()),
+                None => Err(MsgKindErr::TimestampNs(value.timestamp())),
+            },
+            MsgKind::Array(kind, values) => {
+                Self::homogeneous(kind, values)?;
+                values.iter().try_for_each(|value| value.check())
+            }
+            MsgKind::Map(values) => values.iter().try_for_each(|(key, value)| {
+                key.check()?;
+                value.check()
+            }),
+            MsgKind::Struct(fields) => fields.iter().try_for_each(|(_, value)| value.check()),
+            _ => Ok(()),
+        }
+    }
+    ///
+    /// Returns error if any of the `values` isn't of the `kind`,
+    /// elements of the nested arrays must be of the same `Kind` as well
+    fn homogeneous(kind: &MessageKind, values: &[MsgKind]) -> Result<(), MsgKindErr> {
+        let mut item_kind = None;
+        for value in values {
+            if value.kind() != *kind {
+                return Err(MsgKindErr::Element { expected: kind.clone(), found: value.kind() });
+            }
+            if let MsgKind::Array(inner, _) = value {
+                match item_kind {
+                    Some(item_kind) if item_kind != inner => {
+                        return Err(MsgKindErr::Element { expected: item_kind.clone(), found: inner.clone() });
+                    }
+                    Some(_) => {}
+                    None => item_kind = Some(inner),
+                }
+            }
+        }
+        Ok(())
+    }
+    ///
+    /// Appends `Size` (u32) & `data` to the `bytes`
+    fn put(bytes: &mut Vec<u8>, data: &[u8]) {
+        bytes.extend((data.len() as u32).to_be_bytes());
+        bytes.extend_from_slice(data);
+    }
+    ///
+    /// Appends `Kind` (u8), `Size` (u32) & be bytes of the `value` to the `bytes`
+    fn put_kind(bytes: &mut Vec<u8>, value: &MsgKind) {
+        bytes.extend_from_slice(value.kind().to_bytes());
+        Self::put(bytes, &value.to_be_bytes());
+    }
+    ///
+    /// Returns [MsgKind] decoded from the `Data` field `bytes` of the specified `kind`
+    /// - The exact mirror of the [MsgKind::to_be_bytes]
+    /// - Fixed size kinds requires exact `bytes` length
+    /// - `Bool` accepts only 0 / 1
+    /// - `Decimal` requires the scale and at least one byte of the unscaled value
+    /// - `String` requires valid UTF-8
+    /// - `Uuid` requires 16 bytes
+    /// - `Timestamp` requires microseconds in the range of the [DateTime]
+    /// - `Duration` requires positive finite seconds
+    /// - `TimestampNs` requires 8 bytes, or 12 bytes with the offset in the range of the [FixedOffset]
+    /// - `DurationNs` requires nanoseconds less than 1_000_000_000
+    /// - `Date` requires days in the range of the [NaiveDate]
+    /// - `DateTime` requires seconds in the range of the [NaiveDateTime],
+    ///    nanoseconds less than 2_000_000_000 and 1_000_000_000.. only for the leap second
+    /// - `Json`, `MsgPack`, `Cbor` payload is not verified, see [MsgKind::deserialize]
+    /// - `Array`, `Map`, `Struct` requires all elements to be decoded without trailing bytes,
+    ///    nesting is limited by [MsgKind::MAX_DEPTH]
+    /// - `Array` requires the elements of the nested arrays to be of the same `Kind`
+    /// - `Fragment` requires the header of 6 bytes with the last flag 0 / 1
+    pub fn from_be_bytes(kind: &MessageKind, bytes: &[u8]) -> Result<Self, MsgKindErr> {
+        Self::decode(kind, bytes, 0)
+    }
+    ///
+    /// Returns [MsgKind] decoded from `bytes`, `depth` - nesting level of the composite kinds
+    fn decode(kind: &MessageKind, bytes: &[u8], depth: usize) -> Result<Self, MsgKindErr> {
+        fn take<const N: usize>(kind: &MessageKind, bytes: &[u8]) -> Result<[u8; N], MsgKindErr> {
+            bytes.try_into().map_err(|_| MsgKindErr::Size { kind: kind.clone(), expected: N, found: bytes.len() })
+        }
+        match kind {
+            MessageKind::Any => Ok(MsgKind::Any(bytes.to_vec())),
+            MessageKind::Empty => take::<0>(kind, bytes).map(|_| MsgKind::Empty),
+            MessageKind::Bytes => Ok(MsgKind::Bytes(bytes.to_vec())),
+            MessageKind::Bool => match take::<1>(kind, bytes)? {
+                [0] => Ok(MsgKind::Bool(false)),
+                [1] => Ok(MsgKind::Bool(true)),
+                [value] => Err(MsgKindErr::Bool(value)),
+            },
+            MessageKind::U16 => Ok(MsgKind::U16(u16::from_be_bytes(take(kind, bytes)?))),
+            MessageKind::U32 => Ok(MsgKind::U32(u32::from_be_bytes(take(kind, bytes)?))),
+            MessageKind::U64 => Ok(MsgKind::U64(u64::from_be_bytes(take(kind, bytes)?))),
+            MessageKind::I16 => Ok(MsgKind::I16(i16::from_be_bytes(take(kind, bytes)?))),
+            MessageKind::I32 => Ok(MsgKind::I32(i32::from_be_bytes(take(kind, bytes)?))),
+            MessageKind::I64 => Ok(MsgKind::I64(i64::from_be_bytes(take(kind, bytes)?))),
+            MessageKind::F32 => Ok(MsgKind::F32(f32::from_be_bytes(take(kind, bytes)?))),
+            MessageKind::F64 => Ok(MsgKind::F64(f64::from_be_bytes(take(kind, bytes)?))),
+            MessageKind::Decimal => match bytes.len() {
+                len if len > 8 => {
+                    let scale = i64::from_be_bytes(take(kind, &bytes[..8])?);
+                    Ok(MsgKind::Decimal(BigDecimal::new(BigInt::from_signed_bytes_be(&bytes[8..]), scale)))
+                }
+                len => Err(MsgKindErr::Size { kind: kind.clone(), expected: 9, found: len }),
+            },
+            MessageKind::String => String::from_utf8(bytes.to_vec())
+                .map(MsgKind::String)
+                .map_err(MsgKindErr::Utf8),
+            MessageKind::Uuid => Ok(MsgKind::Uuid(Uuid::from_bytes(take(kind, bytes)?))),
+            MessageKind::Timestamp => {
+                let micros = i64::from_be_bytes(take(kind, bytes)?);
+                DateTime::from_timestamp_micros(micros)
+                    .map(MsgKind::Timestamp)
+                    .ok_or(MsgKindErr::Timestamp(micros))
+            }
+            MessageKind::Duration => {
+                let secs = f64::from_be_bytes(take(kind, bytes)?);
+                Duration::try_from_secs_f64(secs)
+                    .map(MsgKind::Duration)
+                    .map_err(|_| MsgKindErr::Duration(secs))
+            }
+            MessageKind::TimestampNs => {
+                let (nanos, offset) = match bytes.len() {
+                    12 => (&bytes[..8], i32::from_be_bytes(take(kind, &bytes[8..])?)),
+                    len if len > 8 => return Err(MsgKindErr::Size { kind: kind.clone(), expected: 12, found: len }),
+                    _ => (bytes, 0),
+                };
+                let nanos = i64::from_be_bytes(take(kind, nanos)?);
+                FixedOffset::east_opt(offset)
+                    .map(|offset| MsgKind::TimestampNs(DateTime::from_timestamp_nanos(nanos).with_timezone(&offset)))
+                    .ok_or(MsgKindErr::Offset(offset))
+            }
+            MessageKind::DurationNs => {
+                let bytes: [u8; 12] = take(kind, bytes)?;
+                let secs = u64::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3], bytes[4], bytes[5], bytes[6], bytes[7]]);
+                let nanos = u32::from_be_bytes([bytes[8], bytes[9], bytes[10], bytes[11]]);
+                match nanos < 1_000_000_000 {
+                    true => Ok(MsgKind::DurationNs(Duration::new(secs, nanos))),
+                    false => Err(MsgKindErr::Nanos(nanos)),
+                }
+            }
+            MessageKind::Date => {
+                let days = i32::from_be_bytes(take(kind, bytes)?);
+                days.checked_add(Self::UNIX_EPOCH_DAYS_CE)
+                    .and_then(NaiveDate::from_num_days_from_ce_opt)
+                    .map(MsgKind::Date)
+                    .ok_or(MsgKindErr::Date(days))
+            }
+            MessageKind::DateTime => {
+                let bytes: [u8; 12] = take(kind, bytes)?;
+                let secs = i64::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3], bytes[4], bytes[5], bytes[6], bytes[7]]);
+                let nanos = u32::from_be_bytes([bytes[8], bytes[9], bytes[10], bytes[11]]);
+                match DateTime::from_timestamp(secs, nanos) {
+                    Some(value) => Ok(MsgKind::DateTime(value.naive_utc())),
+                    None if nanos >= 1_000_000_000 => Err(MsgKindErr::Nanos(nanos)),
+                    None => Err(MsgKindErr::DateTime(secs)),
+                }
+            }
+            MessageKind::Json => Ok(MsgKind::Json(bytes.to_vec())),
+            MessageKind::MsgPack => Ok(MsgKind::MsgPack(bytes.to_vec())),
+            MessageKind::Cbor => Ok(MsgKind::Cbor(bytes.to_vec())),
+            MessageKind::Array | MessageKind::Map | MessageKind::Struct if depth >= Self::MAX_DEPTH => {
+                Err(MsgKindErr::Depth(Self::MAX_DEPTH))
+            }
+            MessageKind::Array => {
+                let mut reader = Reader::new(kind, bytes);
+                let item_kind = reader.kind()?;
+                let count = reader.count()?;
+                let mut values = Vec::with_capacity(count.min(reader.len()));
+                for _ in 0..count {
+                    values.push(Self::decode(&item_kind, reader.item()?, depth + 1)?);
+                }
+                reader.end()?;
+                Self::homogeneous(&item_kind, &values)?;
+                Ok(MsgKind::Array(item_kind, values))
+            }
+            MessageKind::Map => {
+                let mut reader = Reader::new(kind, bytes);
+                let count = reader.count()?;
+                let mut values = Vec::with_capacity(count.min(reader.len()));
+                for _ in 0..count {
+                    let key_kind = reader.kind()?;
+                    let key = Self::decode(&key_kind, reader.item()?, depth + 1)?;
+                    let value_kind = reader.kind()?;
+                    let value = Self::decode(&value_kind, reader.item()?, depth + 1)?;
+                    values.push((key, value));
+                }
+                reader.end().map(|_| MsgKind::Map(values))
+            }
+            MessageKind::Struct => {
+                let mut reader = Reader::new(kind, bytes);
+                let count = reader.count()?;
+                let mut fields = Vec::with_capacity(count.min(reader.len()));
+                for _ in 0..count {
+                    let name = String::from_utf8(reader.item()?.to_vec()).map_err(MsgKindErr::Utf8)?;
+                    let value_kind = reader.kind()?;
+                    let value = Self::decode(&value_kind, reader.item()?, depth + 1)?;
+                    fields.push((name, value));
+                }
+                reader.end().map(|_| MsgKind::Struct(fields))
+            }
+            MessageKind::Fragment => {
+                let mut reader = Reader::new(kind, bytes);
+                let index = reader.count()? as u32;
+                let last = match reader.take(1)?[0] {
+                    0 => false,
+                    1 => true,
+                    value => return Err(MsgKindErr::Bool(value)),
+                };
+                let payload_kind = reader.kind()?;
+                let bytes = reader.take(reader.len())?.to_vec();
+                Ok(MsgKind::Fragment(Fragment { index, last, kind: payload_kind, bytes }))
+            }
+        }
+    }
+    ///
+    /// Returns `Json` payload serialized from the `value`
+    pub fn json<T: Serialize + ?Sized>(value: &T) -> Result<Self, MsgKindErr> {
+        serde_json::to_vec(value)
+            .map(MsgKind::Json)
+            .map_err(|err| MsgKindErr::Serde { kind: MessageKind::Json, err: err.to_string() })
+    }
+    ///
+    /// Returns `MsgPack` payload serialized from the `value`
+    /// - Structs are encoded as maps with the field names
+    pub fn msgpack<T: Serialize + ?Sized>(value: &T) -> Result<Self, MsgKindErr> {
+        rmp_serde::to_vec_named(value)
+            .map(MsgKind::MsgPack)
+            .map_err(|err| MsgKindErr::Serde { kind: MessageKind::MsgPack, err: err.to_string() })
+    }
+    ///
+    /// Returns `Cbor` payload serialized from the `value`
+    pub fn cbor<T: Serialize + ?Sized>(value: &T) -> Result<Self, MsgKindErr> {
+        let mut bytes = vec![];
+        ciborium::into_writer(value, &mut bytes)
+            .map(|_| MsgKind::Cbor(bytes))
+            .map_err(|err| MsgKindErr::Serde { kind: MessageKind::Cbor, err: err.to_string() })
+    }
+    ///
+    /// Returns value deserialized from the `Json`, `MsgPack` or `Cbor` payload
+    /// - Other kinds are rejected with [MsgKindErr::Serde]
+    pub fn deserialize<T: DeserializeOwned>(&self) -> Result<T, MsgKindErr> {
+        let err = |err: String| MsgKindErr::Serde { kind: self.kind(), err };
+        match self {
+            MsgKind::Json(bytes) => serde_json::from_slice(bytes).map_err(|e| err(e.to_string())),
+            MsgKind::MsgPack(bytes) => rmp_serde::from_slice(bytes).map_err(|e| err(e.to_string())),
+            MsgKind::Cbor(bytes) => ciborium::from_reader(bytes.as_slice()).map_err(|e| err(e.to_string())),
+            _ => Err(err("Serialized payload expected".to_owned())),
+        }
+    }
+    ///
+    /// Returns [MessageKind] of the `MsgKind` variant
+    pub fn kind(&self) -> MessageKind {
+        match self {
+            MsgKind::Any(_) => MessageKind::Any,
+            MsgKind::Empty => MessageKind::Empty,
+            MsgKind::Bytes(_) => MessageKind::Bytes,
+            MsgKind::Bool(_) => MessageKind::Bool,
+            MsgKind::U16(_) => MessageKind::U16,
+            MsgKind::U32(_) => MessageKind::U32,
+            MsgKind::U64(_) => MessageKind::U64,
+            MsgKind::I16(_) => MessageKind::I16,
+            MsgKind::I32(_) => MessageKind::I32,
+            MsgKind::I64(_) => MessageKind::I64,
+            MsgKind::F32(_) => MessageKind::F32,
+            MsgKind::F64(_) => MessageKind::F64,
+            MsgKind::Decimal(_) => MessageKind::Decimal,
+            MsgKind::String(_) => MessageKind::String,
+            MsgKind::Uuid(_) => MessageKind::Uuid,
+            MsgKind::Timestamp(_) => MessageKind::Timestamp,
+            MsgKind::Duration(_) => MessageKind::Duration,
+            MsgKind::TimestampNs(_) => MessageKind::TimestampNs,
+            MsgKind::DurationNs(_) => MessageKind::DurationNs,
+            MsgKind::Date(_) => MessageKind::Date,
+            MsgKind::DateTime(_) => MessageKind::DateTime,
+            MsgKind::Json(_) => MessageKind::Json,
+            MsgKind::MsgPack(_) => MessageKind::MsgPack,
+            MsgKind::Cbor(_) => MessageKind::Cbor,
+            MsgKind::Array(..) => MessageKind::Array,
+            MsgKind::Map(_) => MessageKind::Map,
+            MsgKind::Struct(_) => MessageKind::Struct,
+            MsgKind::Fragment(_) => MessageKind::Fragment,
+        }
+    }
+}
+///
+/// Sequential reading of the composite `Data` field
+struct Reader<'a> {
+    kind: &'a MessageKind,
+    bytes: &'a [u8],
+    pos: usize,
+}
+//
+//
+impl<'a> Reader<'a> {
+    ///
+    /// Returns [Reader] new instance, `kind` - the composite kind been read
+    fn new(kind: &'a MessageKind, bytes: &'a [u8]) -> Self {
+        Self { kind, bytes, pos: 0 }
+    }
+    ///
+    /// Returns the number of bytes not read yet
+    fn len(&self) -> usize {
+        self.bytes.len() - self.pos
+    }
+    ///
+    /// Returns next `len` bytes
+    fn take(&mut self, len: usize) -> Result<&'a [u8], MsgKindErr> {
+        match self.bytes.get(self.pos..).and_then(|bytes| bytes.get(..len)) {
+            Some(bytes) => {
+                self.pos += len;
+                Ok(bytes)
+            }
+            None => Err(MsgKindErr::Size { kind: self.kind.clone(), expected: self.pos + len, found: self.bytes.len() }),
+        }
+    }
+    ///
+    /// Returns next `Kind` (u8)
+    fn kind(&mut self) -> Result<MessageKind, MsgKindErr> {
+        let bytes = self.take(1)?;
+        MessageKind::from_bytes(bytes).map_err(|_| MsgKindErr::Kind(bytes[0]))
+    }
+    ///
+    /// Returns next count (u32)
+    fn count(&mut self) -> Result<usize, MsgKindErr> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as usize)
+    }
+    ///
+    /// Returns bytes of the next item, prefixed by it's `Size` (u32)
+    fn item(&mut self) -> Result<&'a [u8], MsgKindErr> {
+        let len = self.count()?;
+        self.take(len)
+    }
+    ///
+    /// Returns error if some bytes are not read
+    fn end(&self) -> Result<(), MsgKindErr> {
+        match self.len() {
+            0 => Ok(()),
+            _ => Err(MsgKindErr::Size { kind: self.kind.clone(), expected: self.pos, found: self.bytes.len() }),
         }
     }
 }
@@ -0,0 +1,88 @@
+use crate::error::str_err::StrErr;
+use super::message_kind::MessageKind;
+///
+/// Errors of decoding [MsgKind](super::msg_kind::MsgKind) from the `Data` field bytes
+#[derive(Clone, PartialEq)]
+pub enum MsgKindErr {
+    ///
+    /// Length of the `Data` doesn't match the `Kind`
+    Size { kind: MessageKind, expected: usize, found: usize },
+    ///
+    /// `String` contains invalid UTF-8 sequence
+    Utf8(std::string::FromUtf8Error),
+    ///
+    /// `Bool` byte is neither 0 nor 1
+    Bool(u8),
+    ///
+    /// `Timestamp` microseconds out of the representable range
+    Timestamp(i64),
+    ///
+    /// `Duration` seconds negative, not finite or overflowed
+    Duration(f64),
+    ///
+    /// `TimestampNs` seconds out of the range of the i64 nanoseconds
+    TimestampNs(i64),
+    ///
+    /// `TimestampNs` offset seconds out of the range of the `FixedOffset`
+    Offset(i32),
+    ///
+    /// `DurationNs` nanoseconds greater than 999_999_999, `DateTime` nanoseconds invalid
+    Nanos(u32),
+    ///
+    /// `Date` days out of the range of the `NaiveDate`
+    Date(i32),
+    ///
+    /// `DateTime` seconds out of the range of the `NaiveDateTime`
+    DateTime(i64),
+    ///
+    /// `Json`, `MsgPack` or `Cbor` payload can't be serialized / deserialized
+    Serde { kind: MessageKind, err: String },
+    ///
+    /// Unknown `Kind` of the element of the composite kind
+    Kind(u8),
+    ///
+    /// Element of the `Array` doesn't match the element `Kind` of the array
+    Element { expected: MessageKind, found: MessageKind },
+    ///
+    /// Nesting of the composite kinds exceeds the maximum
+    Depth(usize),
+}
+//
+//
+impl std::fmt::Display for MsgKindErr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MsgKindErr::Size { kind, expected, found } => write!(f, "MsgKindErr | Kind '{:?}' expected Size {}, but found {}", kind, expected, found),
+            MsgKindErr::Utf8(err) => write!(f, "MsgKindErr | Invalid UTF-8: {}", err),
+            MsgKindErr::Bool(value) => write!(f, "MsgKindErr | Invalid Bool byte: {}", value),
+            MsgKindErr::Timestamp(value) => write!(f, "MsgKindErr | Timestamp out of range: {} us", value),
+            MsgKindErr::Duration(value) => write!(f, "MsgKindErr | Invalid Duration: {} s", value),
+            MsgKindErr::TimestampNs(value) => write!(f, "MsgKindErr | TimestampNs out of range: {} s", value),
+            MsgKindErr::Offset(value) => write!(f, "MsgKindErr | TimestampNs offset out of range: {} s", value),
+            MsgKindErr::Nanos(value) => write!(f, "MsgKindErr | Nanoseconds out of range: {}", value),
+            MsgKindErr::Date(value) => write!(f, "MsgKindErr | Date out of range: {} days", value),
+            MsgKindErr::DateTime(value) => write!(f, "MsgKindErr | DateTime out of range: {} s", value),
+            MsgKindErr::Serde { kind, err } => write!(f, "MsgKindErr | Kind '{:?}' serde error: {}", kind, err),
+            MsgKindErr::Kind(value) => write!(f, "MsgKindErr | Unknown element Kind: {}", value),
+            MsgKindErr::Element { expected, found } => write!(f, "MsgKindErr | Array element of Kind '{:?}' expected, but found '{:?}'", expected, found),
+            MsgKindErr::Depth(value) => write!(f, "MsgKindErr | Nesting exceeds maximum depth {}", value),
+        }
+    }
+}
+//
+//
+impl std::fmt::Debug for MsgKindErr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+//
+//
+impl std::error::Error for MsgKindErr {}
+//
+//
+impl From<MsgKindErr> for StrErr {
+    fn from(value: MsgKindErr) -> Self {
+        StrErr(value.to_string())
+    }
+}
@@ -0,0 +1,111 @@
+use super::message::Bytes;
+///
+/// Growable buffer shared by the stages of the parser chain
+/// - Received bytes are appended to the end, or read into it directly, see [ParseBuffer::read_from]
+/// - Stages are holding offsets from the beginning of the buffer instead of copies of the bytes
+/// - Parsed message is consumed from the beginning, the memory is reused for the following bytes
+#[derive(Debug, Default)]
+pub struct ParseBuffer {
+    bytes: Bytes,
+    start: usize,
+    end: usize,
+    returned: usize,
+}
+//
+//
+impl ParseBuffer {
+    ///
+    /// Returns [ParseBuffer] new instance
+    pub fn new() -> Self {
+        Self::default()
+    }
+    ///
+    /// Makes room for `len` bytes at the end of the buffer
+    /// - Consumed space at the beginning is reclaimed before growing
+    fn reserve(&mut self, len: usize) {
+        if self.end + len > self.bytes.len() {
+            if self.start > 0 {
+                self.bytes.copy_within(self.start..self.end, 0);
+                self.end -= self.start;
+                self.start = 0;
+            }
+            if self.end + len > self.bytes.len() {
+                self.bytes.resize(self.end + len, 0);
+            }
+        }
+    }
+    ///
+    /// Appends `bytes` to the end of the buffer
+    pub fn extend(&mut self, bytes: &[u8]) {
+        self.reserve(bytes.len());
+        self.bytes[self.end..(self.end + bytes.len())].copy_from_slice(bytes);
+        self.end += bytes.len();
+    }
+    ///
+    /// Reads up to `len` bytes by `read` directly into the end of the buffer
+    /// - Returns the number of bytes read, as returned by `read`
+    pub fn read_from(&mut self, len: usize, read: &mut dyn FnMut(&mut [u8]) -> std::io::Result<usize>) -> std::io::Result<usize> {
+        self.reserve(len);
+        let read = read(&mut self.bytes[self.end..(self.end + len)])?;
+        self.end += read.min(len);
+        Ok(read)
+    }
+    ///
+    /// Returns not consumed bytes
+    pub fn as_slice(&self) -> &[u8] {
+        &self.bytes[self.start..self.end]
+    }
+    ///
+    /// Returns the number of not consumed bytes
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+    ///
+    /// Returns true if all bytes are consumed
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    ///
+    /// Consumes `len` bytes from the beginning
+    pub fn consume(&mut self, len: usize) {
+        let len = len.min(self.len());
+        self.start += len;
+        self.returned = self.returned.saturating_sub(len);
+        if self.start == self.end {
+            self.start = 0;
+            self.end = 0;
+        }
+    }
+    ///
+    /// Returns bytes in the range `from..to` and consumes `end` bytes from the beginning
+    /// - If the range takes the most of the allocated memory and nothing follows the `end`,
+    ///   the memory is handed over instead of copying the bytes, new one is allocated by the next read
+    pub fn take(&mut self, from: usize, to: usize, end: usize) -> Bytes {
+        if self.start + end == self.end && (to - from) * 2 >= self.bytes.len() {
+            let mut bytes = std::mem::take(&mut self.bytes);
+            bytes.truncate(self.start + to);
+            bytes.drain(..(self.start + from));
+            self.clear();
+            bytes
+        } else {
+            let bytes = self.as_slice()[from..to].to_vec();
+            self.consume(end);
+            bytes
+        }
+    }
+    ///
+    /// Returns bytes following the `offset`, which are not returned by the previous calls
+    /// - Used when the intermediate stage of the parser chain is called directly
+    pub fn following(&mut self, offset: usize) -> Bytes {
+        let from = offset.max(self.returned).min(self.len());
+        self.returned = self.len();
+        self.as_slice()[from..].to_vec()
+    }
+    ///
+    /// Removes all bytes, keeping allocated memory
+    pub fn clear(&mut self) {
+        self.start = 0;
+        self.end = 0;
+        self.returned = 0;
+    }
+}
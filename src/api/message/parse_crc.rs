@@ -0,0 +1,118 @@
+use crate::{api::message::message_kind::MessageKind, debug::dbg_id::DbgId};
+use super::{fields::{FieldCrc, FieldId, FieldSize, FieldSyn}, message::{Bytes, MessageParse, ParseField}, parse_buffer::ParseBuffer, parse_err::ParseErr};
+///
+/// Extracting `Crc` field following the `Data` field from the input bytes
+/// - Verifies the checksum of the parsed message
+/// - Message with wrong checksum is rejected, parser resynchronises on the next `Syn`
+/// - Owns the [ParseBuffer] shared by all stages of the parser chain
+pub struct ParseCrc {
+    dbgid: DbgId,
+    conf: FieldCrc,
+    field: Box<dyn ParseField<(FieldId, MessageKind, FieldSize)>>,
+    buffer: ParseBuffer,
+    pending: bool,
+}
+//
+//
+impl ParseCrc {
+    ///
+    /// Returns [ParseCrc] new instance
+    pub fn new(dbgid: &DbgId, conf: FieldCrc, field: impl ParseField<(FieldId, MessageKind, FieldSize)> + 'static) -> Self {
+        Self {
+            dbgid: DbgId(format!("{}/ParseCrc", dbgid)),
+            conf,
+            field: Box::new(field),
+            buffer: ParseBuffer::new(),
+            pending: false,
+        }
+    }
+}
+//
+//
+impl ParseField<(FieldId, MessageKind, FieldSize)> for ParseCrc {
+    ///
+    /// Extracting `Crc` field from the `buffer`
+    /// - returns `Id`, `Kind`, `Size` & the offset of the first byte following by the `Crc` if the checksum is valid
+    /// - `Data` occupies `Size` bytes preceding the `Crc`
+    /// - Checksum is calculated over the received bytes between the `Syn` and the `Crc`,
+    ///   so it doesn't depend on the encoding of the `Id` & `Size` fields
+    fn parse_field(&mut self, buffer: &mut ParseBuffer) -> Result<((FieldId, MessageKind, FieldSize), usize), ParseErr> {
+        let ((id, kind, size), start) = self.field.parse_field(buffer)?;
+        let end = start + self.conf.len();
+        match buffer.as_slice().get(start..end) {
+            Some(crc_bytes) => {
+                log::trace!("{}.parse | crc_bytes: {:?}", self.dbgid, crc_bytes);
+                let crc = u32::from_be_bytes([crc_bytes[0], crc_bytes[1], crc_bytes[2], crc_bytes[3]]);
+                let target = FieldCrc::checksum(&buffer.as_slice()[FieldSyn::default().len()..start]);
+                match crc == target {
+                    true => Ok(((id, kind, size), end)),
+                    false => {
+                        log::warn!("{}.parse | Message {:?} discarded, checksum received: {:#010x}, calculated: {:#010x}", self.dbgid, id, crc, target);
+                        self.field.resync(buffer);
+                        Err(ParseErr::Crc { received: crc, calculated: target })
+                    }
+                }
+            }
+            None => Err(ParseErr::Pending(format!("{}.parse | Take error", self.dbgid).into())),
+        }
+    }
+    ///
+    /// Discards the current message and resynchronises on the next `Syn`
+    fn resync(&mut self, buffer: &mut ParseBuffer) {
+        self.field.resync(buffer);
+    }
+    ///
+    /// Returns the number of bytes skipped since the previous call
+    fn skipped(&mut self) -> usize {
+        self.field.skipped()
+    }
+    ///
+    /// Resets parsed fields
+    fn reset(&mut self) {
+        self.field.reset();
+    }
+}
+//
+//
+impl MessageParse<(FieldId, MessageKind, FieldSize, Bytes)> for ParseCrc {
+    ///
+    /// Extracting `Crc` field from the input bytes
+    /// - returns `Id`, `Kind`, `Size` & `Bytes` of the message if it's checksum is valid
+    /// - call this method multiple times, until the end of message
+    /// - bytes following the parsed message are kept for the next call
+    fn parse(&mut self, bytes: &[u8]) -> Result<(FieldId, MessageKind, FieldSize, Bytes), ParseErr> {
+        let mut buffer = std::mem::take(&mut self.buffer);
+        buffer.extend(bytes);
+        let result = match self.parse_field(&mut buffer) {
+            Ok(((id, kind, size), end)) => {
+                let data_end = end - self.conf.len();
+                let data = buffer.take(data_end - size.size(), data_end, end);
+                ParseField::reset(self);
+                Ok((id, kind, size, data))
+            }
+            Err(err) => Err(err),
+        };
+        self.pending = matches!(result, Err(ParseErr::Pending(_)));
+        self.buffer = buffer;
+        result
+    }
+    ///
+    /// Reads bytes directly into the buffer of the stage
+    fn read_from(&mut self, len: usize, read: &mut dyn FnMut(&mut [u8]) -> std::io::Result<usize>) -> std::io::Result<usize> {
+        self.buffer.read_from(len, read)
+    }
+    ///
+    /// Returns the number of bytes skipped since the previous call
+    fn skipped(&mut self) -> usize {
+        ParseField::skipped(self)
+    }
+    ///
+    /// Resets state to the initial
+    /// - Partially received message is discarded, bytes following the last parsed message are kept
+    fn reset(&mut self) {
+        ParseField::reset(self);
+        if std::mem::take(&mut self.pending) {
+            self.buffer.clear();
+        }
+    }
+}
@@ -1,62 +1,103 @@
-use crate::{api::message::message_kind::MessageKind, debug::dbg_id::DbgId, error::str_err::StrErr};
-use super::{fields::{FieldId, FieldSize}, message::{Bytes, MessageParse}};
+use crate::{api::message::message_kind::MessageKind, debug::dbg_id::DbgId};
+use super::{fields::{FieldId, FieldSize}, message::{Bytes, MessageParse, ParseField}, parse_buffer::ParseBuffer, parse_err::ParseErr};
 ///
 /// Extracting `Data` field from the input bytes
+/// - Owns the [ParseBuffer] shared by all stages of the parser chain
 pub struct ParseData {
     dbgid: DbgId,
-    field: Box<dyn MessageParse<(FieldId, MessageKind, FieldSize, Bytes)>>,
-    buffer: Bytes,
-    remains: Bytes,
+    field: Box<dyn ParseField<(FieldId, MessageKind, FieldSize)>>,
+    buffer: ParseBuffer,
+    pending: bool,
 }
 //
 //
 impl ParseData {
     ///
     /// Returns [ParseData] new instance
-    pub fn new(dbgid: &DbgId, field: impl MessageParse<(FieldId, MessageKind, FieldSize, Bytes)> + 'static) -> Self {
+    pub fn new(dbgid: &DbgId, field: impl ParseField<(FieldId, MessageKind, FieldSize)> + 'static) -> Self {
         Self {
             dbgid: DbgId(format!("{}/ParseData", dbgid)),
             field: Box::new(field),
-            buffer: vec![],
-            remains: vec![],
+            buffer: ParseBuffer::new(),
+            pending: false,
         }
     }
 }
 //
 //
+impl ParseField<(FieldId, MessageKind, FieldSize)> for ParseData {
+    ///
+    /// Extracting `Data` field from the `buffer`
+    /// - returns `Id`, `Kind`, `Size` & the offset of the first byte following by the `Data`
+    /// - `Data` occupies `Size` bytes preceding the returned offset
+    fn parse_field(&mut self, buffer: &mut ParseBuffer) -> Result<((FieldId, MessageKind, FieldSize), usize), ParseErr> {
+        let ((id, kind, size), start) = self.field.parse_field(buffer)?;
+        let end = start + size.size();
+        match buffer.as_slice().get(start..end) {
+            Some(data_bytes) => {
+                let dbg_bytes = if data_bytes.len() > 16 {format!("{:?}...", &data_bytes[..16])} else {format!("{:?}", data_bytes)};
+                log::trace!("{}.parse | data_bytes: {:?}", self.dbgid, dbg_bytes);
+                Ok(((id, kind, size), end))
+            }
+            None => Err(ParseErr::Pending(format!("{}.parse | Take error", self.dbgid).into())),
+        }
+    }
+    ///
+    /// Discards the current message and resynchronises on the next `Syn`
+    fn resync(&mut self, buffer: &mut ParseBuffer) {
+        self.field.resync(buffer);
+    }
+    ///
+    /// Returns the number of bytes skipped since the previous call
+    fn skipped(&mut self) -> usize {
+        self.field.skipped()
+    }
+    ///
+    /// Resets parsed fields
+    fn reset(&mut self) {
+        self.field.reset();
+    }
+}
+//
+//
 impl MessageParse<(FieldId, MessageKind, FieldSize, Bytes)> for ParseData {
     ///
     /// Extracting `Data` field from the input bytes
-    /// - returns `Id`, `Kind`, `Size` & `Bytes` following by the `Size`
+    /// - returns `Id`, `Kind`, `Size` & `Bytes` of the `Data` field
     /// - call this method multiple times, until the end of message
-    fn parse(&mut self, bytes: Bytes) -> Result<(FieldId, MessageKind, FieldSize, Bytes), StrErr> {
-        let bytes = [std::mem::take(&mut self.remains), bytes].concat();
-        match self.field.parse(bytes) {
-            Ok((id, kind, size, bytes)) => {
-                let bytes = [std::mem::take(&mut self.buffer), bytes].concat();
-                match bytes.get(..size.size()) {
-                    Some(data_bytes) => {
-                        let dbg_bytes = if data_bytes.len() > 16 {format!("{:?}...", &data_bytes[..16])} else {format!("{:?}", data_bytes)};
-                        log::trace!("{}.parse | data_bytes: {:?}", self.dbgid, dbg_bytes);
-                        if let Some(bytes) = bytes.get(size.size()..) {
-                            self.remains.extend_from_slice(bytes);
-                        }
-                        self.field.reset();
-                        Ok((id, kind, size, data_bytes.to_vec()))
-                    }
-                    None => {
-                        self.buffer.extend_from_slice(&bytes);
-                        Err(format!("{}.parse | Take error", self.dbgid).into())
-                    }
-                }
+    /// - bytes following the parsed message are kept for the next call
+    fn parse(&mut self, bytes: &[u8]) -> Result<(FieldId, MessageKind, FieldSize, Bytes), ParseErr> {
+        let mut buffer = std::mem::take(&mut self.buffer);
+        buffer.extend(bytes);
+        let result = match self.parse_field(&mut buffer) {
+            Ok(((id, kind, size), end)) => {
+                let data = buffer.take(end - size.size(), end, end);
+                ParseField::reset(self);
+                Ok((id, kind, size, data))
             }
-            Err(err) => Err(format!("{}.parse | Error: {:?}", self.dbgid, err).into())
-        }
+            Err(err) => Err(err),
+        };
+        self.pending = matches!(result, Err(ParseErr::Pending(_)));
+        self.buffer = buffer;
+        result
+    }
+    ///
+    /// Reads bytes directly into the buffer of the stage
+    fn read_from(&mut self, len: usize, read: &mut dyn FnMut(&mut [u8]) -> std::io::Result<usize>) -> std::io::Result<usize> {
+        self.buffer.read_from(len, read)
+    }
+    ///
+    /// Returns the number of bytes skipped since the previous call
+    fn skipped(&mut self) -> usize {
+        ParseField::skipped(self)
     }
     ///
     /// Resets state to the initial
+    /// - Partially received message is discarded, bytes following the last parsed message are kept
     fn reset(&mut self) {
-        self.field.reset();
-        self.buffer.clear();
+        ParseField::reset(self);
+        if std::mem::take(&mut self.pending) {
+            self.buffer.clear();
+        }
     }
 }
@@ -0,0 +1,52 @@
+use crate::error::str_err::StrErr;
+///
+/// Errors of parsing [Message](super::message::Message) from the input bytes
+pub enum ParseErr {
+    ///
+    /// More bytes are required to complete the message
+    Pending(StrErr),
+    ///
+    /// `Syn` is not found in the input bytes
+    Syn(StrErr),
+    ///
+    /// Header field can't be decoded
+    Field(StrErr),
+    ///
+    /// `Size` of the `Data` field exceeds configured maximum
+    /// - The message is discarded
+    Size { size: usize, max: usize },
+    ///
+    /// Checksum of the message doesn't match
+    /// - The message is discarded
+    Crc { received: u32, calculated: u32 },
+}
+//
+//
+impl std::fmt::Display for ParseErr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseErr::Pending(err) => write!(f, "{}", err),
+            ParseErr::Syn(err) => write!(f, "{}", err),
+            ParseErr::Field(err) => write!(f, "{}", err),
+            ParseErr::Size { size, max } => write!(f, "ParseErr | Size {} exceeds maximum {}", size, max),
+            ParseErr::Crc { received, calculated } => write!(f, "ParseErr | Checksum error, received: {:#010x}, calculated: {:#010x}", received, calculated),
+        }
+    }
+}
+//
+//
+impl std::fmt::Debug for ParseErr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+//
+//
+impl std::error::Error for ParseErr {}
+//
+//
+impl From<ParseErr> for StrErr {
+    fn from(value: ParseErr) -> Self {
+        StrErr(value.to_string())
+    }
+}
@@ -1,28 +1,87 @@
-use crate::{debug::dbg_id::DbgId, error::str_err::StrErr};
-use super::{fields::FieldId, message::{Bytes, MessageParse}};
+use crate::{debug::dbg_id::DbgId};
+use super::{fields::{FieldCodec, FieldId}, message::{Bytes, MessageParse, ParseField}, parse_buffer::ParseBuffer, parse_err::ParseErr};
 ///
 /// Extracting `Id` field from the input bytes
 pub struct ParseId {
     dbgid: DbgId,
-    conf: FieldId,
-    field: Box<dyn MessageParse<Bytes>>,
-    value: Option<FieldId>,
-    buffer: Bytes,
+    codec: FieldCodec,
+    field: Box<dyn ParseField<()>>,
+    value: Option<(FieldId, usize)>,
+    buffer: ParseBuffer,
 }
 //
 //
 impl ParseId {
     ///
     /// Returns [ParseId] new instance
-    pub fn new(dbgid: &DbgId, conf: FieldId, field: impl MessageParse<Bytes> + 'static) -> Self {
+    /// - `conf` - length of the `Id` field in bytes, see [FieldCodec::with_len]
+    /// - Panics if the length isn't supported, [MessageSchema](super::message_schema::MessageSchema) returns the error instead
+    pub fn new(dbgid: &DbgId, conf: FieldId, field: impl ParseField<()> + 'static) -> Self {
+        let dbgid = DbgId(format!("{}/ParseId", dbgid));
+        let codec = match FieldCodec::with_len(conf.0) {
+            Ok(codec) => codec,
+            Err(err) => panic!("{}.new | {}", dbgid, err),
+        };
         Self {
-            dbgid: DbgId(format!("{}/ParseId", dbgid)),
-            conf,
+            dbgid,
+            codec,
             field: Box::new(field),
             value: None,
-            buffer: vec![],
+            buffer: ParseBuffer::new(),
         }
     }
+    ///
+    /// Returns [ParseId] with specified encoding of the `Id` field
+    pub fn with_codec(mut self, codec: FieldCodec) -> Self {
+        self.codec = codec;
+        self
+    }
+}
+//
+//
+impl ParseField<FieldId> for ParseId {
+    ///
+    /// Extracting `Id` field from the `buffer`
+    /// - returns `Id` & the offset of the first byte following by the `Id`
+    fn parse_field(&mut self, buffer: &mut ParseBuffer) -> Result<(FieldId, usize), ParseErr> {
+        if let Some((id, end)) = &self.value {
+            return Ok((id.clone(), *end));
+        }
+        let (_, start) = self.field.parse_field(buffer)?;
+        let bytes = buffer.as_slice().get(start..).unwrap_or_default();
+        match self.codec.decode(bytes) {
+            Ok(Some((id, len))) => {
+                log::trace!("{}.parse | id: {:?}", self.dbgid, id);
+                let id = FieldId(id);
+                let end = start + len;
+                self.value = Some((id.clone(), end));
+                Ok((id, end))
+            }
+            Ok(None) => Err(ParseErr::Pending(format!("{}.parse | Take error", self.dbgid).into())),
+            Err(err) => {
+                log::warn!("{}.parse | Message discarded, Id error: {:?}", self.dbgid, err);
+                self.field.resync(buffer);
+                Err(ParseErr::Field(format!("{}.parse | Parse error: {:#?}", self.dbgid, err).into()))
+            }
+        }
+    }
+    ///
+    /// Discards the current message and resynchronises on the next `Syn`
+    fn resync(&mut self, buffer: &mut ParseBuffer) {
+        self.value = None;
+        self.field.resync(buffer);
+    }
+    ///
+    /// Returns the number of bytes skipped since the previous call
+    fn skipped(&mut self) -> usize {
+        self.field.skipped()
+    }
+    ///
+    /// Resets parsed fields
+    fn reset(&mut self) {
+        self.field.reset();
+        self.value = None;
+    }
 }
 //
 //
@@ -31,45 +90,27 @@ impl MessageParse<(FieldId, Bytes)> for ParseId {
     /// Extracting `Id` field from the input bytes
     /// - returns Id & bytes following by the `Id`
     /// - call this method multiple times, until the end of message
-    fn parse(&mut self, bytes: Bytes) -> Result<(FieldId, Bytes), StrErr> {
-        match self.field.parse(bytes) {
-            Ok(bytes) => {
-                let bytes = [std::mem::take(&mut self.buffer), bytes].concat();
-                match &self.value {
-                    Some(id) => Ok((id.to_owned(), bytes)),
-                    None => {
-                        match bytes.get(..self.conf.len()) {
-                            Some(id_bytes) => {
-                                let dbg_bytes = if id_bytes.len() > 16 {format!("{:?}...", &id_bytes[..16])} else {format!("{:?}", id_bytes)};
-                                log::trace!("{}.parse | id_bytes: {:?}", self.dbgid, dbg_bytes);
-                                match id_bytes.try_into() {
-                                    Ok(id_bytes) => {
-                                        let id= u32::from_be_bytes(id_bytes);
-                                        self.value = Some(FieldId(id));
-                                        Ok((FieldId(id), bytes[self.conf.len()..].to_vec()))
-                                    },
-                                    Err(err) => {
-                                        self.buffer = id_bytes.into();
-                                        Err(format!("{}.parse | Parse error: {:#?}", self.dbgid, err).into())
-                                    }
-                                }
-                            }
-                            None => {
-                                self.buffer.extend_from_slice(&bytes);
-                                Err(format!("{}.parse | Take error", self.dbgid).into())
-                            }
-                        }
-                    }
-                }
-            }
-            Err(err) => Err(format!("{}.parse | Error: {:?}", self.dbgid, err).into())
-        }
+    fn parse(&mut self, bytes: &[u8]) -> Result<(FieldId, Bytes), ParseErr> {
+        let mut buffer = std::mem::take(&mut self.buffer);
+        buffer.extend(bytes);
+        let result = self.parse_field(&mut buffer).map(|(id, end)| (id, buffer.following(end)));
+        self.buffer = buffer;
+        result
+    }
+    ///
+    /// Reads bytes directly into the buffer of the stage
+    fn read_from(&mut self, len: usize, read: &mut dyn FnMut(&mut [u8]) -> std::io::Result<usize>) -> std::io::Result<usize> {
+        self.buffer.read_from(len, read)
+    }
+    ///
+    /// Returns the number of bytes skipped since the previous call
+    fn skipped(&mut self) -> usize {
+        ParseField::skipped(self)
     }
     ///
     /// Resets state to the initial
     fn reset(&mut self) {
-        self.field.reset();
-        self.value = None;
+        ParseField::reset(self);
         self.buffer.clear();
     }
 }
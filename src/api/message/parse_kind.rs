@@ -1,75 +1,107 @@
-use crate::{api::message::{from_bytes::FromBytes, message_kind::MessageKind}, debug::dbg_id::DbgId, error::str_err::StrErr};
-use super::{fields::{FieldId, FieldKind}, message::{Bytes, MessageParse}};
+use crate::{api::message::{from_bytes::FromBytes, message_kind::MessageKind}, debug::dbg_id::DbgId};
+use super::{fields::{FieldId, FieldKind}, message::{Bytes, MessageParse, ParseField}, parse_buffer::ParseBuffer, parse_err::ParseErr};
 ///
 /// Extracting `Kind` field from the input bytes
 pub struct ParseKind {
     dbgid: DbgId,
     conf: FieldKind,
-    field: Box<dyn MessageParse<(FieldId, Bytes)>>,
-    value: Option<MessageKind>,
-    buffer: Bytes,
+    field: Box<dyn ParseField<FieldId>>,
+    value: Option<(FieldId, MessageKind, usize)>,
+    buffer: ParseBuffer,
 }
 //
 //
 impl ParseKind {
     ///
     /// Returns [ParseKind] new instance
-    pub fn new(dbgid: &DbgId, conf: FieldKind, field: impl MessageParse<(FieldId, Bytes)> + 'static) -> Self {
+    pub fn new(dbgid: &DbgId, conf: FieldKind, field: impl ParseField<FieldId> + 'static) -> Self {
         Self {
             dbgid: DbgId(format!("{}/ParseKind", dbgid)),
             conf,
             field: Box::new(field),
             value: None,
-            buffer: vec![],
+            buffer: ParseBuffer::new(),
         }
     }
 }
 //
 //
-impl MessageParse<(FieldId, MessageKind, Bytes)> for ParseKind {
+impl ParseField<(FieldId, MessageKind)> for ParseKind {
     ///
-    /// Extracting `Kind` field from the input bytes
-    /// - returns `Id`, `Kind` & `Bytes` following by the `Kind`
-    /// - call this method multiple times, until the end of message
-    fn parse(&mut self, bytes: Bytes) -> Result<(FieldId, MessageKind, Bytes), StrErr> {
-        match self.field.parse(bytes) {
-            Ok((id, bytes)) => {
-                let bytes = [std::mem::take(&mut self.buffer), bytes].concat();
-                match &self.value {
-                    Some(kind) => Ok((id.clone(), kind.clone(), bytes)),
-                    None => {
-                        match bytes.get(..self.conf.len()) {
-                            Some(kind_bytes) => {
-                                let dbg_bytes = if kind_bytes.len() > 16 {format!("{:?}...", &kind_bytes[..16])} else {format!("{:?}", kind_bytes)};
-                                log::trace!("{}.parse | bytes: {:?}", self.dbgid, dbg_bytes);
-                                match MessageKind::from_bytes(kind_bytes) {
-                                    Ok(kind) => {
-                                        log::trace!("{}.parse | kind: {:?}", self.dbgid, kind);
-                                        self.value = Some(kind.clone());
-                                        Ok((id, kind, bytes[self.conf.len()..].to_vec()))
-                                    },
-                                    Err(err) => {
-                                        self.buffer = kind_bytes.into();
-                                        Err(format!("{}.parse | Parse error: {:#?}", self.dbgid, err).into())
-                                    }
-                                }
-                            }
-                            None => {
-                                self.buffer.extend_from_slice(&bytes);
-                                Err(format!("{}.parse | Take error", self.dbgid).into())
-                            }
-                        }
+    /// Extracting `Kind` field from the `buffer`
+    /// - returns `Id`, `Kind` & the offset of the first byte following by the `Kind`
+    fn parse_field(&mut self, buffer: &mut ParseBuffer) -> Result<((FieldId, MessageKind), usize), ParseErr> {
+        if let Some((id, kind, end)) = &self.value {
+            return Ok(((id.clone(), kind.clone()), *end));
+        }
+        let (id, start) = self.field.parse_field(buffer)?;
+        let end = start + self.conf.len();
+        match buffer.as_slice().get(start..end) {
+            Some(kind_bytes) => {
+                let dbg_bytes = if kind_bytes.len() > 16 {format!("{:?}...", &kind_bytes[..16])} else {format!("{:?}", kind_bytes)};
+                log::trace!("{}.parse | bytes: {:?}", self.dbgid, dbg_bytes);
+                match MessageKind::from_bytes(kind_bytes) {
+                    Ok(kind) => {
+                        log::trace!("{}.parse | kind: {:?}", self.dbgid, kind);
+                        self.value = Some((id.clone(), kind.clone(), end));
+                        Ok(((id, kind), end))
+                    }
+                    Err(err) => {
+                        log::warn!("{}.parse | Message {:?} discarded, Kind error: {:?}", self.dbgid, id, err);
+                        self.field.resync(buffer);
+                        Err(ParseErr::Field(format!("{}.parse | Parse error: {:#?}", self.dbgid, err).into()))
                     }
                 }
             }
-            Err(err) => Err(format!("{}.parse | Error: {:?}", self.dbgid, err).into())
+            None => Err(ParseErr::Pending(format!("{}.parse | Take error", self.dbgid).into())),
         }
     }
     ///
-    /// Resets state to the initial
+    /// Discards the current message and resynchronises on the next `Syn`
+    fn resync(&mut self, buffer: &mut ParseBuffer) {
+        self.value = None;
+        self.field.resync(buffer);
+    }
+    ///
+    /// Returns the number of bytes skipped since the previous call
+    fn skipped(&mut self) -> usize {
+        self.field.skipped()
+    }
+    ///
+    /// Resets parsed fields
     fn reset(&mut self) {
         self.field.reset();
         self.value = None;
+    }
+}
+//
+//
+impl MessageParse<(FieldId, MessageKind, Bytes)> for ParseKind {
+    ///
+    /// Extracting `Kind` field from the input bytes
+    /// - returns `Id`, `Kind` & `Bytes` following by the `Kind`
+    /// - call this method multiple times, until the end of message
+    fn parse(&mut self, bytes: &[u8]) -> Result<(FieldId, MessageKind, Bytes), ParseErr> {
+        let mut buffer = std::mem::take(&mut self.buffer);
+        buffer.extend(bytes);
+        let result = self.parse_field(&mut buffer).map(|((id, kind), end)| (id, kind, buffer.following(end)));
+        self.buffer = buffer;
+        result
+    }
+    ///
+    /// Reads bytes directly into the buffer of the stage
+    fn read_from(&mut self, len: usize, read: &mut dyn FnMut(&mut [u8]) -> std::io::Result<usize>) -> std::io::Result<usize> {
+        self.buffer.read_from(len, read)
+    }
+    ///
+    /// Returns the number of bytes skipped since the previous call
+    fn skipped(&mut self) -> usize {
+        ParseField::skipped(self)
+    }
+    ///
+    /// Resets state to the initial
+    fn reset(&mut self) {
+        ParseField::reset(self);
         self.buffer.clear();
     }
 }
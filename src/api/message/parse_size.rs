@@ -1,75 +1,130 @@
-use crate::{api::message::message_kind::MessageKind, debug::dbg_id::DbgId, error::str_err::StrErr};
-use super::{fields::{FieldId, FieldSize}, message::{Bytes, MessageParse}};
+use crate::{api::message::message_kind::MessageKind, debug::dbg_id::DbgId};
+use super::{fields::{FieldCodec, FieldId, FieldSize}, message::{Bytes, MessageParse, ParseField}, parse_buffer::ParseBuffer, parse_err::ParseErr};
 ///
 /// Extracting `Size` field from the input bytes
 pub struct ParseSize {
     dbgid: DbgId,
-    conf: FieldSize,
-    field: Box<dyn MessageParse<(FieldId, MessageKind, Bytes)>>,
-    value: Option<FieldSize>,
-    buffer: Bytes,
+    codec: FieldCodec,
+    field: Box<dyn ParseField<(FieldId, MessageKind)>>,
+    value: Option<(FieldId, MessageKind, FieldSize, usize)>,
+    buffer: ParseBuffer,
+    max: usize,
 }
 //
 //
 impl ParseSize {
     ///
     /// Returns [ParseSize] new instance
-    pub fn new(dbgid: &DbgId, conf: FieldSize, field: impl MessageParse<(FieldId, MessageKind, Bytes)> + 'static) -> Self {
+    /// - `conf` - length of the `Size` field in bytes, see [FieldCodec::with_len]
+    /// - Panics if the length isn't supported, [MessageSchema](super::message_schema::MessageSchema) returns the error instead
+    pub fn new(dbgid: &DbgId, conf: FieldSize, field: impl ParseField<(FieldId, MessageKind)> + 'static) -> Self {
+        let dbgid = DbgId(format!("{}/ParseSize", dbgid));
+        let codec = match FieldCodec::with_len(conf.0) {
+            Ok(codec) => codec,
+            Err(err) => panic!("{}.new | {}", dbgid, err),
+        };
         Self {
-            dbgid: DbgId(format!("{}/ParseSize", dbgid)),
-            conf,
+            dbgid,
+            codec,
             field: Box::new(field),
             value: None,
-            buffer: vec![],
+            buffer: ParseBuffer::new(),
+            max: u32::MAX as usize,
         }
     }
+    ///
+    /// Returns [ParseSize] with specified maximum `Size` of the `Data` field in bytes (default u32::MAX)
+    /// - Message with greater `Size` is discarded, [ParseErr::Size] returned
+    /// - Bytes following the `Syn` of the discarded message are searched for the next `Syn`
+    pub fn with_max(mut self, max: usize) -> Self {
+        self.max = max;
+        self
+    }
+    ///
+    /// Returns [ParseSize] with specified encoding of the `Size` field
+    pub fn with_codec(mut self, codec: FieldCodec) -> Self {
+        self.codec = codec;
+        self
+    }
 }
 //
 //
-impl MessageParse<(FieldId, MessageKind, FieldSize, Bytes)> for ParseSize {
+impl ParseField<(FieldId, MessageKind, FieldSize)> for ParseSize {
     ///
-    /// Extracting `Size` field from the input bytes
-    /// - returns `Id`, `Kind`, `Size` & `Bytes` following by the `Size`
-    /// - call this method multiple times, until the end of message
-    fn parse(&mut self, bytes: Bytes) -> Result<(FieldId, MessageKind, FieldSize, Bytes), StrErr> {
-        match self.field.parse(bytes) {
-            Ok((id, kind, bytes)) => {
-                let bytes = [std::mem::take(&mut self.buffer), bytes].concat();
-                match &self.value {
-                    Some(size) => Ok((id.clone(), kind.clone(), size.clone(), bytes)),
-                    None => {
-                        match bytes.get(..self.conf.len()) {
-                            Some(size_bytes) => {
-                                let dbg_bytes = if size_bytes.len() > 16 {format!("{:?}...", &size_bytes[..16])} else {format!("{:?}", size_bytes)};
-                                log::trace!("{}.parse | size_bytes: {:?}", self.dbgid, dbg_bytes);
-                                match size_bytes.try_into() {
-                                    Ok(size_bytes) => {
-                                        let size= u32::from_be_bytes(size_bytes);
-                                        self.value = Some(FieldSize(size));
-                                        Ok((id, kind, FieldSize(size), bytes[self.conf.len()..].to_vec()))
-                                    },
-                                    Err(err) => {
-                                        self.buffer = size_bytes.into();
-                                        Err(format!("{}.parse | Parse error: {:#?}", self.dbgid, err).into())
-                                    }
-                                }
-                            }
-                            None => {
-                                self.buffer.extend_from_slice(&bytes);
-                                Err(format!("{}.parse | Take error", self.dbgid).into())
-                            }
-                        }
-                    }
+    /// Extracting `Size` field from the `buffer`
+    /// - returns `Id`, `Kind`, `Size` & the offset of the first byte following by the `Size`
+    fn parse_field(&mut self, buffer: &mut ParseBuffer) -> Result<((FieldId, MessageKind, FieldSize), usize), ParseErr> {
+        if let Some((id, kind, size, end)) = &self.value {
+            return Ok(((id.clone(), kind.clone(), size.clone()), *end));
+        }
+        let ((id, kind), start) = self.field.parse_field(buffer)?;
+        let bytes = buffer.as_slice().get(start..).unwrap_or_default();
+        match self.codec.decode(bytes) {
+            Ok(Some((size, len))) => {
+                log::trace!("{}.parse | size: {:?}", self.dbgid, size);
+                if size as usize > self.max {
+                    log::warn!("{}.parse | Message {:?} discarded, Size {} exceeds maximum {}", self.dbgid, id, size, self.max);
+                    self.field.resync(buffer);
+                    return Err(ParseErr::Size { size: size as usize, max: self.max });
                 }
+                let end = start + len;
+                self.value = Some((id.clone(), kind.clone(), FieldSize(size), end));
+                Ok(((id, kind, FieldSize(size)), end))
+            }
+            Ok(None) => Err(ParseErr::Pending(format!("{}.parse | Take error", self.dbgid).into())),
+            Err(err) => {
+                log::warn!("{}.parse | Message {:?} discarded, Size error: {:?}", self.dbgid, id, err);
+                self.field.resync(buffer);
+                Err(ParseErr::Field(format!("{}.parse | Parse error: {:#?}", self.dbgid, err).into()))
             }
-            Err(err) => Err(format!("{}.parse | Error: {:?}", self.dbgid, err).into())
         }
     }
     ///
-    /// Resets state to the initial
+    /// Discards the current message and resynchronises on the next `Syn`
+    fn resync(&mut self, buffer: &mut ParseBuffer) {
+        self.value = None;
+        self.field.resync(buffer);
+    }
+    ///
+    /// Returns the number of bytes skipped since the previous call
+    fn skipped(&mut self) -> usize {
+        self.field.skipped()
+    }
+    ///
+    /// Resets parsed fields
     fn reset(&mut self) {
         self.field.reset();
         self.value = None;
+    }
+}
+//
+//
+impl MessageParse<(FieldId, MessageKind, FieldSize, Bytes)> for ParseSize {
+    ///
+    /// Extracting `Size` field from the input bytes
+    /// - returns `Id`, `Kind`, `Size` & `Bytes` following by the `Size`
+    /// - call this method multiple times, until the end of message
+    fn parse(&mut self, bytes: &[u8]) -> Result<(FieldId, MessageKind, FieldSize, Bytes), ParseErr> {
+        let mut buffer = std::mem::take(&mut self.buffer);
+        buffer.extend(bytes);
+        let result = self.parse_field(&mut buffer).map(|((id, kind, size), end)| (id, kind, size, buffer.following(end)));
+        self.buffer = buffer;
+        result
+    }
+    ///
+    /// Reads bytes directly into the buffer of the stage
+    fn read_from(&mut self, len: usize, read: &mut dyn FnMut(&mut [u8]) -> std::io::Result<usize>) -> std::io::Result<usize> {
+        self.buffer.read_from(len, read)
+    }
+    ///
+    /// Returns the number of bytes skipped since the previous call
+    fn skipped(&mut self) -> usize {
+        ParseField::skipped(self)
+    }
+    ///
+    /// Resets state to the initial
+    fn reset(&mut self) {
+        ParseField::reset(self);
         self.buffer.clear();
     }
 }
@@ -1,12 +1,15 @@
-use crate::{debug::dbg_id::DbgId, error::str_err::StrErr};
-use super::{fields::FieldSyn, message::{Bytes, MessageParse}};
+use crate::debug::dbg_id::DbgId;
+use super::{fields::FieldSyn, message::{MessageParse, ParseField}, parse_buffer::ParseBuffer, parse_err::ParseErr};
 ///
 /// Extracting `Syn` symbol from the input bytes
 /// - Used to identify a start of the message
+/// - Bytes preceding the `Syn` are skipped
 pub struct ParseSyn {
     dbgid: DbgId,
     conf: FieldSyn,
-    value: Option<()>,
+    value: Option<usize>,
+    buffer: ParseBuffer,
+    skipped: usize,
 }
 //
 //
@@ -18,42 +21,89 @@ impl ParseSyn {
             dbgid: DbgId(format!("{}/ParseSyn", dbgid)),
             conf,
             value: None,
+            buffer: ParseBuffer::new(),
+            skipped: 0,
         }
     }
 }
 //
 //
-impl MessageParse<Vec<u8>> for ParseSyn {
+impl ParseField<()> for ParseSyn {
     ///
-    /// Extracting `Syn` symbol from the input bytes
-    /// - returns bytes following by the `Syn`
-    /// - call this method multiple times, until the end of message
-    fn parse(&mut self, bytes: Bytes) -> Result<Vec<u8>, StrErr> {
+    /// Extracting `Syn` symbol from the beginning of the `buffer`
+    /// - Bytes preceding the `Syn` are consumed from the `buffer`
+    /// - returns the offset of the first byte following by the `Syn`
+    fn parse_field(&mut self, buffer: &mut ParseBuffer) -> Result<((), usize), ParseErr> {
         match self.value {
-            Some(_) => Ok(bytes),
+            Some(end) => Ok(((), end)),
             None => {
-                match bytes.iter().position(|b| *b == self.conf.0) {
+                match buffer.as_slice().iter().position(|b| *b == self.conf.0) {
                     Some(pos) => {
-                        // log::trace!("{} | bytes: {:?}", self.dbgid, bytes);
-                        match bytes.get((pos + 1)..) {
-                            Some(bytes) => {
-                                self.value = Some(());
-                                Ok(bytes.to_owned())
-                            }
-                            None => Ok(vec![]),
-                        }
+                        self.skipped += pos;
+                        buffer.consume(pos);
+                        let end = self.conf.len();
+                        self.value = Some(end);
+                        Ok(((), end))
                     }
                     None => {
+                        let bytes = buffer.as_slice();
                         let dbg_bytes = if bytes.len() > 16 { format!("{:?}...", &bytes[..16]) } else { format!("{:?}", bytes) };
-                        Err(format!("{}.parse | Syn not found in message: {:?}", self.dbgid, dbg_bytes ).into())
+                        let err = ParseErr::Syn(format!("{}.parse | Syn not found in message: {:?}", self.dbgid, dbg_bytes ).into());
+                        self.skipped += bytes.len();
+                        buffer.consume(bytes.len());
+                        Err(err)
                     }
                 }
             }
         }
     }
     ///
+    /// Discards the current message, the `Syn` is consumed from the `buffer` and counted as skipped
+    fn resync(&mut self, buffer: &mut ParseBuffer) {
+        if let Some(end) = self.value.take() {
+            self.skipped += end;
+            buffer.consume(end);
+        }
+    }
+    ///
+    /// Returns the number of bytes skipped since the previous call
+    fn skipped(&mut self) -> usize {
+        std::mem::take(&mut self.skipped)
+    }
+    ///
+    /// Resets parsed `Syn`
+    fn reset(&mut self) {
+        self.value = None;
+    }
+}
+//
+//
+impl MessageParse<Vec<u8>> for ParseSyn {
+    ///
+    /// Extracting `Syn` symbol from the input bytes
+    /// - returns bytes following by the `Syn`
+    /// - call this method multiple times, until the end of message
+    fn parse(&mut self, bytes: &[u8]) -> Result<Vec<u8>, ParseErr> {
+        let mut buffer = std::mem::take(&mut self.buffer);
+        buffer.extend(bytes);
+        let result = self.parse_field(&mut buffer).map(|(_, end)| buffer.following(end));
+        self.buffer = buffer;
+        result
+    }
+    ///
+    /// Reads bytes directly into the buffer of the stage
+    fn read_from(&mut self, len: usize, read: &mut dyn FnMut(&mut [u8]) -> std::io::Result<usize>) -> std::io::Result<usize> {
+        self.buffer.read_from(len, read)
+    }
+    ///
+    /// Returns the number of bytes skipped since the previous call
+    fn skipped(&mut self) -> usize {
+        ParseField::skipped(self)
+    }
+    ///
     /// Resets state to the initial
     fn reset(&mut self) {
-        self.value = None;        
+        ParseField::reset(self);
+        self.buffer.clear();
     }
 }
@@ -0,0 +1,106 @@
+use crate::{
+    api::message::{fields::FieldId, from_bytes::FromBytes, message_kind::MessageKind, msg_kind::MsgKind},
+    error::str_err::StrErr,
+};
+///
+/// Capabilities of the peer exchanged by the handshake on connect
+/// - Sent as [MsgKind::Struct] with reserved [Handshake::ID]:
+///     - `version` - [MsgKind::U16], protocol version
+///     - `kinds` - [MsgKind::Bytes], supported [MessageKind] codes
+///     - `max_size` - [MsgKind::U32], maximum `Size` of the `Data` field accepted
+/// - Connecting side sends it's handshake first, accepting side answers with it's own
+#[derive(Debug, Clone, PartialEq)]
+pub struct Handshake {
+    pub version: u16,
+    pub kinds: Vec<MessageKind>,
+    pub max_size: u32,
+}
+//
+//
+impl Handshake {
+    ///
+    /// `Id` of the handshake message, never used by the regular messages
+    pub const ID: FieldId = FieldId(0);
+    ///
+    /// Current protocol version
+    pub const VERSION: u16 = 1;
+    ///
+    /// The oldest protocol version supported
+    pub const MIN_VERSION: u16 = 1;
+    ///
+    /// Returns [Handshake] of the current protocol version
+    /// - `kinds` - all [MessageKind]'s known by this implementation
+    /// - `max_size` - u32::MAX
+    pub fn new() -> Self {
+        Self {
+            version: Self::VERSION,
+            kinds: (0..=u8::MAX).filter_map(|code| MessageKind::from_bytes(&[code]).ok()).collect(),
+            max_size: u32::MAX,
+        }
+    }
+    ///
+    /// Returns [Handshake] with specified supported `kinds`
+    pub fn with_kinds(mut self, kinds: Vec<MessageKind>) -> Self {
+        self.kinds = kinds;
+        self
+    }
+    ///
+    /// Returns [Handshake] with specified maximum `Size` of the `Data` field accepted
+    pub fn with_max_size(mut self, max_size: u32) -> Self {
+        self.max_size = max_size;
+        self
+    }
+    ///
+    /// Returns the message value to be sent to the peer
+    pub fn to_msg_kind(&self) -> MsgKind {
+        MsgKind::Struct(vec![
+            ("version".to_owned(), MsgKind::U16(self.version)),
+            ("kinds".to_owned(), MsgKind::Bytes(self.kinds.iter().flat_map(|kind| kind.to_bytes().to_vec()).collect())),
+            ("max_size".to_owned(), MsgKind::U32(self.max_size)),
+        ])
+    }
+    ///
+    /// Returns [Handshake] received from the peer
+    /// - Unknown fields are ignored, unknown kind codes are skipped
+    pub fn from_msg_kind(value: &MsgKind) -> Result<Self, StrErr> {
+        let fields = match value {
+            MsgKind::Struct(fields) => fields,
+            _ => return Err(format!("Handshake.from_msg_kind | Expected Struct, but received {:?}", value.kind()).into()),
+        };
+        let field = |name: &str| fields.iter().find(|(field, _)| field == name).map(|(_, value)| value);
+        let version = match field("version") {
+            Some(MsgKind::U16(version)) => *version,
+            value => return Err(format!("Handshake.from_msg_kind | Invalid version: {:?}", value).into()),
+        };
+        let kinds = match field("kinds") {
+            Some(MsgKind::Bytes(codes)) => codes.iter().filter_map(|code| MessageKind::from_bytes(&[*code]).ok()).collect(),
+            value => return Err(format!("Handshake.from_msg_kind | Invalid kinds: {:?}", value).into()),
+        };
+        let max_size = match field("max_size") {
+            Some(MsgKind::U32(max_size)) => *max_size,
+            value => return Err(format!("Handshake.from_msg_kind | Invalid max_size: {:?}", value).into()),
+        };
+        Ok(Self { version, kinds, max_size })
+    }
+    ///
+    /// Returns values supported by both sides
+    /// - the lower `version`, the common `kinds` and the lower `max_size`
+    /// - Returns error if the peer `version` is older then [Handshake::MIN_VERSION]
+    pub fn negotiate(&self, peer: &Handshake) -> Result<Handshake, StrErr> {
+        if peer.version < Self::MIN_VERSION {
+            return Err(format!("Handshake.negotiate | Incompatible protocol version, local: {}, peer: {}", self.version, peer.version).into());
+        }
+        Ok(Self {
+            version: self.version.min(peer.version),
+            kinds: self.kinds.iter().filter(|kind| peer.kinds.contains(kind)).cloned().collect(),
+            max_size: self.max_size.min(peer.max_size),
+        })
+    }
+}
+//
+//
+impl Default for Handshake {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,69 @@
+use std::time::Duration;
+use crate::api::message::{fields::FieldId, message_kind::MessageKind};
+///
+/// Keepalive of the idle connection
+/// - After `idle` period without any received bytes the `Empty` ping is sent with the greatest `Id`, see [Heartbeat::ping_id]
+/// - The peer answers with the `Empty` pong with the `Id` next to the ping's one, see [Heartbeat::pong_id]
+/// - If no pong received in the `deadline` the connection is closed
+/// - Heartbeat frames are never returned to the application
+#[derive(Debug, Clone, PartialEq)]
+pub struct Heartbeat {
+    pub idle: Duration,
+    pub deadline: Duration,
+}
+//
+//
+impl Heartbeat {
+    ///
+    /// `Id` of the ping message of the 4 bytes `Id` field, never used by the regular messages
+    /// - See [Heartbeat::ping_id] for the narrower `Id` field
+    pub const PING: FieldId = FieldId(u32::MAX);
+    ///
+    /// `Id` of the pong message of the 4 bytes `Id` field, never used by the regular messages
+    /// - See [Heartbeat::pong_id] for the narrower `Id` field
+    pub const PONG: FieldId = FieldId(u32::MAX - 1);
+    ///
+    /// Returns [Heartbeat] new instance
+    /// - `idle` - period without received bytes, after which the ping is sent
+    /// - `deadline` - time to wait for the pong
+    pub fn new(idle: Duration, deadline: Duration) -> Self {
+        Self { idle, deadline }
+    }
+    ///
+    /// Returns the period of the connection state checks
+    pub fn poll(&self) -> Duration {
+        self.idle.min(self.deadline)
+    }
+    ///
+    /// Returns `Id` of the ping message, the greatest value of the `Id` field
+    /// - `max_id` - the greatest value can be encoded in the `Id` field, see [Message::max_id](crate::api::message::message::Message::max_id)
+    pub fn ping_id(max_id: u32) -> FieldId {
+        FieldId(max_id)
+    }
+    ///
+    /// Returns `Id` of the pong message, the value next to the [Heartbeat::ping_id]
+    /// - `max_id` - the greatest value can be encoded in the `Id` field
+    pub fn pong_id(max_id: u32) -> FieldId {
+        FieldId(max_id.saturating_sub(1))
+    }
+    ///
+    /// Returns true if the message is the ping of the 4 bytes `Id` field
+    pub fn is_ping(id: &FieldId, kind: &MessageKind) -> bool {
+        Self::is_ping_within(id, kind, u32::MAX)
+    }
+    ///
+    /// Returns true if the message is the pong of the 4 bytes `Id` field
+    pub fn is_pong(id: &FieldId, kind: &MessageKind) -> bool {
+        Self::is_pong_within(id, kind, u32::MAX)
+    }
+    ///
+    /// Returns true if the message is the ping of the `Id` field holding values up to `max_id`
+    pub fn is_ping_within(id: &FieldId, kind: &MessageKind, max_id: u32) -> bool {
+        *id == Self::ping_id(max_id) && *kind == MessageKind::Empty
+    }
+    ///
+    /// Returns true if the message is the pong of the `Id` field holding values up to `max_id`
+    pub fn is_pong_within(id: &FieldId, kind: &MessageKind, max_id: u32) -> bool {
+        *id == Self::pong_id(max_id) && *kind == MessageKind::Empty
+    }
+}
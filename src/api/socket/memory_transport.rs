@@ -0,0 +1,144 @@
+use std::{collections::VecDeque, sync::{mpsc::{self, Receiver, Sender}, Arc, Condvar, Mutex}, time::{Duration, Instant}};
+use super::transport::{Endpoint, Transport};
+///
+/// One direction of the [MemoryTransport]
+#[derive(Debug, Default)]
+struct Pipe {
+    state: Mutex<PipeState>,
+    ready: Condvar,
+}
+///
+/// Bytes written but not read yet, `closed` - no more bytes will be written
+#[derive(Debug, Default)]
+struct PipeState {
+    bytes: VecDeque<u8>,
+    closed: bool,
+}
+//
+//
+impl Pipe {
+    ///
+    /// Marks the pipe closed, wakes up the waiting reader
+    fn close(&self) {
+        self.state.lock().unwrap_or_else(|err| err.into_inner()).closed = true;
+        self.ready.notify_all();
+    }
+}
+///
+/// In-memory duplex [Transport], both ends are returned by [MemoryTransport::pair]
+/// - Used to connect the client and the server in the same process without binding ports
+/// - Writing never blocks, read blocks until bytes available, read timeout expired or the peer closed
+#[derive(Debug)]
+pub struct MemoryTransport {
+    input: Arc<Pipe>,
+    output: Arc<Pipe>,
+    read_timeout: Mutex<Option<Duration>>,
+}
+//
+//
+impl MemoryTransport {
+    ///
+    /// Returns two connected ends of the duplex pipe
+    pub fn pair() -> (MemoryTransport, MemoryTransport) {
+        let forward = Arc::new(Pipe::default());
+        let backward = Arc::new(Pipe::default());
+        (
+            MemoryTransport { input: backward.clone(), output: forward.clone(), read_timeout: Mutex::new(None) },
+            MemoryTransport { input: forward, output: backward, read_timeout: Mutex::new(None) },
+        )
+    }
+}
+//
+//
+impl Transport for MemoryTransport {
+    fn read(&self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let timeout = *self.read_timeout.lock().unwrap_or_else(|err| err.into_inner());
+        let deadline = timeout.map(|timeout| Instant::now() + timeout);
+        let mut state = self.input.state.lock().unwrap_or_else(|err| err.into_inner());
+        loop {
+            if !state.bytes.is_empty() {
+                let len = buf.len().min(state.bytes.len());
+                for (dst, src) in buf.iter_mut().zip(state.bytes.drain(..len)) {
+                    *dst = src;
+                }
+                return Ok(len);
+            }
+            if state.closed || buf.is_empty() {
+                return Ok(0);
+            }
+            state = match deadline {
+                Some(deadline) => {
+                    let remaining = deadline.saturating_duration_since(Instant::now());
+                    if remaining.is_zero() {
+                        return Err(std::io::Error::new(std::io::ErrorKind::WouldBlock, "MemoryTransport.read | Read timeout"));
+                    }
+                    self.input.ready.wait_timeout(state, remaining).unwrap_or_else(|err| err.into_inner()).0
+                }
+                None => self.input.ready.wait(state).unwrap_or_else(|err| err.into_inner()),
+            };
+        }
+    }
+    fn write_all(&self, bytes: &[u8]) -> std::io::Result<()> {
+        let mut state = self.output.state.lock().unwrap_or_else(|err| err.into_inner());
+        if state.closed {
+            return Err(std::io::Error::new(std::io::ErrorKind::BrokenPipe, "MemoryTransport.write_all | Pipe closed"));
+        }
+        state.bytes.extend(bytes);
+        self.output.ready.notify_all();
+        Ok(())
+    }
+    fn set_read_timeout(&self, timeout: Option<Duration>) -> std::io::Result<()> {
+        if timeout == Some(Duration::ZERO) {
+            return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, "MemoryTransport.set_read_timeout | Zero timeout"));
+        }
+        *self.read_timeout.lock().unwrap_or_else(|err| err.into_inner()) = timeout;
+        Ok(())
+    }
+    fn set_write_timeout(&self, _timeout: Option<Duration>) -> std::io::Result<()> {
+        Ok(())
+    }
+    fn shutdown(&self) -> std::io::Result<()> {
+        self.input.close();
+        self.output.close();
+        Ok(())
+    }
+}
+//
+//
+impl Drop for MemoryTransport {
+    fn drop(&mut self) {
+        self.input.close();
+        self.output.close();
+    }
+}
+///
+/// [Endpoint] opening the [MemoryTransport] connections
+/// - The accepting ends of the connections are received from the [Receiver] returned by [MemoryEndpoint::new]
+#[derive(Debug, Clone)]
+pub struct MemoryEndpoint {
+    name: String,
+    accept: Sender<MemoryTransport>,
+}
+//
+//
+impl MemoryEndpoint {
+    ///
+    /// Returns [MemoryEndpoint] and the [Receiver] of the accepted connections
+    pub fn new(name: impl Into<String>) -> (Self, Receiver<MemoryTransport>) {
+        let (send, recv) = mpsc::channel();
+        (Self { name: name.into(), accept: send }, recv)
+    }
+}
+//
+//
+impl Endpoint for MemoryEndpoint {
+    fn connect(&self, _timeout: Duration) -> std::io::Result<Arc<dyn Transport>> {
+        let (local, remote) = MemoryTransport::pair();
+        self.accept.send(remote)
+            .map(|_| Arc::new(local) as Arc<dyn Transport>)
+            .map_err(|_| std::io::Error::new(std::io::ErrorKind::ConnectionRefused, format!("MemoryEndpoint.connect | '{}' not accepting", self.name)))
+    }
+    fn name(&self) -> String {
+        format!("memory://{}", self.name)
+    }
+}
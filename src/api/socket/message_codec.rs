@@ -0,0 +1,83 @@
+use std::collections::VecDeque;
+use bytes::{BufMut, BytesMut};
+use tokio_util::codec::{Decoder, Encoder};
+use crate::{
+    api::message::{fields::{FieldId, FieldSize}, message::{Bytes, MessageParse}, message_kind::MessageKind, msg_kind::MsgKind},
+    debug::dbg_id::DbgId, error::str_err::StrErr,
+};
+use super::tcp_socket::TcpMessage;
+///
+/// Tokio codec of the [Message](crate::api::message::message::Message) framing,
+/// async counterpart of the [TcpSocket](super::tcp_socket::TcpSocket) read / send
+/// - Decodes `Id` & the value of the received messages, skipping junk between them
+/// - Encodes `Id` & the value, `Kind` of the message is taken from the value
+/// - [Heartbeat](super::heartbeat::Heartbeat) messages are passed as is
+/// ```ignore
+/// let mut framed = Framed::new(stream, MessageCodec::new(&dbgid, schema.message()));
+/// framed.send((FieldId(1), MsgKind::String("Hello".into()))).await?;
+/// let (id, value) = framed.next().await.unwrap()?;
+/// ```
+#[derive(Debug)]
+pub struct MessageCodec {
+    dbgid: DbgId,
+    message: TcpMessage,
+    frames: VecDeque<(FieldId, MessageKind, FieldSize, Bytes)>,
+}
+//
+//
+impl MessageCodec {
+    ///
+    /// Returns [MessageCodec] new instance
+    /// - `message` - [TcpMessage] provides `build` and `parse`
+    pub fn new(dbgid: &DbgId, message: TcpMessage) -> Self {
+        Self {
+            dbgid: DbgId::with_parent(dbgid, "MessageCodec"),
+            message,
+            frames: VecDeque::new(),
+        }
+    }
+}
+//
+//
+impl Decoder for MessageCodec {
+    type Item = (FieldId, MsgKind);
+    type Error = StrErr;
+    ///
+    /// All the received bytes are consumed by the parser, partial message is kept inside until completed
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        if !src.is_empty() {
+            let results = self.message.parse_all(&src[..]);
+            src.clear();
+            for result in results {
+                match result {
+                    Ok(frame) => self.frames.push_back(frame),
+                    Err(err) => log::warn!("{}.decode | Resynchronising: {}", self.dbgid, err),
+                }
+            }
+            let skipped = self.message.skipped();
+            if skipped > 0 {
+                log::warn!("{}.decode | Skipped {} bytes searching for the message start", self.dbgid, skipped);
+            }
+        }
+        match self.frames.pop_front() {
+            Some((id, kind, size, bytes)) => {
+                log::trace!("{}.decode | id: {:?},  kind: {:?},  size: {:?}", self.dbgid, id, kind, size);
+                MsgKind::from_be_bytes(&kind, &bytes)
+                    .map(|value| Some((id, value)))
+                    .map_err(|err| format!("{}.decode | Decode error: {}", self.dbgid, err).into())
+            }
+            None => Ok(None),
+        }
+    }
+}
+//
+//
+impl Encoder<(FieldId, MsgKind)> for MessageCodec {
+    type Error = StrErr;
+    fn encode(&mut self, (id, value): (FieldId, MsgKind), dst: &mut BytesMut) -> Result<(), Self::Error> {
+        let bytes = self.message.build_kind(&value, id.0)?;
+        dst.reserve(bytes.len());
+        dst.put_slice(&bytes);
+        Ok(())
+    }
+}
@@ -1 +1,11 @@
+pub mod handshake;
+pub mod heartbeat;
+pub mod memory_transport;
+#[cfg(feature = "tokio")]
+pub mod message_codec;
+pub mod reconnect;
+pub mod socket_err;
 pub mod tcp_socket;
+pub mod timeouts;
+pub mod tls_transport;
+pub mod transport;
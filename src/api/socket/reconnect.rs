@@ -0,0 +1,109 @@
+use std::{collections::hash_map::RandomState, hash::{BuildHasher, Hasher}, time::Duration};
+///
+/// Delay between the failed connection attempts
+#[derive(Debug, Clone, PartialEq)]
+pub enum Backoff {
+    ///
+    /// The same `delay` after each attempt
+    Fixed(Duration),
+    ///
+    /// Delay starts from `initial` and doubles after each attempt up to the `max`
+    Exponential { initial: Duration, max: Duration },
+}
+///
+/// Reconnect policy shared by the [TcpSocket](super::tcp_socket::TcpSocket)
+/// and the [TcpClientConnect](crate::tcp::tcp_client_connect::TcpClientConnect)
+/// - `backoff` - delay between the failed attempts, see [Backoff]
+/// - `jitter` - fraction of the delay randomly subtracted, 0.0..=1.0,
+///    prevents the clients from reconnecting all at once
+/// - `max_attempts` - connecting stops after specified number of the failed attempts
+/// ```ignore
+/// let policy = ReconnectPolicy::exponential(Duration::from_millis(10), Duration::from_secs(5))
+///     .with_jitter(0.5)
+///     .with_max_attempts(10);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReconnectPolicy {
+    backoff: Backoff,
+    jitter: f64,
+    max_attempts: Option<u32>,
+}
+//
+//
+impl ReconnectPolicy {
+    ///
+    /// Returns [ReconnectPolicy] with the fixed `delay` between the attempts
+    pub fn fixed(delay: Duration) -> Self {
+        Self { backoff: Backoff::Fixed(delay), jitter: 0.0, max_attempts: None }
+    }
+    ///
+    /// Returns [ReconnectPolicy] with the delay doubling from `initial` up to the `max`
+    pub fn exponential(initial: Duration, max: Duration) -> Self {
+        Self { backoff: Backoff::Exponential { initial, max }, jitter: 0.0, max_attempts: None }
+    }
+    ///
+    /// Returns [ReconnectPolicy] with specified `jitter`, clamped to 0.0..=1.0
+    pub fn with_jitter(mut self, jitter: f64) -> Self {
+        self.jitter = if jitter.is_nan() { 0.0 } else { jitter.clamp(0.0, 1.0) };
+        self
+    }
+    ///
+    /// Returns [ReconnectPolicy] with specified maximum number of the failed attempts
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = Some(max_attempts);
+        self
+    }
+    ///
+    /// Returns the delay before the next attempt after the `attempt` failed, starting from 1
+    /// - Returns None if the maximum number of attempts is reached
+    pub fn delay(&self, attempt: u32) -> Option<Duration> {
+        if let Some(max_attempts) = self.max_attempts {
+            if attempt >= max_attempts {
+                return None;
+            }
+        }
+        let delay = match &self.backoff {
+            Backoff::Fixed(delay) => *delay,
+            Backoff::Exponential { initial, max } => {
+                let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+                initial.saturating_mul(factor).min(*max)
+            }
+        };
+        match self.jitter > 0.0 {
+            true => Some(delay.mul_f64(1.0 - self.jitter * Self::random())),
+            false => Some(delay),
+        }
+    }
+    ///
+    /// Returns the random value in 0.0..=1.0
+    /// - [RandomState] keys are seeded from the OS randomness and change with each instance,
+    ///   so the SipHash of the empty input is uniformly distributed and differs between the calls and the processes
+    /// - Not suitable for the cryptography, but enough to spread the reconnecting clients, without the extra dependency
+    fn random() -> f64 {
+        RandomState::new().build_hasher().finish() as f64 / u64::MAX as f64
+    }
+}
+//
+//
+impl Default for ReconnectPolicy {
+    ///
+    /// Exponential from 10 ms up to 1 s, jitter 0.2, unlimited attempts
+    fn default() -> Self {
+        Self::exponential(Duration::from_millis(10), Duration::from_secs(1)).with_jitter(0.2)
+    }
+}
+///
+/// Connection state changes
+/// - `endpoint` - address of the remote side, see [Endpoint::name](super::transport::Endpoint::name)
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConnectEvent {
+    ///
+    /// Connection attempt started, `attempt` starting from 1
+    Connecting { endpoint: String, attempt: u32 },
+    ///
+    /// Connection established
+    Connected(String),
+    ///
+    /// Established connection is lost or closed, `reason` of the loss
+    Lost { endpoint: String, reason: String },
+}
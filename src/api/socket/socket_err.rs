@@ -0,0 +1,79 @@
+use std::time::Duration;
+use crate::error::str_err::StrErr;
+///
+/// Errors of the [TcpSocket](super::tcp_socket::TcpSocket) operations
+/// - Each expired timeout of the [Timeouts](super::timeouts::Timeouts) has it's own variant
+/// - Converts into [StrErr], so can be returned with `?` where [StrErr] expected
+pub enum SocketErr {
+    ///
+    /// Connection isn't established in the `connect` timeout
+    ConnectTimeout(Duration),
+    ///
+    /// Message isn't received in the `read` timeout
+    ReadTimeout(Duration),
+    ///
+    /// Message isn't written in the `write` timeout
+    WriteTimeout(Duration),
+    ///
+    /// Request isn't completed in the `deadline`
+    Deadline(Duration),
+    ///
+    /// Any other error
+    Other(StrErr),
+}
+//
+//
+impl SocketErr {
+    ///
+    /// Returns true if the error is caused by any expired timeout
+    pub fn is_timeout(&self) -> bool {
+        !matches!(self, SocketErr::Other(_))
+    }
+}
+//
+//
+impl std::fmt::Display for SocketErr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SocketErr::ConnectTimeout(timeout) => write!(f, "SocketErr | Not connected in specified timeout {:?}", timeout),
+            SocketErr::ReadTimeout(timeout) => write!(f, "SocketErr | No valid message received in specified timeout {:?}", timeout),
+            SocketErr::WriteTimeout(timeout) => write!(f, "SocketErr | Message not written in specified timeout {:?}", timeout),
+            SocketErr::Deadline(deadline) => write!(f, "SocketErr | Request not completed in specified deadline {:?}", deadline),
+            SocketErr::Other(err) => write!(f, "{}", err),
+        }
+    }
+}
+//
+//
+impl std::fmt::Debug for SocketErr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+//
+//
+impl std::error::Error for SocketErr {}
+//
+//
+impl From<StrErr> for SocketErr {
+    fn from(value: StrErr) -> Self {
+        SocketErr::Other(value)
+    }
+}
+//
+//
+impl From<String> for SocketErr {
+    fn from(value: String) -> Self {
+        SocketErr::Other(StrErr(value))
+    }
+}
+//
+//
+impl From<SocketErr> for StrErr {
+    fn from(value: SocketErr) -> Self {
+        match value {
+            SocketErr::Other(err) => err,
+            err => StrErr(err.to_string()),
+        }
+    }
+}
@@ -1,8 +1,12 @@
-use std::{io::{BufReader, BufWriter, Read, Write}, net::{Shutdown, SocketAddr, TcpStream, ToSocketAddrs}, sync::Arc, time::{Duration, Instant}};
+use std::{collections::{BTreeMap, VecDeque}, io::Write, net::{TcpStream, ToSocketAddrs}, sync::{mpsc::Sender, Arc}, thread, time::{Duration, Instant}};
 use crate::{
-    api::message::{fields::{FieldId, FieldSize}, message::{Bytes, Message, MessageParse}, message_kind::MessageKind, msg_kind::MsgKind},
+    api::message::{fields::{FieldId, FieldSize}, fragment::FragmentStream, message::{Bytes, Message, MessageParse}, message_kind::MessageKind, msg_kind::MsgKind},
     debug::dbg_id::DbgId, error::str_err::StrErr,
 };
+use super::{
+    handshake::Handshake, heartbeat::Heartbeat, reconnect::{ConnectEvent, ReconnectPolicy}, socket_err::SocketErr, timeouts::Timeouts,
+    transport::{Endpoint, TcpEndpoint, Transport},
+};
 ///
 /// 
 pub type TcpMessage = Message<(FieldId, MessageKind, FieldSize, Bytes)>;
@@ -13,15 +17,84 @@ pub enum IsConnected<T, E> {
     Closed(E),
 }
 ///
+/// The way the failed IO operation is handled, see [IoOutcome::from]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IoOutcome {
+    ///
+    /// Operation interrupted, can be repeated immediately, connection is active
+    Retry,
+    ///
+    /// Timeout of the blocking operation expired, connection is active
+    Timeout,
+    ///
+    /// Connection is broken and must be closed
+    Closed,
+}
+//
+//
+impl From<std::io::ErrorKind> for IoOutcome {
+    ///
+    /// Classifies the IO error
+    /// - `Interrupted` - [IoOutcome::Retry]
+    /// - `WouldBlock`, `TimedOut` - [IoOutcome::Timeout], the read / write timeout is reported with one of them depending on the platform
+    /// - Any other - [IoOutcome::Closed], the stream state is unknown after the failure, so the connection can't be used anymore
+    fn from(kind: std::io::ErrorKind) -> Self {
+        match kind {
+            std::io::ErrorKind::Interrupted => IoOutcome::Retry,
+            std::io::ErrorKind::WouldBlock => IoOutcome::Timeout,
+            std::io::ErrorKind::TimedOut => IoOutcome::Timeout,
+            std::io::ErrorKind::NotFound => IoOutcome::Closed,
+            std::io::ErrorKind::PermissionDenied => IoOutcome::Closed,
+            std::io::ErrorKind::ConnectionRefused => IoOutcome::Closed,
+            std::io::ErrorKind::ConnectionReset => IoOutcome::Closed,
+            std::io::ErrorKind::ConnectionAborted => IoOutcome::Closed,
+            std::io::ErrorKind::NotConnected => IoOutcome::Closed,
+            std::io::ErrorKind::AddrInUse => IoOutcome::Closed,
+            std::io::ErrorKind::AddrNotAvailable => IoOutcome::Closed,
+            std::io::ErrorKind::BrokenPipe => IoOutcome::Closed,
+            std::io::ErrorKind::AlreadyExists => IoOutcome::Closed,
+            // Invalid argument or the corrupted stream, e.g. TLS record
+            std::io::ErrorKind::InvalidInput => IoOutcome::Closed,
+            std::io::ErrorKind::InvalidData => IoOutcome::Closed,
+            // Part of the message may be already written
+            std::io::ErrorKind::WriteZero => IoOutcome::Closed,
+            std::io::ErrorKind::Unsupported => IoOutcome::Closed,
+            std::io::ErrorKind::UnexpectedEof => IoOutcome::Closed,
+            std::io::ErrorKind::OutOfMemory => IoOutcome::Closed,
+            std::io::ErrorKind::Other => IoOutcome::Closed,
+            // HostUnreachable, NetworkUnreachable, NetworkDown & the kinds added later
+            _ => IoOutcome::Closed,
+        }
+    }
+}
+///
+/// Replies received to the pending request in the pipelined mode
+/// - Reply sent in [MsgKind::Fragment]'s is done with the last fragment
+#[derive(Debug, Default)]
+struct Pending {
+    received: VecDeque<MsgKind>,
+    done: bool,
+}
+///
 /// Basic Read / Write [Message]' via TCP Socket
 pub struct TcpSocket {
     dbgid: DbgId,
-    address: SocketAddr,
+    endpoint: Box<dyn Endpoint>,
     message: TcpMessage,
     msg_id: u32,
-    connection: Option<Arc<TcpStream>>,
-    buf: [u8; Self::BUF_LEN],
-    timeout: Duration,
+    connection: Option<Arc<dyn Transport>>,
+    frames: VecDeque<(FieldId, MessageKind, FieldSize, Bytes)>,
+    handshake: Option<Handshake>,
+    negotiated: Option<Handshake>,
+    heartbeat: Option<Heartbeat>,
+    received: Instant,
+    ping: Option<Instant>,
+    reconnect: ReconnectPolicy,
+    events: Option<Sender<ConnectEvent>>,
+    pending: Option<BTreeMap<u32, Pending>>,
+    timeouts: Timeouts,
+    deadline: Option<Instant>,
+    request: bool,
 }
 //
 //
@@ -29,12 +102,13 @@ impl std::fmt::Debug for TcpSocket {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("TcpSocket")
             .field("dbgid", &self.dbgid)
-            .field("address", &self.address)
+            .field("endpoint", &self.endpoint)
             .field("message", &self.message)
             .field("connection", &self.connection)
+            .field("negotiated", &self.negotiated)
+            .field("pending", &self.pending.as_ref().map(|pending| pending.len()))
             // .field("stream", &self.stream)
-            // .field("buf", &self.buf)
-            .field("timeout", &self.timeout).finish()
+            .field("timeouts", &self.timeouts).finish()
     }
 }
 //
@@ -44,79 +118,312 @@ impl TcpSocket {
     /// bytes to be read from socket at once
     const BUF_LEN: usize = 1024 * 4;
     ///
+    /// Timeout of the read taking the already received bytes before the send on the idle connection, see [TcpSocket::keepalive]
+    const KEEPALIVE_READ: Duration = Duration::from_millis(1);
+    ///
     /// Returns [TcpSocket] new instance
     /// - `address` - TCP address of the remote host to be connected
     /// - `message` - [TcpMessage] provides `build` and `parse`
     /// - `stream` - TcpStream if already connected,
     ///    - If None specified, connection will be opened internally only when required 
     pub fn new(dbgid: &DbgId, address: impl ToSocketAddrs + std::fmt::Debug, message: TcpMessage, stream: Option<Arc<TcpStream>>) -> Self {
-        let dbgid = DbgId::with_parent(&dbgid, "TcpSocket");
         let address = match address.to_socket_addrs() {
             Ok(mut addrs) => match addrs.next() {
                 Some(addr) => addr,
-                None => panic!("{}.new | Empty address: {:?}", dbgid, address),
+                None => panic!("{}/TcpSocket.new | Empty address: {:?}", dbgid, address),
             },
-            Err(err) => panic!("{}.new | Address error: {:#?}", dbgid, err),
+            Err(err) => panic!("{}/TcpSocket.new | Address error: {:#?}", dbgid, err),
         };
+        Self::from_endpoint(dbgid, TcpEndpoint::new(address), message, stream.map(|stream| stream as Arc<dyn Transport>))
+    }
+    ///
+    /// Returns [TcpSocket] new instance working over any [Transport]
+    /// - `endpoint` - opens the connection to the remote side, see [TcpEndpoint], [UdsEndpoint](super::transport::UdsEndpoint),
+    ///    [MemoryEndpoint](super::memory_transport::MemoryEndpoint)
+    /// - `message` - [TcpMessage] provides `build` and `parse`
+    /// - `transport` - connection if already opened,
+    ///    - If None specified, connection will be opened internally only when required 
+    pub fn from_endpoint(dbgid: &DbgId, endpoint: impl Endpoint + 'static, message: TcpMessage, transport: Option<Arc<dyn Transport>>) -> Self {
         Self {
-            dbgid,
-            address: address.into(),
+            dbgid: DbgId::with_parent(dbgid, "TcpSocket"),
+            endpoint: Box::new(endpoint),
             message,
             msg_id: 0,
-            connection: stream,
-            buf: [0; Self::BUF_LEN],
-            timeout: Duration::from_secs(10),
+            connection: transport,
+            frames: VecDeque::new(),
+            handshake: None,
+            negotiated: None,
+            heartbeat: None,
+            received: Instant::now(),
+            ping: None,
+            reconnect: ReconnectPolicy::default(),
+            events: None,
+            pending: None,
+            timeouts: Timeouts::default(),
+            deadline: None,
+            request: false,
+        }
+    }
+    ///
+    /// Returns [TcpSocket] with specified connect, read & write [Timeouts]
+    /// - By default [Timeouts::default]
+    /// - The `deadline` is applied to each request started by [TcpSocket::start_request]
+    pub fn with_timeouts(mut self, timeouts: Timeouts) -> Self {
+        self.timeouts = timeouts;
+        if let Some(stream) = &self.connection {
+            self.apply_timeouts(stream);
+        }
+        self
+    }
+    ///
+    /// Returns [TcpSocket] with specified delays between the failed connection attempts
+    /// - By default [ReconnectPolicy::default]
+    pub fn with_reconnect(mut self, reconnect: ReconnectPolicy) -> Self {
+        self.reconnect = reconnect;
+        self
+    }
+    ///
+    /// Returns [TcpSocket] sending the connection state changes into the `events`
+    pub fn with_events(mut self, events: Sender<ConnectEvent>) -> Self {
+        self.events = Some(events);
+        self
+    }
+    ///
+    /// Returns [TcpSocket] sending the `handshake` on each new connection
+    /// - The peer must answer with it's own [Handshake], see [TcpSocket::answer_handshake]
+    /// - Connection is closed if the handshake failed
+    pub fn with_handshake(mut self, handshake: Handshake) -> Self {
+        self.handshake = Some(handshake);
+        self
+    }
+    ///
+    /// Returns [TcpSocket] in the pipelined mode
+    /// - Many requests can be sent before the replies are received
+    /// - Replies are matched to the requests by `Id`, see [TcpSocket::reply]
+    /// - Received message with `Id` of no pending request is an error
+    /// - Request can be sent in [MsgKind::Fragment]'s, the first fragment registers the request,
+    ///   the following ones must be sent with the same `Id`, the reply can be fragmented as well
+    pub fn with_pipelining(mut self) -> Self {
+        self.pending = Some(BTreeMap::new());
+        self
+    }
+    ///
+    /// Returns [TcpSocket] with enabled keepalive of the idle connection, see [Heartbeat]
+    /// - Heartbeat is driven by [TcpSocket::read], connection state is checked every [Heartbeat::poll] while reading
+    /// - And by [TcpSocket::send] & [TcpSocket::send_kind], so the socket used for the sending only is kept alive as well
+    /// - Ping of the peer is answered regardless of this option
+    pub fn with_heartbeat(mut self, heartbeat: Heartbeat) -> Self {
+        self.heartbeat = Some(heartbeat);
+        if let Some(stream) = &self.connection {
+            self.apply_timeouts(stream);
         }
+        self
+    }
+    ///
+    /// Returns values negotiated with the peer by the last handshake
+    /// - None if the handshake isn't configured or isn't done yet
+    pub fn negotiated(&self) -> Option<&Handshake> {
+        self.negotiated.as_ref()
     }
     ///
     /// Opens a connection to the TCP Socket and preparing the `Message`
-    pub fn connect(&mut self) -> Result<Arc<TcpStream>, StrErr> {
+    /// - Failed attempts are repeated with the delay of the [ReconnectPolicy] until the `connect` timeout expires
+    /// - Returns [SocketErr::ConnectTimeout] if not connected in the `connect` timeout of the [Timeouts]
+    pub fn connect(&mut self) -> Result<Arc<dyn Transport>, SocketErr> {
+        if let Some(stream) = &self.connection {
+            return Ok(Arc::clone(stream));
+        }
         let time = Instant::now();
+        let mut attempt = 0;
         loop {
-            match &self.connection {
-                Some(stream) => {
-                    return Ok(Arc::clone(stream))
-                },
-                None => {
-                    match TcpStream::connect(self.address) {
-                        Ok(stream) => {
-                            log::debug!("{}.connect | connected to: \n\t{:?}", self.dbgid, stream);
-                            if let Err(err) = stream.set_read_timeout(Some(self.timeout)) {
-                                let message = format!("{}.connect | set_read_timeout error: \n\t{:?}", self.dbgid, err);
-                                log::warn!("{}", message);
-                            }
-                            if let Err(err) = stream.set_write_timeout(Some(self.timeout)) {
-                                let message = format!("{}.connect | set_write_timeout error: \n\t{:?}", self.dbgid, err);
-                                log::warn!("{}", message);
-                            }
-                            let stream = Arc::new(stream);
-                            let stream_clone = Arc::clone(&stream);
-                            self.connection = Some(stream);
-                            return Ok(stream_clone)
-                        },
-                        Err(err) => {
-                            let err = format!("{}.connect | Connection error: \n\t{:?}", self.dbgid, err);
-                            if log::max_level() >= log::LevelFilter::Trace {
-                                log::warn!("{}", err);
-                            }
+            let remaining = self.timeouts.connect.saturating_sub(time.elapsed());
+            if remaining.is_zero() {
+                let err = SocketErr::ConnectTimeout(self.timeouts.connect);
+                log::warn!("{}.connect | {}", self.dbgid, err);
+                return Err(err);
+            }
+            let remaining = match self.deadline_left() {
+                Ok(Some(left)) => remaining.min(left),
+                Ok(None) => remaining,
+                Err(err) => return Err(self.expired(err)),
+            };
+            attempt += 1;
+            self.event(ConnectEvent::Connecting { endpoint: self.endpoint.name(), attempt });
+            match self.endpoint.connect(remaining) {
+                Ok(stream) => {
+                    log::debug!("{}.connect | connected to: \n\t{:?}", self.dbgid, stream);
+                    self.apply_timeouts(&stream);
+                    let stream_clone = Arc::clone(&stream);
+                    self.connection = Some(stream);
+                    self.negotiated = None;
+                    self.received = Instant::now();
+                    self.ping = None;
+                    if let Some(handshake) = self.handshake.clone() {
+                        if let Err(err) = self.handshake(&handshake) {
+                            log::warn!("{}", err);
+                            self.lost(&err.to_string());
+                            return Err(err);
                         }
                     }
+                    self.event(ConnectEvent::Connected(self.endpoint.name()));
+                    return Ok(stream_clone)
                 },
+                Err(err) => {
+                    let err = format!("{}.connect | Connection error: \n\t{:?}", self.dbgid, err);
+                    if log::max_level() >= log::LevelFilter::Trace {
+                        log::warn!("{}", err);
+                    }
+                }
             }
-            if time.elapsed() > self.timeout {
-                let err = format!("{}.connect | Not connected in specified timeout {:?}", self.dbgid, self.timeout);
-                log::warn!("{}", err);
-                return Err(StrErr(err))
+            let delay = match self.reconnect.delay(attempt) {
+                Some(delay) => delay,
+                None => {
+                    let err = format!("{}.connect | Not connected in {} attempts", self.dbgid, attempt);
+                    log::warn!("{}", err);
+                    return Err(err.into())
+                }
+            };
+            let remaining = self.timeouts.connect.saturating_sub(time.elapsed());
+            let remaining = match self.deadline {
+                Some(deadline) => remaining.min(deadline.saturating_duration_since(Instant::now())),
+                None => remaining,
+            };
+            thread::sleep(delay.min(remaining));
+        }
+    }
+    ///
+    /// Returns the greatest `Id` assigned to the sent message internally
+    /// - Greater ones are reserved for the [Heartbeat], so depends on the width of the `Id` field
+    fn max_msg_id(&self) -> u32 {
+        Heartbeat::pong_id(self.message.max_id()).0 - 1
+    }
+    ///
+    /// Applies the read & write [Timeouts] to the `stream`
+    /// - Read timeout is limited by the [Heartbeat::poll] if heartbeat enabled
+    fn apply_timeouts(&self, stream: &Arc<dyn Transport>) {
+        if let Err(err) = stream.set_read_timeout(Some(self.read_timeout())) {
+            log::warn!("{}.apply_timeouts | set_read_timeout error: \n\t{:?}", self.dbgid, err);
+        }
+        if let Err(err) = stream.set_write_timeout(Some(self.timeouts.write)) {
+            log::warn!("{}.apply_timeouts | set_write_timeout error: \n\t{:?}", self.dbgid, err);
+        }
+    }
+    ///
+    /// Returns the timeout of the single blocking read
+    fn read_timeout(&self) -> Duration {
+        match &self.heartbeat {
+            Some(heartbeat) => heartbeat.poll().min(self.timeouts.read),
+            None => self.timeouts.read,
+        }
+    }
+    ///
+    /// Starts the request limited by the `deadline` of the [Timeouts]
+    /// - Connect, write & read return [SocketErr::Deadline] after the deadline expired
+    /// - Connection is closed if the request is timed out, so the late reply can't be taken for the reply to the next request
+    /// - Must be finished by [TcpSocket::finish_request]
+    pub fn start_request(&mut self) {
+        self.request = true;
+        self.deadline = self.timeouts.deadline.map(|deadline| Instant::now() + deadline);
+    }
+    ///
+    /// Finishes the request started by [TcpSocket::start_request]
+    pub fn finish_request(&mut self) {
+        self.request = false;
+        if self.deadline.take().is_some() {
+            if let Some(stream) = &self.connection {
+                self.apply_timeouts(stream);
+            }
+        }
+    }
+    ///
+    /// Returns the time left until the deadline of the current request
+    /// - None if the deadline isn't limited
+    fn deadline_left(&self) -> Result<Option<Duration>, SocketErr> {
+        match self.deadline {
+            Some(deadline) => {
+                let left = deadline.saturating_duration_since(Instant::now());
+                match left.is_zero() {
+                    true => Err(SocketErr::Deadline(self.timeouts.deadline.unwrap_or_default())),
+                    false => Ok(Some(left)),
+                }
+            }
+            None => Ok(None),
+        }
+    }
+    ///
+    /// Returns the timeout `err`, closing the connection if the request is in progress
+    fn expired(&mut self, err: SocketErr) -> SocketErr {
+        log::warn!("{} | {}", self.dbgid, err);
+        if self.request {
+            self.lost(&err.to_string());
+        }
+        err
+    }
+    ///
+    /// Closes the lost connection, new one will be opened by the next call
+    /// - Pending requests are dropped, they will never be replied
+    fn lost(&mut self, reason: &str) {
+        if let Err(err) = self.close() {
+            log::warn!("{}.lost | Close tcp stream error: {:?}", self.dbgid, err);
+        }
+        if let Some(pending) = &mut self.pending {
+            pending.clear();
+        }
+        if self.connection.take().is_some() {
+            self.event(ConnectEvent::Lost { endpoint: self.endpoint.name(), reason: reason.to_owned() });
+        }
+    }
+    ///
+    /// Sends the connection state change `event` to the subscriber if any
+    fn event(&self, event: ConnectEvent) {
+        if let Some(events) = &self.events {
+            log::trace!("{}.event | {:?}", self.dbgid, event);
+            if let Err(err) = events.send(event) {
+                log::debug!("{}.event | Subscriber gone: {:?}", self.dbgid, err);
             }
         }
     }
     ///
+    /// Sends the `local` [Handshake] and waits for the peer's answer
+    fn handshake(&mut self, local: &Handshake) -> Result<(), SocketErr> {
+        self.send_kind(&local.to_msg_kind(), Some(Handshake::ID.0))
+            .map_err(|err| StrErr(format!("{}.handshake | Send error: {}", self.dbgid, err)))?;
+        let peer = match self.read() {
+            Ok((id, value)) if id == Handshake::ID => Handshake::from_msg_kind(&value)
+                .map_err(|err| StrErr(format!("{}.handshake | {}", self.dbgid, err)))?,
+            Ok((id, value)) => return Err(format!("{}.handshake | Expected handshake, but received {:?}: {:?}", self.dbgid, id, value.kind()).into()),
+            Err(err) => return Err(format!("{}.handshake | Read error: {}", self.dbgid, err).into()),
+        };
+        let negotiated = local.negotiate(&peer)
+            .map_err(|err| StrErr(format!("{}.handshake | {}", self.dbgid, err)))?;
+        log::debug!("{}.handshake | Negotiated: {:?}", self.dbgid, negotiated);
+        self.negotiated = Some(negotiated);
+        Ok(())
+    }
+    ///
+    /// Waits for the [Handshake] of the connecting peer and answers with the `local` one
+    /// - Used on the accepting side, where the socket is created with already connected `stream`
+    /// - Returns the negotiated values, also available by [TcpSocket::negotiated]
+    pub fn answer_handshake(&mut self, local: Handshake) -> Result<Handshake, SocketErr> {
+        let peer = match self.read()? {
+            (id, value) if id == Handshake::ID => Handshake::from_msg_kind(&value)
+                .map_err(|err| StrErr(format!("{}.answer_handshake | {}", self.dbgid, err)))?,
+            (id, value) => return Err(format!("{}.answer_handshake | Expected handshake, but received {:?}: {:?}", self.dbgid, id, value.kind()).into()),
+        };
+        let negotiated = local.negotiate(&peer)
+            .map_err(|err| StrErr(format!("{}.answer_handshake | {}", self.dbgid, err)))?;
+        self.send_kind(&local.to_msg_kind(), Some(Handshake::ID.0))?;
+        log::debug!("{}.answer_handshake | Negotiated: {:?}", self.dbgid, negotiated);
+        self.negotiated = Some(negotiated.clone());
+        Ok(negotiated)
+    }
+    ///
     /// Closes a connection
     pub fn close(&mut self) -> Result<(), StrErr> {
         match &self.connection {
             Some(stream) => {
                 stream
-                    .shutdown(Shutdown::Both)
+                    .shutdown()
                     .map_err(|err| StrErr(format!("{}.close | Error: {:#?}", self.dbgid, err)))
             },
             None => Ok(()),
@@ -124,156 +431,408 @@ impl TcpSocket {
     }
     ///
     /// Sending a [Message] via TCP socket
-    pub fn send(&mut self, bytes: &[u8], msg_id: Option<u32>) -> Result<FieldId, StrErr> {
+    /// - `Kind` of the message is the configured one
+    /// - Returns error if the `Kind` or the length of the `bytes` isn't accepted by the peer, see [TcpSocket::negotiated]
+    pub fn send(&mut self, bytes: &[u8], msg_id: Option<u32>) -> Result<FieldId, SocketErr> {
         log::trace!("{}.send | bytes: {:?}", self.dbgid, bytes);
-        match self.connect() {
-            Ok(stream) => {
-                let msg_id = msg_id.unwrap_or_else(|| {
-                    self.msg_id = (self.msg_id % u32::MAX) + 1;
-                    self.msg_id
-                });
-                let bytes = self.message.build(bytes, msg_id);
-                match BufWriter::new(stream.as_ref()).write_all(&bytes) {
-                    Ok(_) => {
-                        return Ok(FieldId(msg_id))
+        if let Some(kind) = self.message.kind() {
+            self.check_negotiated("send", &kind, bytes.len())?;
+        }
+        self.keepalive()?;
+        self.write(msg_id, false, |message, id| message.build(bytes, id))
+    }
+    ///
+    /// Sending a [Message] via TCP socket
+    /// - `Kind` of the message is taken from the `value`
+    /// - Returns error if the `Kind` or the length of the encoded `value` isn't accepted by the peer, see [TcpSocket::negotiated]
+    pub fn send_kind(&mut self, value: &MsgKind, msg_id: Option<u32>) -> Result<FieldId, SocketErr> {
+        log::trace!("{}.send_kind | value: {:?}", self.dbgid, value);
+        let kind = value.kind();
+        let bytes = value.encode()
+            .map_err(|err| StrErr(format!("{}.send_kind | {}", self.dbgid, err)))?;
+        self.check_negotiated("send_kind", &kind, bytes.len())?;
+        let continued = matches!(value, MsgKind::Fragment(fragment) if fragment.index > 0);
+        self.keepalive()?;
+        self.write(msg_id, continued, |message, id| message.build_encoded(&kind, &bytes, id))
+    }
+    ///
+    /// Returns error if the message of the `kind` with `len` bytes of the `Data` isn't accepted by the peer
+    /// - Connects first, so the [Handshake] is done before the check, see [TcpSocket::negotiated]
+    /// - Nothing is checked if the [Handshake] isn't configured
+    fn check_negotiated(&mut self, method: &str, kind: &MessageKind, len: usize) -> Result<(), SocketErr> {
+        self.connect()?;
+        if let Some(negotiated) = &self.negotiated {
+            if !negotiated.kinds.contains(kind) {
+                return Err(format!("{}.{} | Kind {:?} isn't supported by the peer", self.dbgid, method, kind).into());
+            }
+            if len > negotiated.max_size as usize {
+                return Err(format!("{}.{} | Data length {} exceeds maximum {} accepted by the peer", self.dbgid, method, len, negotiated.max_size).into());
+            }
+        }
+        Ok(())
+    }
+    ///
+    /// Drives the [Heartbeat] before the send, if no one is reading the socket
+    /// - Does nothing until the connection is idle for the [Heartbeat] `idle` interval, so the send isn't delayed
+    /// - Bytes already received are taken without waiting, the ping is answered, the pong is accepted,
+    ///   other messages are kept for the next [TcpSocket::read]
+    /// - Sends the ping if the connection is idle, closes the connection if the pong isn't received in the deadline
+    fn keepalive(&mut self) -> Result<(), SocketErr> {
+        let stream = match (&self.heartbeat, &self.connection) {
+            (Some(heartbeat), Some(stream)) if self.received.elapsed() >= heartbeat.idle => Arc::clone(stream),
+            _ => return Ok(()),
+        };
+        if let Err(err) = stream.set_read_timeout(Some(Self::KEEPALIVE_READ)) {
+            log::warn!("{}.keepalive | set_read_timeout error: \n\t{:?}", self.dbgid, err);
+        }
+        let result = self.message.read_from(Self::BUF_LEN, &mut |buf| stream.read(buf));
+        if let Err(err) = stream.set_read_timeout(Some(self.read_timeout())) {
+            log::warn!("{}.keepalive | set_read_timeout error: \n\t{:?}", self.dbgid, err);
+        }
+        match result {
+            Ok(0) => {
+                let err = format!("{}.keepalive | tcp stream closed", self.dbgid);
+                self.lost(&err);
+                return Err(err.into());
+            }
+            Ok(_) => {
+                self.received = Instant::now();
+                for result in self.message.parse_all(&[]) {
+                    match result {
+                        Ok(frame) => self.frames.push_back(frame),
+                        Err(err) => log::warn!("{}.keepalive | Resynchronising: {}", self.dbgid, err),
                     }
-                    Err(err) => {
-                        let err = format!("{}.send | write to tcp stream error: {:?}", self.dbgid, err);
-                        log::warn!("{}", err);
-                        if let Err(err) = self.close() {
-                            log::warn!("{}.send | Close tcp stream error: {:?}", self.dbgid, err);
-                        }
-                        return Err(StrErr(err));
+                }
+            }
+            Err(err) => match self.parse_err(&err) {
+                IoOutcome::Retry | IoOutcome::Timeout => {}
+                IoOutcome::Closed => {
+                    let err = format!("{}.keepalive | Close tcp stream error: {:#?}", self.dbgid, err);
+                    self.lost(&err);
+                    return Err(err.into());
+                }
+            }
+        }
+        let mut frames = std::mem::take(&mut self.frames);
+        while let Some(frame) = frames.pop_front() {
+            if let Some(frame) = self.heartbeat_frame(frame)? {
+                self.frames.push_back(frame);
+            }
+        }
+        self.check_heartbeat()
+    }
+    ///
+    /// Writes a [Message] built by `build` into the TCP socket
+    /// - `msg_id` - if None, the next internal id is used
+    /// - `continued` - the message continues the pending request, the next fragment
+    fn write(&mut self, msg_id: Option<u32>, continued: bool, build: impl FnOnce(&mut TcpMessage, u32) -> Result<Vec<u8>, StrErr>) -> Result<FieldId, SocketErr> {
+        let stream = self.connect()?;
+        if let Err(err) = self.deadline_left() {
+            return Err(self.expired(err));
+        }
+        let max_msg_id = self.max_msg_id();
+        let msg_id = msg_id.unwrap_or_else(|| {
+            self.msg_id = (self.msg_id % max_msg_id) + 1;
+            self.msg_id
+        });
+        let request = msg_id <= max_msg_id;
+        if let Some(pending) = &self.pending {
+            if request {
+                match (pending.contains_key(&msg_id), continued) {
+                    (true, false) => return Err(format!("{}.send | Request {} is already pending", self.dbgid, msg_id).into()),
+                    (false, true) => return Err(format!("{}.send | Fragment continues no pending request {}", self.dbgid, msg_id).into()),
+                    _ => {}
+                }
+            }
+        }
+        let bytes = build(&mut self.message, msg_id)?;
+        match stream.write_all(&bytes) {
+            Ok(_) => {
+                if let Some(pending) = &mut self.pending {
+                    if request && !continued {
+                        pending.insert(msg_id, Pending::default());
                     }
                 }
+                Ok(FieldId(msg_id))
             }
-            Err(err) => {
-                let err = format!("{}.send | Connection error: {:?}", self.dbgid, err);
-                log::warn!("{}", err);
-                return Err(StrErr(err));
+            // `write_all` retries the interrupted writes itself,
+            // so any error may leave the message partially written, connection can't be used anymore
+            Err(err) => match self.parse_err(&err) {
+                IoOutcome::Timeout => {
+                    let err = SocketErr::WriteTimeout(self.timeouts.write);
+                    log::warn!("{}.send | {}", self.dbgid, err);
+                    self.lost(&err.to_string());
+                    Err(err)
+                }
+                IoOutcome::Retry | IoOutcome::Closed => {
+                    let err = format!("{}.send | write to tcp stream error: {:?}", self.dbgid, err);
+                    log::warn!("{}", err);
+                    self.lost(&err);
+                    Err(err.into())
+                }
             }
-        };
+        }
     }
     ///
     /// Reads a [Message] parsed from TCP socket
     /// - Returns payload bytes only (cuting header)
-    pub fn read(&mut self) -> Result<(FieldId, MsgKind), StrErr> {
-        match self.connect() {
-            Ok(stream) => {
-                let time = Instant::now();
-                let mut stream = BufReader::new(stream.as_ref());
-                loop {
-                    match stream.read(&mut self.buf) {
-                        Ok(len) => {
-                            log::trace!("{}.read |     read len: {:?}", self.dbgid, len);
-                            match self.message.parse(self.buf[..len].to_vec()) {
-                                Ok((id, kind, size, bytes)) => {
-                                    let dbg_bytes = if bytes.len() > 16 {format!("{:?} ...", &bytes[..16])} else {format!("{:?}", bytes)};
-                                    log::trace!("{}.read | id: {:?},  kind: {:?},  size: {:?},  bytes: {:?}", self.dbgid, id, kind, size, dbg_bytes);
-                                    match kind {
-                                        MessageKind::Any => return Ok((id.clone(), MsgKind::Bytes(bytes.to_owned()))),
-                                        MessageKind::Empty => log::warn!("{} | Message of kind '{:?}' - is not implemented yet", self.dbgid, kind),
-                                        MessageKind::Bytes => return Ok((id.clone(), MsgKind::Bytes(bytes.to_owned()))),
-                                        MessageKind::Bool => log::warn!("{} | Message of kind '{:?}' - is not implemented yet", self.dbgid, kind),
-                                        MessageKind::U16 => log::warn!("{} | Message of kind '{:?}' - is not implemented yet", self.dbgid, kind),
-                                        MessageKind::U32 => log::warn!("{} | Message of kind '{:?}' - is not implemented yet", self.dbgid, kind),
-                                        MessageKind::U64 => log::warn!("{} | Message of kind '{:?}' - is not implemented yet", self.dbgid, kind),
-                                        MessageKind::I16 => log::warn!("{} | Message of kind '{:?}' - is not implemented yet", self.dbgid, kind),
-                                        MessageKind::I32 => log::warn!("{} | Message of kind '{:?}' - is not implemented yet", self.dbgid, kind),
-                                        MessageKind::I64 => log::warn!("{} | Message of kind '{:?}' - is not implemented yet", self.dbgid, kind),
-                                        MessageKind::F32 => log::warn!("{} | Message of kind '{:?}' - is not implemented yet", self.dbgid, kind),
-                                        MessageKind::F64 => log::warn!("{} | Message of kind '{:?}' - is not implemented yet", self.dbgid, kind),
-                                        MessageKind::String => match String::from_utf8(bytes) {
-                                            Ok(value) => return Ok((id.clone(), MsgKind::String(value))),
-                                            Err(err) => return Err(format!("{}.read | Message::string parse error: {}", self.dbgid, err).into()),
-                                        },
-                                        MessageKind::Timestamp => log::warn!("{}.read | Message of kind '{:?}' - is not implemented yet", self.dbgid, kind),
-                                        MessageKind::Duration => log::warn!("{}.read | Message of kind '{:?}' - is not implemented yet", self.dbgid, kind),
-                                    }
-                                }
-                                Err(err) => {
-                                    log::warn!("{}", err);
-                                }
-                            };
-                            if len == 0 {
-                                if let Err(err) = self.close() {
-                                    log::warn!("{}.read | Close tcp stream error: {:?}", self.dbgid, err);
-                                }
-                                return Err(format!("{}.read | tcp stream closed", self.dbgid).into());
-                            }
-                        }
-                        Err(err) => {
-                            let msg = format!("{}.read | Close tcp stream error: {:#?}", self.dbgid, err);
-                            if let IsConnected::Closed(_) = self.parse_err(err) {
-                                if let Err(err) = self.close() {
-                                    log::warn!("{}.read | Close tcp stream error: {:?}", self.dbgid, err);
-                                }
-                            };
-                            return Err(StrErr(msg));
+    /// - Messages already received by the previous reads are returned first, without reading the socket
+    /// - [Heartbeat] messages are handled internally and never returned
+    /// - In the pipelined mode returns the reply to any pending request, see [TcpSocket::reply]
+    pub fn read(&mut self) -> Result<(FieldId, MsgKind), SocketErr> {
+        match self.poll()? {
+            Some(message) => Ok(message),
+            None => Err(self.expired(SocketErr::ReadTimeout(self.timeouts.read))),
+        }
+    }
+    ///
+    /// Reads a [Message] same as [TcpSocket::read], but returns None if nothing received in the `read` timeout
+    /// - Expired `read` timeout isn't an error, so the connection is kept and the pending requests are waiting further
+    /// - In the pipelined mode the replies already received are returned first, the lowest `Id` first
+    pub fn poll(&mut self) -> Result<Option<(FieldId, MsgKind)>, SocketErr> {
+        if self.pending.is_none() {
+            return self.poll_message();
+        }
+        loop {
+            let received = self.pending.as_ref()
+                .and_then(|pending| pending.iter().find_map(|(id, reply)| (!reply.received.is_empty()).then_some(*id)));
+            if let Some(id) = received {
+                if let Some(value) = self.take(id) {
+                    return Ok(Some((FieldId(id), value)));
+                }
+            }
+            match self.poll_message()? {
+                Some((id, value)) => self.receive(id, value)?,
+                None => return Ok(None),
+            }
+        }
+    }
+    ///
+    /// Returns true if the connection is opened
+    pub fn is_connected(&self) -> bool {
+        self.connection.is_some()
+    }
+    ///
+    /// Reads the reply to the request sent with `id` in the pipelined mode, see [TcpSocket::with_pipelining]
+    /// - Replies to the other pending requests received meanwhile are kept until requested
+    /// - Fragmented reply is returned by fragment on each call
+    /// - Returns error if the request with `id` isn't pending or the reply with unknown `Id` received
+    pub fn reply(&mut self, id: &FieldId) -> Result<MsgKind, SocketErr> {
+        loop {
+            match &self.pending {
+                Some(pending) => if !pending.contains_key(&id.0) {
+                    return Err(format!("{}.reply | Request {} isn't pending", self.dbgid, id.0).into());
+                }
+                None => return Err(format!("{}.reply | Pipelining isn't enabled", self.dbgid).into()),
+            }
+            if let Some(value) = self.take(id.0) {
+                return Ok(value);
+            }
+            let (received, value) = self.read_message()?;
+            self.receive(received, value)?;
+        }
+    }
+    ///
+    /// Returns the next received reply to the request `id`, the request is done after it's last reply taken
+    fn take(&mut self, id: u32) -> Option<MsgKind> {
+        let pending = self.pending.as_mut()?;
+        let reply = pending.get_mut(&id)?;
+        let value = reply.received.pop_front();
+        if reply.done && reply.received.is_empty() {
+            pending.remove(&id);
+        }
+        value
+    }
+    ///
+    /// Keeps the received reply until requested
+    fn receive(&mut self, id: FieldId, value: MsgKind) -> Result<(), SocketErr> {
+        match self.pending.as_mut().and_then(|pending| pending.get_mut(&id.0)) {
+            Some(reply) if !reply.done => {
+                reply.done = !matches!(&value, MsgKind::Fragment(fragment) if !fragment.last);
+                reply.received.push_back(value);
+                Ok(())
+            }
+            Some(_) => Err(format!("{}.reply | Duplicated reply {}", self.dbgid, id.0).into()),
+            None => Err(self.unknown(&id, &value)),
+        }
+    }
+    ///
+    /// Returns the number of the requests waiting for the reply in the pipelined mode
+    pub fn pending(&self) -> usize {
+        self.pending.as_ref().map_or(0, |pending| pending.len())
+    }
+    ///
+    /// Drops the pending request `id` in the pipelined mode, for example if the caller doesn't wait for it anymore
+    /// - Late reply to the dropped request is received as the reply with unknown `Id`
+    pub fn cancel(&mut self, id: &FieldId) {
+        if let Some(pending) = &mut self.pending {
+            pending.remove(&id.0);
+        }
+    }
+    ///
+    /// Returns the error of the received message having `Id` of no pending request
+    fn unknown(&self, id: &FieldId, value: &MsgKind) -> SocketErr {
+        let err = format!("{}.read | Reply with unknown id {} received: {:?}", self.dbgid, id.0, value.kind());
+        log::warn!("{}", err);
+        err.into()
+    }
+    ///
+    /// Reads the next [Message] parsed from TCP socket
+    /// - Returns [SocketErr::ReadTimeout] if nothing received in the `read` timeout
+    fn read_message(&mut self) -> Result<(FieldId, MsgKind), SocketErr> {
+        match self.poll_message()? {
+            Some(message) => Ok(message),
+            None => Err(self.expired(SocketErr::ReadTimeout(self.timeouts.read))),
+        }
+    }
+    ///
+    /// Reads the next [Message] parsed from TCP socket
+    /// - Returns None if nothing received in the `read` timeout
+    fn poll_message(&mut self) -> Result<Option<(FieldId, MsgKind)>, SocketErr> {
+        if let Some(frame) = self.next_frame()? {
+            return self.decode(frame).map(Some);
+        }
+        let stream = self.connect()?;
+        let time = Instant::now();
+        loop {
+            match self.deadline_left() {
+                Ok(Some(left)) => if let Err(err) = stream.set_read_timeout(Some(left.min(self.read_timeout()))) {
+                    log::warn!("{}.read | set_read_timeout error: \n\t{:?}", self.dbgid, err);
+                }
+                Ok(None) => {}
+                Err(err) => return Err(self.expired(err)),
+            }
+            match self.message.read_from(Self::BUF_LEN, &mut |buf| stream.read(buf)) {
+                Ok(len) => {
+                    log::trace!("{}.read |     read len: {:?}", self.dbgid, len);
+                    if len > 0 {
+                        self.received = Instant::now();
+                    }
+                    for result in self.message.parse_all(&[]) {
+                        match result {
+                            Ok(frame) => self.frames.push_back(frame),
+                            Err(err) => log::warn!("{}.read | Resynchronising: {}", self.dbgid, err),
                         }
-                    };
-                    if time.elapsed() > self.timeout {
-                        let err = format!("{}.read | No valid message received in specified timeout {:?}", self.dbgid, self.timeout);
-                        log::warn!("{}", err);
-                        return Err(StrErr(err));
+                    }
+                    let skipped = self.message.skipped();
+                    if skipped > 0 {
+                        log::warn!("{}.read | Skipped {} bytes searching for the message start", self.dbgid, skipped);
+                    }
+                    if let Some(frame) = self.next_frame()? {
+                        return self.decode(frame).map(Some);
+                    }
+                    if len == 0 {
+                        let err = format!("{}.read | tcp stream closed", self.dbgid);
+                        self.lost(&err);
+                        return Err(err.into());
                     }
                 }
+                Err(err) => match self.parse_err(&err) {
+                    IoOutcome::Retry => {}
+                    IoOutcome::Timeout => self.check_heartbeat()?,
+                    IoOutcome::Closed => {
+                        let err = format!("{}.read | Close tcp stream error: {:#?}", self.dbgid, err);
+                        self.lost(&err);
+                        return Err(err.into());
+                    }
+                }
+            };
+            if time.elapsed() >= self.timeouts.read {
+                return Ok(None);
             }
-            Err(err) => {
-                let err = format!("{}.read | Connection error: {:?}", self.dbgid, err);
-                log::warn!("{}", err);
-                return Err(StrErr(err));
+        }
+    }
+    ///
+    /// Returns the next received message, answering and skipping the [Heartbeat] messages
+    fn next_frame(&mut self) -> Result<Option<(FieldId, MessageKind, FieldSize, Bytes)>, SocketErr> {
+        while let Some(frame) = self.frames.pop_front() {
+            if let Some(frame) = self.heartbeat_frame(frame)? {
+                return Ok(Some(frame));
             }
+        }
+        Ok(None)
+    }
+    ///
+    /// Answers the ping and accepts the pong, returns any other `frame` back
+    fn heartbeat_frame(&mut self, frame: (FieldId, MessageKind, FieldSize, Bytes)) -> Result<Option<(FieldId, MessageKind, FieldSize, Bytes)>, SocketErr> {
+        let max_id = self.message.max_id();
+        let (id, kind, _, _) = &frame;
+        if Heartbeat::is_ping_within(id, kind, max_id) {
+            log::trace!("{}.heartbeat_frame | Ping received", self.dbgid);
+            self.write(Some(Heartbeat::pong_id(max_id).0), false, |message, id| message.build_kind(&MsgKind::Empty, id))?;
+            Ok(None)
+        } else if Heartbeat::is_pong_within(id, kind, max_id) {
+            log::trace!("{}.heartbeat_frame | Pong received", self.dbgid);
+            self.ping = None;
+            Ok(None)
+        } else {
+            Ok(Some(frame))
+        }
+    }
+    ///
+    /// Sends the ping if the connection is idle, closes the connection if the pong isn't received in the deadline
+    fn check_heartbeat(&mut self) -> Result<(), SocketErr> {
+        let heartbeat = match self.heartbeat.clone() {
+            Some(heartbeat) => heartbeat,
+            None => return Ok(()),
         };
+        match self.ping {
+            Some(ping) => if ping.elapsed() > heartbeat.deadline {
+                let err = format!("{}.check_heartbeat | Pong not received in specified deadline {:?}, connection closed", self.dbgid, heartbeat.deadline);
+                log::warn!("{}", err);
+                self.lost(&err);
+                self.ping = None;
+                return Err(err.into());
+            }
+            None => if self.received.elapsed() >= heartbeat.idle {
+                log::trace!("{}.check_heartbeat | Idle {:?}, ping sent", self.dbgid, self.received.elapsed());
+                let ping = Heartbeat::ping_id(self.message.max_id());
+                self.write(Some(ping.0), false, |message, id| message.build_kind(&MsgKind::Empty, id))?;
+                self.ping = Some(Instant::now());
+            }
+        }
+        Ok(())
+    }
+    ///
+    /// Reads the payload sent in [MsgKind::Fragment]'s, writing each fragment into the `writer` as it arrives
+    /// - Returns `Id`, `Kind` & the length of the payload in bytes
+    /// - Any other message or fragment out of order is an error
+    pub fn read_stream(&mut self, writer: &mut impl Write) -> Result<(FieldId, MessageKind, u64), SocketErr> {
+        let mut stream = FragmentStream::new();
+        loop {
+            match self.read()? {
+                (id, MsgKind::Fragment(fragment)) => {
+                    let fragment = stream.push(&id, fragment)
+                        .map_err(|err| StrErr(format!("{}.read_stream | {}", self.dbgid, err)))?;
+                    writer.write_all(&fragment.bytes)
+                        .map_err(|err| StrErr(format!("{}.read_stream | Write error: {:?}", self.dbgid, err)))?;
+                    if stream.is_done() {
+                        return Ok((id, fragment.kind, stream.received()));
+                    }
+                }
+                (id, value) => {
+                    return Err(format!("{}.read_stream | Expected Fragment, but received {:?}: {:?}", self.dbgid, id, value.kind()).into());
+                }
+            }
+        }
+    }
+    ///
+    /// Returns the [MsgKind] decoded from the parsed message
+    fn decode(&self, (id, kind, size, bytes): (FieldId, MessageKind, FieldSize, Bytes)) -> Result<(FieldId, MsgKind), SocketErr> {
+        let dbg_bytes = if bytes.len() > 16 {format!("{:?} ...", &bytes[..16])} else {format!("{:?}", bytes)};
+        log::trace!("{}.read | id: {:?},  kind: {:?},  size: {:?},  bytes: {:?}", self.dbgid, id, kind, size, dbg_bytes);
+        MsgKind::from_be_bytes(&kind, &bytes)
+            .map(|value| (id, value))
+            .map_err(|err| format!("{}.read | Decode error: {}", self.dbgid, err).into())
     }
     ///
-    /// Returns Connection status dipending on IO Error
-    fn parse_err(&self, err: std::io::Error) -> IsConnected<(), StrErr> {
-        log::warn!("{}.parse_err | error reading from socket: {:?}", self.dbgid, err);
-        log::warn!("{}.parse_err | error kind: {:?}", self.dbgid, err.kind());
-        let str_err = format!("{}.parse_err | error: {:#?}", self.dbgid, err).into();
-        match err.kind() {
-            // std::io::ErrorKind::NotFound => todo!(),
-            std::io::ErrorKind::PermissionDenied => IsConnected::Closed(str_err),
-            std::io::ErrorKind::ConnectionRefused => IsConnected::Closed(str_err),
-            std::io::ErrorKind::ConnectionReset => IsConnected::Closed(str_err),
-            // std::io::ErrorKind::HostUnreachable => ConnectionStatus::Closed(str_err),
-            // std::io::ErrorKind::NetworkUnreachable => ConnectionStatus::Closed(str_err),
-            std::io::ErrorKind::ConnectionAborted => IsConnected::Closed(str_err),
-            std::io::ErrorKind::NotConnected => IsConnected::Closed(str_err),
-            std::io::ErrorKind::AddrInUse => IsConnected::Closed(str_err),
-            std::io::ErrorKind::AddrNotAvailable => IsConnected::Closed(str_err),
-            // std::io::ErrorKind::NetworkDown => ConnectionStatus::Closed(str_err),
-            std::io::ErrorKind::BrokenPipe => IsConnected::Closed(str_err),
-            std::io::ErrorKind::AlreadyExists => todo!(),
-            std::io::ErrorKind::WouldBlock => IsConnected::Closed(str_err),
-            // std::io::ErrorKind::NotADirectory => todo!(),
-            // std::io::ErrorKind::IsADirectory => todo!(),
-            // std::io::ErrorKind::DirectoryNotEmpty => todo!(),
-            // std::io::ErrorKind::ReadOnlyFilesystem => todo!(),
-            // std::io::ErrorKind::FilesystemLoop => todo!(),
-            // std::io::ErrorKind::StaleNetworkFileHandle => todo!(),
-            std::io::ErrorKind::InvalidInput => todo!(),
-            std::io::ErrorKind::InvalidData => todo!(),
-            std::io::ErrorKind::TimedOut => todo!(),
-            std::io::ErrorKind::WriteZero => todo!(),
-            // std::io::ErrorKind::StorageFull => todo!(),
-            // std::io::ErrorKind::NotSeekable => todo!(),
-            // std::io::ErrorKind::FilesystemQuotaExceeded => todo!(),
-            // std::io::ErrorKind::FileTooLarge => todo!(),
-            // std::io::ErrorKind::ResourceBusy => todo!(),
-            // std::io::ErrorKind::ExecutableFileBusy => todo!(),
-            // std::io::ErrorKind::Deadlock => todo!(),
-            // std::io::ErrorKind::CrossesDevices => todo!(),
-            // std::io::ErrorKind::TooManyLinks => todo!(),
-            // std::io::ErrorKind::InvalidFilename => todo!(),
-            // std::io::ErrorKind::ArgumentListTooLong => todo!(),
-            std::io::ErrorKind::Interrupted => todo!(),
-            std::io::ErrorKind::Unsupported => todo!(),
-            std::io::ErrorKind::UnexpectedEof => todo!(),
-            std::io::ErrorKind::OutOfMemory => todo!(),
-            std::io::ErrorKind::Other => todo!(),
-            _ => IsConnected::Closed(str_err),
+    /// Returns the way the failed IO operation to be handled, see [IoOutcome::from]
+    fn parse_err(&self, err: &std::io::Error) -> IoOutcome {
+        let outcome = IoOutcome::from(err.kind());
+        match outcome {
+            IoOutcome::Retry | IoOutcome::Timeout => log::trace!("{}.parse_err | {:?}: {:?}", self.dbgid, outcome, err),
+            IoOutcome::Closed => log::warn!("{}.parse_err | {:?}: {:?}", self.dbgid, outcome, err),
         }
+        outcome
     }
 }
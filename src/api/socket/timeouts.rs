@@ -0,0 +1,61 @@
+use std::time::Duration;
+///
+/// Timeouts of the [TcpSocket](super::tcp_socket::TcpSocket) operations
+/// - `connect` - connection must be established in, including all the reconnect attempts
+/// - `read` - the message must be received in
+/// - `write` - the message must be written in
+/// - `deadline` - whole request (connect, write & read the reply) must be completed in, not limited by default
+/// ```ignore
+/// let timeouts = Timeouts::new(Duration::from_secs(3))
+///     .with_connect(Duration::from_millis(500))
+///     .with_deadline(Duration::from_secs(5));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Timeouts {
+    pub connect: Duration,
+    pub read: Duration,
+    pub write: Duration,
+    pub deadline: Option<Duration>,
+}
+//
+//
+impl Timeouts {
+    ///
+    /// Returns [Timeouts] with the same `timeout` of connect, read & write, the deadline isn't limited
+    pub fn new(timeout: Duration) -> Self {
+        Self { connect: timeout, read: timeout, write: timeout, deadline: None }
+    }
+    ///
+    /// Returns [Timeouts] with specified `connect` timeout
+    pub fn with_connect(mut self, connect: Duration) -> Self {
+        self.connect = connect;
+        self
+    }
+    ///
+    /// Returns [Timeouts] with specified `read` timeout
+    pub fn with_read(mut self, read: Duration) -> Self {
+        self.read = read;
+        self
+    }
+    ///
+    /// Returns [Timeouts] with specified `write` timeout
+    pub fn with_write(mut self, write: Duration) -> Self {
+        self.write = write;
+        self
+    }
+    ///
+    /// Returns [Timeouts] with specified `deadline` of the whole request
+    pub fn with_deadline(mut self, deadline: Duration) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+}
+//
+//
+impl Default for Timeouts {
+    ///
+    /// 10 sec connect, read & write, the deadline isn't limited
+    fn default() -> Self {
+        Self::new(Duration::from_secs(10))
+    }
+}
@@ -0,0 +1,381 @@
+use std::{
+    io::{Read, Write}, net::{Shutdown, TcpStream, ToSocketAddrs}, ops::{Deref, DerefMut}, path::Path, sync::{Arc, Mutex}, time::{Duration, Instant},
+};
+use rustls::{
+    client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier},
+    crypto::{self, CryptoProvider},
+    pki_types::{CertificateDer, PrivateKeyDer, ServerName, UnixTime},
+    server::WebPkiClientVerifier,
+    CertificateError, ClientConfig, ClientConnection, ConnectionCommon, DigitallySignedStruct, RootCertStore,
+    ServerConfig, ServerConnection, SideData, SignatureScheme, StreamOwned,
+};
+use crate::error::str_err::StrErr;
+use super::transport::{Endpoint, TcpEndpoint, Transport};
+///
+/// Returns the crypto provider used by all TLS configs
+fn provider() -> Arc<CryptoProvider> {
+    Arc::new(crypto::ring::default_provider())
+}
+///
+/// Returns all certificates found in the `pem`
+fn certs(pem: &[u8]) -> Result<Vec<CertificateDer<'static>>, StrErr> {
+    let certs = rustls_pemfile::certs(&mut &*pem)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|err| StrErr(format!("TlsConfig.certs | PEM error: {:?}", err)))?;
+    match certs.is_empty() {
+        true => Err(StrErr("TlsConfig.certs | No certificates found in PEM".to_owned())),
+        false => Ok(certs),
+    }
+}
+///
+/// Returns the PEM content of the file at the `path`
+fn read_pem(path: impl AsRef<Path>) -> Result<Vec<u8>, StrErr> {
+    std::fs::read(path.as_ref())
+        .map_err(|err| StrErr(format!("TlsConfig.read_pem | Read '{}' error: {:?}", path.as_ref().display(), err)))
+}
+///
+/// Certificate chain and the private key, identifying the server or the client
+pub struct TlsIdentity {
+    certs: Vec<CertificateDer<'static>>,
+    key: PrivateKeyDer<'static>,
+}
+//
+//
+impl TlsIdentity {
+    ///
+    /// Returns [TlsIdentity] parsed from PEM encoded certificate chain and private key
+    pub fn from_pem(cert_pem: &[u8], key_pem: &[u8]) -> Result<Self, StrErr> {
+        let key = match rustls_pemfile::private_key(&mut &*key_pem) {
+            Ok(Some(key)) => key,
+            Ok(None) => return Err(StrErr("TlsIdentity.from_pem | No private key found in PEM".to_owned())),
+            Err(err) => return Err(StrErr(format!("TlsIdentity.from_pem | PEM error: {:?}", err))),
+        };
+        Ok(Self { certs: certs(cert_pem)?, key })
+    }
+    ///
+    /// Returns [TlsIdentity] read from PEM files
+    pub fn from_pem_files(cert_path: impl AsRef<Path>, key_path: impl AsRef<Path>) -> Result<Self, StrErr> {
+        Self::from_pem(&read_pem(cert_path)?, &read_pem(key_path)?)
+    }
+}
+//
+//
+impl Clone for TlsIdentity {
+    fn clone(&self) -> Self {
+        Self { certs: self.certs.clone(), key: self.key.clone_key() }
+    }
+}
+//
+//
+impl std::fmt::Debug for TlsIdentity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TlsIdentity")
+            .field("certs", &self.certs.len())
+            .finish_non_exhaustive()
+    }
+}
+///
+/// How the peer certificate is verified
+#[derive(Debug, Clone)]
+pub enum TlsVerify {
+    ///
+    /// Certificate chain must be signed by one of the CA certificates and match the server name
+    Ca(Vec<CertificateDer<'static>>),
+    ///
+    /// Certificate must be exactly one of the pinned certificates, the server name isn't checked
+    Pinned(Vec<CertificateDer<'static>>),
+}
+//
+//
+impl TlsVerify {
+    ///
+    /// Returns [TlsVerify::Ca] with certificates from the PEM encoded CA bundle
+    pub fn ca(pem: &[u8]) -> Result<Self, StrErr> {
+        Ok(Self::Ca(certs(pem)?))
+    }
+    ///
+    /// Returns [TlsVerify::Ca] with certificates from the CA bundle file
+    pub fn ca_file(path: impl AsRef<Path>) -> Result<Self, StrErr> {
+        Self::ca(&read_pem(path)?)
+    }
+    ///
+    /// Returns [TlsVerify::Pinned] with the PEM encoded certificates
+    pub fn pinned(pem: &[u8]) -> Result<Self, StrErr> {
+        Ok(Self::Pinned(certs(pem)?))
+    }
+}
+///
+/// Client side TLS configuration
+/// ```ignore
+/// let config = TlsConfig::new(TlsVerify::ca_file("ca.pem")?, None)?;
+/// let endpoint = TlsEndpoint::new("api.local:8443", "api.local", config)?;
+/// let request = ApiRequest::with_endpoint(&dbgid, endpoint, token, query, true, false);
+/// ```
+#[derive(Debug, Clone)]
+pub struct TlsConfig {
+    config: Arc<ClientConfig>,
+}
+//
+//
+impl TlsConfig {
+    ///
+    /// Returns [TlsConfig] new instance
+    /// - `verify` - how the server certificate is verified
+    /// - `identity` - client certificate, if required by the server
+    pub fn new(verify: TlsVerify, identity: Option<TlsIdentity>) -> Result<Self, StrErr> {
+        let provider = provider();
+        let builder = ClientConfig::builder_with_provider(provider.clone())
+            .with_safe_default_protocol_versions()
+            .map_err(|err| StrErr(format!("TlsConfig.new | Protocol versions error: {:?}", err)))?;
+        let builder = match verify {
+            TlsVerify::Ca(certs) => {
+                let roots = root_store(certs).map_err(|err| StrErr(format!("TlsConfig.new | {}", err)))?;
+                builder.with_root_certificates(roots)
+            }
+            TlsVerify::Pinned(certs) => builder
+                .dangerous()
+                .with_custom_certificate_verifier(Arc::new(PinnedVerifier { certs, provider })),
+        };
+        let config = match identity {
+            Some(identity) => builder.with_client_auth_cert(identity.certs, identity.key)
+                .map_err(|err| StrErr(format!("TlsConfig.new | Client certificate error: {:?}", err)))?,
+            None => builder.with_no_client_auth(),
+        };
+        Ok(Self { config: Arc::new(config) })
+    }
+}
+///
+/// Returns [RootCertStore] containing all the `certs`
+fn root_store(certs: Vec<CertificateDer<'static>>) -> Result<RootCertStore, StrErr> {
+    let mut roots = RootCertStore::empty();
+    for cert in certs {
+        roots.add(cert).map_err(|err| StrErr(format!("Invalid CA certificate: {:?}", err)))?;
+    }
+    Ok(roots)
+}
+///
+/// Accepts only the server presenting one of the pinned certificates
+#[derive(Debug)]
+struct PinnedVerifier {
+    certs: Vec<CertificateDer<'static>>,
+    provider: Arc<CryptoProvider>,
+}
+//
+//
+impl ServerCertVerifier for PinnedVerifier {
+    fn verify_server_cert(&self, end_entity: &CertificateDer<'_>, _intermediates: &[CertificateDer<'_>], _server_name: &ServerName<'_>, _ocsp_response: &[u8], _now: UnixTime) -> Result<ServerCertVerified, rustls::Error> {
+        match self.certs.iter().any(|cert| cert.as_ref() == end_entity.as_ref()) {
+            true => Ok(ServerCertVerified::assertion()),
+            false => Err(rustls::Error::InvalidCertificate(CertificateError::ApplicationVerificationFailure)),
+        }
+    }
+    fn verify_tls12_signature(&self, message: &[u8], cert: &CertificateDer<'_>, dss: &DigitallySignedStruct) -> Result<HandshakeSignatureValid, rustls::Error> {
+        crypto::verify_tls12_signature(message, cert, dss, &self.provider.signature_verification_algorithms)
+    }
+    fn verify_tls13_signature(&self, message: &[u8], cert: &CertificateDer<'_>, dss: &DigitallySignedStruct) -> Result<HandshakeSignatureValid, rustls::Error> {
+        crypto::verify_tls13_signature(message, cert, dss, &self.provider.signature_verification_algorithms)
+    }
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.provider.signature_verification_algorithms.supported_schemes()
+    }
+}
+///
+/// Server side TLS configuration, accepts the incoming TCP connections
+#[derive(Debug, Clone)]
+pub struct TlsServerConfig {
+    config: Arc<ServerConfig>,
+}
+//
+//
+impl TlsServerConfig {
+    ///
+    /// Returns [TlsServerConfig] new instance
+    /// - `identity` - server certificate
+    /// - `client_ca` - PEM encoded CA bundle, if specified, the client certificate signed by one of these CA's is required
+    pub fn new(identity: TlsIdentity, client_ca: Option<&[u8]>) -> Result<Self, StrErr> {
+        let provider = provider();
+        let builder = ServerConfig::builder_with_provider(provider.clone())
+            .with_safe_default_protocol_versions()
+            .map_err(|err| StrErr(format!("TlsServerConfig.new | Protocol versions error: {:?}", err)))?;
+        let builder = match client_ca {
+            Some(pem) => {
+                let roots = certs(pem)
+                    .and_then(root_store)
+                    .map_err(|err| StrErr(format!("TlsServerConfig.new | {}", err)))?;
+                let verifier = WebPkiClientVerifier::builder_with_provider(Arc::new(roots), provider)
+                    .build()
+                    .map_err(|err| StrErr(format!("TlsServerConfig.new | Verifier error: {:?}", err)))?;
+                builder.with_client_cert_verifier(verifier)
+            }
+            None => builder.with_no_client_auth(),
+        };
+        let config = builder.with_single_cert(identity.certs, identity.key)
+            .map_err(|err| StrErr(format!("TlsServerConfig.new | Server certificate error: {:?}", err)))?;
+        Ok(Self { config: Arc::new(config) })
+    }
+    ///
+    /// Performs the server side TLS handshake on the accepted `stream`
+    /// - `timeout` - the handshake must be completed in
+    pub fn accept(&self, stream: TcpStream, timeout: Duration) -> std::io::Result<TlsTransport<ServerConnection>> {
+        let conn = ServerConnection::new(self.config.clone())
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))?;
+        TlsTransport::handshake(conn, stream, timeout)
+    }
+}
+///
+/// [Transport] encrypting the bytes written into and decrypting the bytes read from the TCP stream
+/// - [Message](crate::api::message::message::Message) framing is unchanged on top of the encrypted stream
+/// - The reading and the writing sides are locked separately, the TLS state is locked only to encrypt or decrypt,
+///   never during the blocking socket IO, so the idle reader doesn't block the writer
+#[derive(Debug)]
+pub struct TlsTransport<C> {
+    socket: TcpStream,
+    writer: Mutex<TcpStream>,
+    conn: Mutex<C>,
+}
+//
+//
+impl<C, S> TlsTransport<C> where
+    C: DerefMut + Deref<Target = ConnectionCommon<S>>,
+    S: SideData {
+    ///
+    /// Bytes read from the socket at once
+    const BUF_LEN: usize = 1024 * 16;
+    ///
+    /// Returns [TlsTransport] with completed handshake
+    /// - `timeout` - the handshake must be completed in
+    fn handshake(mut conn: C, mut socket: TcpStream, timeout: Duration) -> std::io::Result<Self> {
+        socket.set_read_timeout(Some(timeout))?;
+        socket.set_write_timeout(Some(timeout))?;
+        while conn.is_handshaking() {
+            conn.complete_io(&mut socket)?;
+        }
+        // Whole message is encrypted at once, see [TlsTransport::write_all]
+        conn.set_buffer_limit(None);
+        Ok(Self {
+            writer: Mutex::new(socket.try_clone()?),
+            socket,
+            conn: Mutex::new(conn),
+        })
+    }
+    ///
+    /// Writes the TLS records pending in the `conn` into the socket
+    /// - The writing side must be locked before the `conn`, so the records are written in the order they are encrypted
+    fn flush(&self, writer: &mut TcpStream) -> std::io::Result<()> {
+        let mut records = vec![];
+        {
+            let mut conn = self.conn.lock().unwrap_or_else(|err| err.into_inner());
+            while conn.wants_write() {
+                conn.write_tls(&mut records)?;
+            }
+        }
+        writer.write_all(&records)?;
+        writer.flush()
+    }
+}
+//
+//
+impl<C, S> Transport for TlsTransport<C> where
+    C: DerefMut + Deref<Target = ConnectionCommon<S>> + std::fmt::Debug + Send,
+    S: SideData {
+    fn read(&self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let mut tls = [0; Self::BUF_LEN];
+        loop {
+            let wants_write = {
+                let mut conn = self.conn.lock().unwrap_or_else(|err| err.into_inner());
+                match conn.reader().read(buf) {
+                    Ok(len) => return Ok(len),
+                    Err(err) if err.kind() == std::io::ErrorKind::WouldBlock => {}
+                    Err(err) => return Err(err),
+                }
+                conn.wants_write()
+            };
+            // Answers, like the key update, are written before the next read
+            if wants_write {
+                let mut writer = self.writer.lock().unwrap_or_else(|err| err.into_inner());
+                self.flush(&mut writer)?;
+            }
+            // Blocking read, the TLS state isn't locked
+            let len = (&self.socket).read(&mut tls)?;
+            let mut conn = self.conn.lock().unwrap_or_else(|err| err.into_inner());
+            let mut records = &tls[..len];
+            // Zero length read notifies the `conn` about the end of the stream
+            loop {
+                conn.read_tls(&mut records)?;
+                conn.process_new_packets()
+                    .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))?;
+                if records.is_empty() {
+                    break;
+                }
+            }
+        }
+    }
+    fn write_all(&self, bytes: &[u8]) -> std::io::Result<()> {
+        let mut writer = self.writer.lock().unwrap_or_else(|err| err.into_inner());
+        self.conn.lock().unwrap_or_else(|err| err.into_inner()).writer().write_all(bytes)?;
+        self.flush(&mut writer)
+    }
+    fn set_read_timeout(&self, timeout: Option<Duration>) -> std::io::Result<()> {
+        self.socket.set_read_timeout(timeout)
+    }
+    fn set_write_timeout(&self, timeout: Option<Duration>) -> std::io::Result<()> {
+        self.socket.set_write_timeout(timeout)
+    }
+    fn shutdown(&self) -> std::io::Result<()> {
+        if let Ok(mut writer) = self.writer.try_lock() {
+            self.conn.lock().unwrap_or_else(|err| err.into_inner()).send_close_notify();
+            if let Err(err) = self.flush(&mut writer) {
+                log::debug!("TlsTransport.shutdown | Close notify error: {:?}", err);
+            }
+        }
+        self.socket.shutdown(Shutdown::Both)
+    }
+}
+///
+/// TLS over TCP [Endpoint]
+#[derive(Debug, Clone)]
+pub struct TlsEndpoint {
+    tcp: TcpEndpoint,
+    server_name: ServerName<'static>,
+    config: TlsConfig,
+}
+//
+//
+impl TlsEndpoint {
+    ///
+    /// Returns [TlsEndpoint] new instance
+    /// - `addr` - TCP address of the server
+    /// - `server_name` - DNS name or IP the server certificate is issued for
+    /// - Returns error if `addr` can't be resolved or `server_name` is invalid
+    pub fn new(addr: impl ToSocketAddrs + std::fmt::Debug, server_name: &str, config: TlsConfig) -> Result<Self, StrErr> {
+        let resolved = match addr.to_socket_addrs() {
+            Ok(mut addrs) => addrs.next()
+                .ok_or_else(|| StrErr(format!("TlsEndpoint.new | Empty address: {:?}", addr)))?,
+            Err(err) => return Err(StrErr(format!("TlsEndpoint.new | Address {:?} error: {:?}", addr, err))),
+        };
+        let server_name = ServerName::try_from(server_name.to_owned())
+            .map_err(|err| StrErr(format!("TlsEndpoint.new | Invalid server name '{}': {:?}", server_name, err)))?;
+        Ok(Self { tcp: TcpEndpoint::new(resolved), server_name, config })
+    }
+}
+//
+//
+impl Endpoint for TlsEndpoint {
+    ///
+    /// - `timeout` - both TCP connection & TLS handshake must be completed in
+    fn connect(&self, timeout: Duration) -> std::io::Result<Arc<dyn Transport>> {
+        let time = Instant::now();
+        let socket = TcpStream::connect_timeout(&self.tcp.addr(), timeout)?;
+        let conn = ClientConnection::new(self.config.config.clone(), self.server_name.clone())
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))?;
+        let timeout = timeout.saturating_sub(time.elapsed());
+        if timeout.is_zero() {
+            return Err(std::io::Error::new(std::io::ErrorKind::TimedOut, "TlsEndpoint.connect | Handshake not started in specified timeout"));
+        }
+        TlsTransport::handshake(conn, socket, timeout)
+            .map(|transport| Arc::new(transport) as Arc<dyn Transport>)
+    }
+    fn name(&self) -> String {
+        format!("tls://{}", self.tcp.name())
+    }
+}
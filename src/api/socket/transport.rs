@@ -0,0 +1,142 @@
+use std::{io::{Read, Write}, net::{Shutdown, SocketAddr, TcpStream, ToSocketAddrs}, sync::Arc, time::Duration};
+#[cfg(unix)]
+use std::{os::unix::net::UnixStream, path::PathBuf};
+///
+/// Connected byte stream the [TcpSocket](super::tcp_socket::TcpSocket) framing works on
+/// - Shared between the reading and the writing side, so all methods take `&self`
+/// - Read timeout expiration is reported as [std::io::ErrorKind::WouldBlock] or [std::io::ErrorKind::TimedOut]
+/// - Read returns 0 if the peer closed the stream
+pub trait Transport: std::fmt::Debug + Send + Sync {
+    ///
+    /// Reads available bytes into the `buf`, returns the number of bytes read
+    fn read(&self, buf: &mut [u8]) -> std::io::Result<usize>;
+    ///
+    /// Writes all the `bytes`
+    /// - Interrupted writes are retried, returned error may leave the `bytes` partially written
+    fn write_all(&self, bytes: &[u8]) -> std::io::Result<()>;
+    ///
+    /// Sets the timeout of the blocking read, None - blocks infinitely
+    fn set_read_timeout(&self, timeout: Option<Duration>) -> std::io::Result<()>;
+    ///
+    /// Sets the timeout of the blocking write, None - blocks infinitely
+    fn set_write_timeout(&self, timeout: Option<Duration>) -> std::io::Result<()>;
+    ///
+    /// Closes both reading and writing sides
+    fn shutdown(&self) -> std::io::Result<()>;
+}
+///
+/// Opens the new [Transport] connection to the remote side
+pub trait Endpoint: std::fmt::Debug + Send + Sync {
+    ///
+    /// Returns the new connection
+    /// - `timeout` - the connection must be established in, if supported by the underlying transport
+    fn connect(&self, timeout: Duration) -> std::io::Result<Arc<dyn Transport>>;
+    ///
+    /// Returns the human readable address of the remote side
+    fn name(&self) -> String;
+}
+//
+//
+impl Transport for TcpStream {
+    fn read(&self, buf: &mut [u8]) -> std::io::Result<usize> {
+        Read::read(&mut &*self, buf)
+    }
+    fn write_all(&self, bytes: &[u8]) -> std::io::Result<()> {
+        Write::write_all(&mut &*self, bytes)
+    }
+    fn set_read_timeout(&self, timeout: Option<Duration>) -> std::io::Result<()> {
+        TcpStream::set_read_timeout(self, timeout)
+    }
+    fn set_write_timeout(&self, timeout: Option<Duration>) -> std::io::Result<()> {
+        TcpStream::set_write_timeout(self, timeout)
+    }
+    fn shutdown(&self) -> std::io::Result<()> {
+        TcpStream::shutdown(self, Shutdown::Both)
+    }
+}
+///
+/// TCP [Endpoint]
+#[derive(Debug, Clone)]
+pub struct TcpEndpoint {
+    addr: SocketAddr,
+}
+//
+//
+impl TcpEndpoint {
+    ///
+    /// Returns [TcpEndpoint] new instance
+    /// - Panics if `addr` can't be resolved
+    pub fn new(addr: impl ToSocketAddrs + std::fmt::Debug) -> Self {
+        let addr = match addr.to_socket_addrs() {
+            Ok(mut addrs) => match addrs.next() {
+                Some(addr) => addr,
+                None => panic!("TcpEndpoint.new | Empty address: {:?}", addr),
+            },
+            Err(err) => panic!("TcpEndpoint.new | Address error: {:#?}", err),
+        };
+        Self { addr }
+    }
+    ///
+    /// Returns the address of the remote host
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+}
+//
+//
+impl Endpoint for TcpEndpoint {
+    fn connect(&self, timeout: Duration) -> std::io::Result<Arc<dyn Transport>> {
+        TcpStream::connect_timeout(&self.addr, timeout).map(|stream| Arc::new(stream) as Arc<dyn Transport>)
+    }
+    fn name(&self) -> String {
+        self.addr.to_string()
+    }
+}
+//
+//
+#[cfg(unix)]
+impl Transport for UnixStream {
+    fn read(&self, buf: &mut [u8]) -> std::io::Result<usize> {
+        Read::read(&mut &*self, buf)
+    }
+    fn write_all(&self, bytes: &[u8]) -> std::io::Result<()> {
+        Write::write_all(&mut &*self, bytes)
+    }
+    fn set_read_timeout(&self, timeout: Option<Duration>) -> std::io::Result<()> {
+        UnixStream::set_read_timeout(self, timeout)
+    }
+    fn set_write_timeout(&self, timeout: Option<Duration>) -> std::io::Result<()> {
+        UnixStream::set_write_timeout(self, timeout)
+    }
+    fn shutdown(&self) -> std::io::Result<()> {
+        UnixStream::shutdown(self, Shutdown::Both)
+    }
+}
+///
+/// Unix domain socket [Endpoint]
+#[cfg(unix)]
+#[derive(Debug, Clone)]
+pub struct UdsEndpoint {
+    path: PathBuf,
+}
+//
+//
+#[cfg(unix)]
+impl UdsEndpoint {
+    ///
+    /// Returns [UdsEndpoint] new instance, `path` - the path of the socket file
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+//
+//
+#[cfg(unix)]
+impl Endpoint for UdsEndpoint {
+    fn connect(&self, _timeout: Duration) -> std::io::Result<Arc<dyn Transport>> {
+        UnixStream::connect(&self.path).map(|stream| Arc::new(stream) as Arc<dyn Transport>)
+    }
+    fn name(&self) -> String {
+        self.path.display().to_string()
+    }
+}
@@ -0,0 +1,357 @@
+use std::{
+    collections::HashMap, net::ToSocketAddrs,
+    sync::{atomic::{AtomicUsize, Ordering}, mpsc::{self, Receiver, RecvTimeoutError, Sender, TryRecvError}, Arc},
+    thread, time::{Duration, Instant},
+};
+use crate::{
+    api::{
+        message::{fields::FieldId, fragment::Reassembly, msg_kind::MsgKind},
+        socket::{
+            handshake::Handshake, heartbeat::Heartbeat, reconnect::ReconnectPolicy, socket_err::SocketErr,
+            tcp_socket::TcpSocket, timeouts::Timeouts, transport::{Endpoint, TcpEndpoint},
+        },
+    },
+    client::{api_query::ApiQuery, api_request::{message_schema, QueryBody}},
+    debug::dbg_id::DbgId, error::str_err::StrErr,
+};
+///
+/// Commands of the worker thread
+enum Command {
+    ///
+    /// Send the query, the reply is sent into `reply`
+    /// - `started` - time the query is made by the caller, the `deadline` is counted from
+    Query { query: Vec<u8>, started: Instant, reply: Sender<Result<MsgKind, SocketErr>> },
+    ///
+    /// All handles are dropped
+    Exit,
+}
+///
+/// Cloneable, thread-safe API client
+/// - Owns single [TcpSocket] in the pipelined mode, shared by all the clones
+/// - Queries are written and replies are read by the worker thread,
+///   replies are returned to the caller by message `Id`, so many threads can query at once
+/// - Connection is opened on the first query and reopened on the next one after lost, see [ApiClientBuilder::with_reconnect]
+/// - Configured by the [ApiClientBuilder] before the first clone, see [ApiClient::builder]
+/// ```ignore
+/// let client = ApiClient::new(&dbgid, "127.0.0.1:8080", "auth-token", false);
+/// let handles: Vec<_> = (0..4).map(|_| {
+///     let client = client.clone();
+///     thread::spawn(move || client.fetch(&query, true))
+/// }).collect();
+/// ```
+#[derive(Debug, Clone)]
+pub struct ApiClient {
+    inner: Arc<Inner>,
+}
+///
+/// State shared by the [ApiClient] clones
+#[derive(Debug)]
+struct Inner {
+    dbgid: DbgId,
+    auth_token: String,
+    debug: bool,
+    timeouts: Timeouts,
+    query_id: AtomicUsize,
+    commands: Sender<Command>,
+}
+//
+//
+impl ApiClient {
+    ///
+    /// Returns [ApiClient] connecting to the TCP `address`
+    /// - Panics if `address` can't be resolved
+    pub fn new(dbgid: &DbgId, address: impl ToSocketAddrs + std::fmt::Debug, auth_token: impl Into<String>, debug: bool) -> Self {
+        Self::with_endpoint(dbgid, TcpEndpoint::new(address), auth_token, debug)
+    }
+    ///
+    /// Returns [ApiClient] connecting over specified `endpoint`
+    /// - `endpoint` - TCP, TLS, Unix domain socket or in-memory, see [Endpoint]
+    pub fn with_endpoint(dbgid: &DbgId, endpoint: impl Endpoint + 'static, auth_token: impl Into<String>, debug: bool) -> Self {
+        Self::builder(dbgid, endpoint, auth_token, debug).build()
+    }
+    ///
+    /// Returns [ApiClientBuilder] of the [ApiClient] connecting over specified `endpoint`
+    /// - Use it to configure the client, the built one can be cloned, but not configured
+    pub fn builder(dbgid: &DbgId, endpoint: impl Endpoint + 'static, auth_token: impl Into<String>, debug: bool) -> ApiClientBuilder {
+        let dbgid = DbgId(format!("{}/ApiClient", dbgid));
+        ApiClientBuilder {
+            socket: TcpSocket::from_endpoint(&dbgid, endpoint, message_schema(&dbgid).message(), None),
+            dbgid,
+            auth_token: auth_token.into(),
+            debug,
+            timeouts: Timeouts::default(),
+        }
+    }
+    ///
+    /// Performs an API request, blocks until the reply received
+    /// - Can be called from many threads at once
+    /// - Reply sent in [MsgKind::Fragment]'s is returned reassembled
+    /// - Returns [SocketErr::ConnectTimeout] / [SocketErr::WriteTimeout] if the query isn't sent,
+    ///   [SocketErr::ReadTimeout] if the reply isn't received in the `read` timeout after the query written,
+    ///   [SocketErr::Deadline] if the whole request isn't completed in the `deadline`, see [ApiClientBuilder::with_timeouts]
+    pub fn fetch(&self, query: &ApiQuery, keep_alive: bool) -> Result<Vec<u8>, SocketErr> {
+        let inner = &self.inner;
+        let started = Instant::now();
+        let body = QueryBody {
+            id: inner.query_id.fetch_add(1, Ordering::Relaxed) % usize::MAX + 1,
+            auth_token: &inner.auth_token,
+            keep_alive,
+            debug: inner.debug,
+            query,
+        };
+        let query = serde_json::to_vec(&body)
+            .map_err(|err| StrErr(format!("{}.fetch | Serialize error: {:?}", inner.dbgid, err)))?;
+        let (reply, recv) = mpsc::channel();
+        inner.commands.send(Command::Query { query, started, reply })
+            .map_err(|_| StrErr(format!("{}.fetch | Worker thread exited", inner.dbgid)))?;
+        // The worker expires the reply itself, waiting for the deadline here is needed if the worker is busy with the connecting
+        let reply = match inner.timeouts.deadline {
+            Some(deadline) => match recv.recv_timeout(deadline) {
+                Ok(reply) => reply,
+                Err(RecvTimeoutError::Timeout) => Err(SocketErr::Deadline(deadline)),
+                Err(RecvTimeoutError::Disconnected) => Err(format!("{}.fetch | Reply dropped", inner.dbgid).into()),
+            },
+            None => recv.recv().unwrap_or_else(|_| Err(format!("{}.fetch | Reply dropped", inner.dbgid).into())),
+        };
+        match reply? {
+            MsgKind::Bytes(bytes) => Ok(bytes),
+            MsgKind::Json(bytes) => Ok(bytes),
+            MsgKind::Any(bytes) => Ok(bytes),
+            msg => {
+                let err = format!("{}.fetch | Message kind error, expected Bytes, Json or Any, but found: {:?}", inner.dbgid, msg);
+                log::warn!("{}", err);
+                Err(err.into())
+            }
+        }
+    }
+}
+///
+/// Configures the [ApiClient] before it's shared
+/// - Consumed by [ApiClientBuilder::build], so can't change the client already cloned
+pub struct ApiClientBuilder {
+    dbgid: DbgId,
+    socket: TcpSocket,
+    auth_token: String,
+    debug: bool,
+    timeouts: Timeouts,
+}
+//
+//
+impl ApiClientBuilder {
+    ///
+    /// Returns [ApiClientBuilder] with the same connect, write & reply `timeout` (default 10 sec)
+    pub fn with_timeout(self, timeout: Duration) -> Self {
+        let timeouts = Timeouts { connect: timeout, read: timeout, write: timeout, ..self.timeouts };
+        self.with_timeouts(timeouts)
+    }
+    ///
+    /// Returns [ApiClientBuilder] with specified [Timeouts] (default [Timeouts::default])
+    /// - `connect` - limits opening of the connection, including all the reconnect attempts
+    /// - `write` - limits writing of the single query
+    /// - `read` - limits waiting for the reply, counted from the query written
+    /// - `deadline` - limits the whole request, including the time the query waits for other ones to be sent
+    pub fn with_timeouts(self, timeouts: Timeouts) -> Self {
+        Self { timeouts, ..self }
+    }
+    ///
+    /// Returns [ApiClientBuilder] with specified delays between the failed connection attempts
+    /// - By default [ReconnectPolicy::default]
+    pub fn with_reconnect(self, reconnect: ReconnectPolicy) -> Self {
+        Self { socket: self.socket.with_reconnect(reconnect), ..self }
+    }
+    ///
+    /// Returns [ApiClientBuilder] sending the `handshake` on each new connection, see [TcpSocket::with_handshake]
+    pub fn with_handshake(self, handshake: Handshake) -> Self {
+        Self { socket: self.socket.with_handshake(handshake), ..self }
+    }
+    ///
+    /// Returns [ApiClientBuilder] with enabled keepalive of the idle connection, see [TcpSocket::with_heartbeat]
+    pub fn with_heartbeat(self, heartbeat: Heartbeat) -> Self {
+        Self { socket: self.socket.with_heartbeat(heartbeat), ..self }
+    }
+    ///
+    /// Returns [ApiClient] starting it's worker thread
+    pub fn build(self) -> ApiClient {
+        let (commands, recv) = mpsc::channel();
+        let socket = self.socket
+            .with_timeouts(Timeouts { read: Worker::POLL, deadline: None, ..self.timeouts })
+            .with_pipelining();
+        Worker {
+            dbgid: self.dbgid.clone(),
+            socket,
+            timeouts: self.timeouts,
+            waiting: HashMap::new(),
+            reassembly: Reassembly::new(&self.dbgid),
+        }.run(recv);
+        ApiClient {
+            inner: Arc::new(Inner {
+                dbgid: self.dbgid,
+                auth_token: self.auth_token,
+                debug: self.debug,
+                timeouts: self.timeouts,
+                query_id: AtomicUsize::new(0),
+                commands,
+            }),
+        }
+    }
+}
+//
+//
+impl std::fmt::Debug for ApiClientBuilder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ApiClientBuilder")
+            .field("dbgid", &self.dbgid)
+            .field("socket", &self.socket)
+            .field("timeouts", &self.timeouts)
+            .finish()
+    }
+}
+//
+//
+impl Drop for Inner {
+    fn drop(&mut self) {
+        if self.commands.send(Command::Exit).is_err() {
+            log::debug!("{}.drop | Worker thread already exited", self.dbgid);
+        }
+    }
+}
+///
+/// Query written, waiting for the reply
+struct Waiting {
+    reply: Sender<Result<MsgKind, SocketErr>>,
+    started: Instant,
+    written: Instant,
+}
+///
+/// Writes the queries and reads the replies over the [TcpSocket]
+struct Worker {
+    dbgid: DbgId,
+    socket: TcpSocket,
+    timeouts: Timeouts,
+    waiting: HashMap<u32, Waiting>,
+    reassembly: Reassembly,
+}
+//
+//
+impl Worker {
+    ///
+    /// Timeout of the single read of the socket, the queries made meanwhile are sent between the reads
+    const POLL: Duration = Duration::from_millis(10);
+    ///
+    /// Starts the worker thread
+    /// - Blocks for the query while not connected, polls the socket otherwise,
+    ///   so the ping is answered and the closed connection is noticed without the queries
+    fn run(mut self, recv: Receiver<Command>) {
+        let dbgid = self.dbgid.clone();
+        let spawned = thread::Builder::new().name(format!("{}.Worker", dbgid)).spawn(move || {
+            'main: loop {
+                let mut commands = vec![];
+                if self.socket.is_connected() {
+                    loop {
+                        match recv.try_recv() {
+                            Ok(command) => commands.push(command),
+                            Err(TryRecvError::Empty) => break,
+                            Err(TryRecvError::Disconnected) => break 'main,
+                        }
+                    }
+                } else {
+                    match recv.recv() {
+                        Ok(command) => commands.push(command),
+                        Err(_) => break 'main,
+                    }
+                }
+                for command in commands {
+                    match command {
+                        Command::Query { query, started, reply } => self.send(&query, started, reply),
+                        Command::Exit => break 'main,
+                    }
+                }
+                if self.socket.is_connected() {
+                    match self.socket.poll() {
+                        Ok(Some((id, MsgKind::Fragment(fragment)))) => match self.reassembly.push(&id, fragment) {
+                            Ok(Some(value)) => self.reply(&id, Ok(value)),
+                            Ok(None) => {}
+                            Err(err) => self.reply(&id, Err(err.into())),
+                        }
+                        Ok(Some((id, value))) => self.reply(&id, Ok(value)),
+                        Ok(None) => {}
+                        Err(err) => match self.socket.is_connected() {
+                            true => log::warn!("{}.run | Read error: {}", self.dbgid, err),
+                            false => self.lost(&err),
+                        }
+                    }
+                }
+                self.expire();
+            }
+            if let Err(err) = self.socket.close() {
+                log::debug!("{}.run | Close error: {:?}", self.dbgid, err);
+            }
+            log::debug!("{}.run | Exit", self.dbgid);
+        });
+        if let Err(err) = spawned {
+            log::error!("{}.run | Start worker thread error: {:?}", dbgid, err);
+        }
+    }
+    ///
+    /// Sends the `query`, the reply will be returned into `reply`
+    /// - The `Id` is assigned by the [TcpSocket] within the range of the `Id` field
+    fn send(&mut self, query: &[u8], started: Instant, reply: Sender<Result<MsgKind, SocketErr>>) {
+        if let Some(deadline) = self.timeouts.deadline {
+            if started.elapsed() >= deadline {
+                let _ = reply.send(Err(SocketErr::Deadline(deadline)));
+                return;
+            }
+        }
+        match self.socket.send(query, None) {
+            Ok(id) => {
+                self.waiting.insert(id.0, Waiting { reply, started, written: Instant::now() });
+            }
+            Err(err) => {
+                log::warn!("{}.send | {}", self.dbgid, err);
+                if !self.socket.is_connected() {
+                    self.lost(&err);
+                }
+                let _ = reply.send(Err(err));
+            }
+        }
+    }
+    ///
+    /// Returns the received `value` to the caller waiting for the reply `id`
+    /// - Reply with unknown `id` is logged and dropped
+    fn reply(&mut self, id: &FieldId, value: Result<MsgKind, SocketErr>) {
+        match self.waiting.remove(&id.0) {
+            Some(waiting) => if waiting.reply.send(value).is_err() {
+                log::debug!("{}.reply | Caller of {} gone", self.dbgid, id.0);
+            }
+            None => log::warn!("{}.reply | Reply with unknown id {} received", self.dbgid, id.0),
+        }
+    }
+    ///
+    /// Returns the error to the callers waiting for the reply over the lost connection
+    /// - Incomplete fragmented replies are discarded
+    fn lost(&mut self, err: &SocketErr) {
+        self.reassembly = Reassembly::new(&self.dbgid);
+        for (id, waiting) in self.waiting.drain() {
+            let _ = waiting.reply.send(Err(format!("{}.lost | Reply {} lost: {}", self.dbgid, id, err).into()));
+        }
+    }
+    ///
+    /// Returns the timeout error to the callers waiting for the reply longer than the `read` timeout or the `deadline`
+    /// - The late reply is dropped as the reply with unknown `Id`
+    fn expire(&mut self) {
+        let (read, deadline) = (self.timeouts.read, self.timeouts.deadline);
+        let expired: Vec<(u32, SocketErr)> = self.waiting.iter()
+            .filter_map(|(id, waiting)| match deadline {
+                Some(deadline) if waiting.started.elapsed() >= deadline => Some((*id, SocketErr::Deadline(deadline))),
+                _ if waiting.written.elapsed() >= read => Some((*id, SocketErr::ReadTimeout(read))),
+                _ => None,
+            })
+            .collect();
+        for (id, err) in expired {
+            log::warn!("{}.expire | Reply {}: {}", self.dbgid, id, err);
+            self.socket.cancel(&FieldId(id));
+            self.reassembly.discard(&FieldId(id));
+            self.reply(&FieldId(id), Err(err));
+        }
+    }
+}
@@ -3,11 +3,11 @@ use std::{net::ToSocketAddrs, time::Duration};
 use crate::{
     api::{
         message::{
-            fields::{FieldData, FieldId, FieldKind, FieldSize, FieldSyn}, message::MessageField, message_kind::MessageKind, msg_kind, parse_data::ParseData, parse_id::ParseId, parse_kind::ParseKind, parse_size::ParseSize, parse_syn::ParseSyn
+            fields::{FieldData, FieldId, FieldKind, FieldSize, FieldSyn}, fragment::Reassembly, message::MessageField, message_kind::MessageKind, message_schema::MessageSchema, msg_kind
         },
-        socket::tcp_socket::{TcpMessage, TcpSocket},
+        socket::{socket_err::SocketErr, tcp_socket::TcpSocket, timeouts::Timeouts, transport::{Endpoint, TcpEndpoint}},
     },
-    client::api_query::ApiQuery, debug::dbg_id::DbgId, error::str_err::StrErr,
+    client::api_query::ApiQuery, debug::dbg_id::DbgId,
 };
 ///
 /// - Holding single input queue
@@ -34,7 +34,10 @@ pub struct ApiRequest {
     query: ApiQuery,
     keep_alive: bool,
     debug: bool,
-    timeout: Duration,
+    timeouts: Timeouts,
+    pipelined: bool,
+    json: bool,
+    reassembly: Reassembly,
     socket: TcpSocket,
 }
 //
@@ -44,97 +47,164 @@ impl ApiRequest {
     /// Creates new instance of [ApiRequest]
     /// - [parent] - the ID if the parent entity
     pub fn new(dbgid: &DbgId, address: impl ToSocketAddrs + std::fmt::Debug, auth_token: impl Into<String>, query: ApiQuery, keep_alive: bool, debug: bool) -> Self {
-        let dbgid = DbgId(format!("{}/ApiRequest", dbgid));
         let address = match address.to_socket_addrs() {
             Ok(mut addr_iter) => match addr_iter.next() {
                 Some(addr) => addr,
-                None => panic!("TcpClientConnect({}).connect | Empty address: {:?}", dbgid, address),
+                None => panic!("TcpClientConnect({}/ApiRequest).connect | Empty address: {:?}", dbgid, address),
             },
-            Err(err) => panic!("TcpClientConnect({}).connect | Address error: {:#?}", dbgid, err),
+            Err(err) => panic!("TcpClientConnect({}/ApiRequest).connect | Address error: {:#?}", dbgid, err),
         };
-        let message = TcpMessage::new(
-            &dbgid,
-            vec![
-                MessageField::Syn(FieldSyn::default()),
-                MessageField::Id(FieldId(4)),
-                MessageField::Kind(FieldKind(MessageKind::Bytes)),
-                MessageField::Size(FieldSize(4)),
-                MessageField::Data(FieldData(vec![]))
-            ],
-            ParseData::new(
-                &dbgid,
-                ParseSize::new(
-                    &dbgid,
-                    FieldSize(4),
-                    ParseKind::new(
-                        &dbgid,
-                        FieldKind(MessageKind::Bytes),
-                        ParseId::new(
-                            &dbgid,
-                            FieldId(4),
-                            ParseSyn::new(
-                                &dbgid,
-                                FieldSyn::default(),
-                            ),
-                        ),
-                    ),
-                ),
-            ),
-        );
+        Self::with_endpoint(dbgid, TcpEndpoint::new(address), auth_token, query, keep_alive, debug)
+    }
+    ///
+    /// Creates new instance of [ApiRequest] connecting over specified `endpoint`
+    /// - [parent] - the ID if the parent entity
+    /// - `endpoint` - TCP, Unix domain socket or in-memory, see [Endpoint]
+    pub fn with_endpoint(dbgid: &DbgId, endpoint: impl Endpoint + 'static, auth_token: impl Into<String>, query: ApiQuery, keep_alive: bool, debug: bool) -> Self {
+        let dbgid = DbgId(format!("{}/ApiRequest", dbgid));
+        let message = message_schema(&dbgid).message();
         Self {
-            socket: TcpSocket::new(&dbgid, address, message, None),
+            socket: TcpSocket::from_endpoint(&dbgid, endpoint, message, None),
+            reassembly: Reassembly::new(&dbgid),
             dbgid,
             query_id: Id::new(),
             auth_token: auth_token.into(),
             query,
             keep_alive,
             debug,
-            timeout: Duration::from_secs(10),
+            timeouts: Timeouts::default(),
+            pipelined: false,
+            json: false,
         }
     }
     ///
-    /// Returns [ApiRequest] with specified socket read/write timeout (default 10 sec)
-    pub fn with_timeout(mut self, timeout: Duration) -> Self {
-        self.timeout = timeout;
-        self
+    /// Returns [ApiRequest] with the same socket connect / read / write `timeout` (default 10 sec)
+    /// - The deadline of the request is kept, see [ApiRequest::with_timeouts]
+    pub fn with_timeout(self, timeout: Duration) -> Self {
+        let timeouts = Timeouts { connect: timeout, read: timeout, write: timeout, ..self.timeouts };
+        self.with_timeouts(timeouts)
+    }
+    ///
+    /// Returns [ApiRequest] with specified socket [Timeouts] (default [Timeouts::default])
+    /// - The `deadline` limits each request as a whole: connect, send & read the reply
+    /// - Each expired timeout returns it's own [SocketErr]
+    pub fn with_timeouts(self, timeouts: Timeouts) -> Self {
+        Self { socket: self.socket.with_timeouts(timeouts), timeouts, ..self }
+    }
+    ///
+    /// Returns [ApiRequest] in the pipelined mode
+    /// - Many requests can be sent by [ApiRequest::send] before the replies are received
+    /// - Replies are matched to the requests by message `Id`, even if arrived out of order
+    pub fn with_pipelining(self) -> Self {
+        Self { socket: self.socket.with_pipelining(), pipelined: true, ..self }
+    }
+    ///
+    /// Returns [ApiRequest] sending the queries with the `Kind` [MessageKind::Json]
+    /// - By default the queries are sent with the `Kind` Bytes, see [message_schema]
+    /// - The server must accept the Json kind
+    pub fn with_json_kind(self) -> Self {
+        Self { json: true, ..self }
     }
     ///
     /// Performs an API request with the parameters specified in the constructor
-    pub fn fetch(&mut self, keep_alive: bool) -> Result<Vec<u8>, StrErr> {
+    pub fn fetch(&mut self, keep_alive: bool) -> Result<Vec<u8>, SocketErr> {
         self.fetch_with(&self.query.clone(), keep_alive)
     }
     ///
     /// Performs an API request with passed query and parameters specified in the constructor
-    pub fn fetch_with(&mut self, query: &ApiQuery, keep_alive: bool) -> Result<Vec<u8>, StrErr>{
+    pub fn fetch_with(&mut self, query: &ApiQuery, keep_alive: bool) -> Result<Vec<u8>, SocketErr>{
+        self.socket.start_request();
+        let result = match self.send(query, keep_alive) {
+            Ok(id) => match self.pipelined {
+                true => self.reply(&id),
+                false => self.read(),
+            }
+            Err(err) => Err(err),
+        };
+        self.socket.finish_request();
+        result
+    }
+    ///
+    /// Sends an API request with passed query without waiting for the reply
+    /// - Returns `Id` of the sent message, the reply can be received by [ApiRequest::reply]
+    /// - Many requests can be in flight if pipelining enabled, see [ApiRequest::with_pipelining]
+    pub fn send(&mut self, query: &ApiQuery, keep_alive: bool) -> Result<FieldId, SocketErr> {
         self.query_id.add();
         self.query = query.clone();
         self.keep_alive = keep_alive;
         match serde_json::to_vec(&self) {
             Ok(query) => {
-                log::trace!("{}.fetch | query: {:#?}", self.dbgid, query);
-                match self.socket.send(&query, None) {
-                    Ok(_id) => {
-                        match self.socket.read() {
-                            Ok((_id, msg)) => match msg {
-                                msg_kind::MsgKind::Bytes(bytes) =>  Ok(bytes),
-                                _ => {
-                                    let err = format!("{}.fetch | Message kind error, expected Bytes, but found: {:?}", self.dbgid, msg);
-                                    log::warn!("{}", err);
-                                    Err(err.into())
-                                }
-                            }
-                            Err(err) => Err(err),
-                        }
-                    }
-                    Err(err) => {
-                        let err = format!("{}.fetch | Send error: {:?}", self.dbgid, err);
-                        log::warn!("{}", err);
-                        Err(err.into())
-                    }
-                }
+                log::trace!("{}.send | query: {:#?}", self.dbgid, query);
+                let result = match self.json {
+                    true => self.socket.send_kind(&msg_kind::MsgKind::Json(query), None),
+                    false => self.socket.send(&query, None),
+                };
+                result.map_err(|err| {
+                    log::warn!("{}.send | Send error: {}", self.dbgid, err);
+                    err
+                })
             }
             Err(err) => {
-                let err = format!("{}.fetch | Serialize error: {:?}", self.dbgid, err);
+                let err = format!("{}.send | Serialize error: {:?}", self.dbgid, err);
+                log::warn!("{}", err);
+                Err(err.into())
+            }
+        }
+    }
+    ///
+    /// Returns the reply to the request sent with `id`, see [ApiRequest::send]
+    /// - Replies may be received in any order
+    /// - Reply with `Id` of no pending request is an error
+    /// - Reply sent in [MsgKind::Fragment](msg_kind::MsgKind::Fragment)'s is returned reassembled
+    pub fn reply(&mut self, id: &FieldId) -> Result<Vec<u8>, SocketErr> {
+        loop {
+            let msg = match self.pipelined {
+                true => self.socket.reply(id)?,
+                false => match self.socket.read()? {
+                    (reply_id, msg) if reply_id == *id => msg,
+                    (reply_id, _) => return Err(format!("{}.reply | Expected reply {}, but received {}", self.dbgid, id.0, reply_id.0).into()),
+                }
+            };
+            if let Some(bytes) = self.assemble(id, msg)? {
+                return Ok(bytes);
+            }
+        }
+    }
+    ///
+    /// Reads the reply to the single request, `Id` of the reply isn't checked
+    fn read(&mut self) -> Result<Vec<u8>, SocketErr> {
+        loop {
+            let (id, msg) = self.socket.read()?;
+            if let Some(bytes) = self.assemble(&id, msg)? {
+                return Ok(bytes);
+            }
+        }
+    }
+    ///
+    /// Returns the payload of the reply `id`
+    /// - [MsgKind::Fragment](msg_kind::MsgKind::Fragment)'s are collected, None returned until the last one received
+    fn assemble(&mut self, id: &FieldId, msg: msg_kind::MsgKind) -> Result<Option<Vec<u8>>, SocketErr> {
+        match msg {
+            msg_kind::MsgKind::Fragment(fragment) => match self.reassembly.push(id, fragment) {
+                Ok(Some(msg)) => self.bytes(msg).map(Some),
+                Ok(None) => Ok(None),
+                Err(err) => {
+                    log::warn!("{}.reply | {}", self.dbgid, err);
+                    Err(err.into())
+                }
+            }
+            msg => self.bytes(msg).map(Some),
+        }
+    }
+    ///
+    /// Returns the payload of the reply
+    fn bytes(&self, msg: msg_kind::MsgKind) -> Result<Vec<u8>, SocketErr> {
+        match msg {
+            msg_kind::MsgKind::Bytes(bytes) =>  Ok(bytes),
+            msg_kind::MsgKind::Json(bytes) =>  Ok(bytes),
+            msg_kind::MsgKind::Any(bytes) =>  Ok(bytes),
+            _ => {
+                let err = format!("{}.fetch | Message kind error, expected Bytes, Json or Any, but found: {:?}", self.dbgid, msg);
                 log::warn!("{}", err);
                 Err(err.into())
             }
@@ -144,11 +214,58 @@ impl ApiRequest {
 //
 //
 impl Serialize for ApiRequest {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer {
+        QueryBody {
+            id: self.query_id.value,
+            auth_token: &self.auth_token,
+            keep_alive: self.keep_alive,
+            debug: self.debug,
+            query: &self.query,
+        }.serialize(serializer)
+    }
+}
+///
+/// Returns the layout of the API messages
+/// - Queries are sent with the `Kind` Bytes, even though the body is JSON,
+///   the wire format is kept as before the [MessageKind::Json] was added, so the servers expecting Bytes keep working,
+///   the Json kind is opt-in, see [ApiRequest::with_json_kind]
+/// - Replies are accepted of the Bytes, Json & Any kinds
+/// - Panics if the layout is invalid
+pub(crate) fn message_schema(dbgid: &DbgId) -> MessageSchema {
+    let schema = MessageSchema::new(
+        dbgid,
+        vec![
+            MessageField::Syn(FieldSyn::default()),
+            MessageField::Id(FieldId(4)),
+            MessageField::Kind(FieldKind(MessageKind::Bytes)),
+            MessageField::Size(FieldSize(4)),
+            MessageField::Data(FieldData(vec![]))
+        ],
+    );
+    match schema {
+        Ok(schema) => schema,
+        Err(err) => panic!("{}.new | Message schema error: {}", dbgid, err),
+    }
+}
+///
+/// JSON body of the API request
+pub(crate) struct QueryBody<'a> {
+    pub id: usize,
+    pub auth_token: &'a str,
+    pub keep_alive: bool,
+    pub debug: bool,
+    pub query: &'a ApiQuery,
+}
+//
+//
+impl Serialize for QueryBody<'_> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer {
         let mut state = serializer.serialize_struct("ApiRequest", 2)?;
-        state.serialize_field("id", &self.query_id)?;
+        state.serialize_field("id", &self.id.to_string())?;
         state.serialize_field("authToken", &self.auth_token)?;
         state.serialize_field("keepAlive", &self.keep_alive)?;
         state.serialize_field("debug", &self.debug)?;
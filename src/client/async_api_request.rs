@@ -0,0 +1,191 @@
+use std::{net::{SocketAddr, ToSocketAddrs}, time::Duration};
+use futures_util::{SinkExt, StreamExt};
+use tokio::{net::TcpStream, time::timeout};
+use tokio_util::codec::Framed;
+use crate::{
+    api::{
+        message::{fields::FieldId, msg_kind::MsgKind},
+        socket::{heartbeat::Heartbeat, message_codec::MessageCodec, socket_err::SocketErr, timeouts::Timeouts},
+    },
+    client::{api_query::ApiQuery, api_request::{message_schema, QueryBody}},
+    debug::dbg_id::DbgId, error::str_err::StrErr,
+};
+///
+/// Async counterpart of the [ApiRequest](super::api_request::ApiRequest)
+/// - Same messages, same [Timeouts] & errors, connection is reopened on the next request after lost
+/// ```ignore
+/// let mut request = AsyncApiRequest::new(&dbgid, "127.0.0.1:8080", auth_token, query, keep_alive, debug);
+/// let reply = request.fetch(true).await?;
+/// ```
+#[derive(Debug)]
+pub struct AsyncApiRequest {
+    dbgid: DbgId,
+    address: SocketAddr,
+    query_id: usize,
+    msg_id: u32,
+    auth_token: String,
+    query: ApiQuery,
+    keep_alive: bool,
+    debug: bool,
+    timeouts: Timeouts,
+    connection: Option<Framed<TcpStream, MessageCodec>>,
+}
+//
+//
+impl AsyncApiRequest {
+    ///
+    /// Creates new instance of [AsyncApiRequest]
+    /// - [parent] - the ID if the parent entity
+    /// - Panics if `address` can't be resolved
+    pub fn new(dbgid: &DbgId, address: impl ToSocketAddrs + std::fmt::Debug, auth_token: impl Into<String>, query: ApiQuery, keep_alive: bool, debug: bool) -> Self {
+        let dbgid = DbgId(format!("{}/AsyncApiRequest", dbgid));
+        let address = match address.to_socket_addrs() {
+            Ok(mut addr_iter) => match addr_iter.next() {
+                Some(addr) => addr,
+                None => panic!("{}.new | Empty address: {:?}", dbgid, address),
+            },
+            Err(err) => panic!("{}.new | Address error: {:#?}", dbgid, err),
+        };
+        Self {
+            dbgid,
+            address,
+            query_id: 0,
+            msg_id: 0,
+            auth_token: auth_token.into(),
+            query,
+            keep_alive,
+            debug,
+            timeouts: Timeouts::default(),
+            connection: None,
+        }
+    }
+    ///
+    /// Returns [AsyncApiRequest] with the same connect / write / read `timeout` (default 10 sec)
+    /// - The deadline of the request is kept, see [AsyncApiRequest::with_timeouts]
+    pub fn with_timeout(self, timeout: Duration) -> Self {
+        let timeouts = Timeouts { connect: timeout, read: timeout, write: timeout, ..self.timeouts };
+        self.with_timeouts(timeouts)
+    }
+    ///
+    /// Returns [AsyncApiRequest] with specified [Timeouts] (default [Timeouts::default])
+    pub fn with_timeouts(mut self, timeouts: Timeouts) -> Self {
+        self.timeouts = timeouts;
+        self
+    }
+    ///
+    /// Performs an API request with the parameters specified in the constructor
+    pub async fn fetch(&mut self, keep_alive: bool) -> Result<Vec<u8>, SocketErr> {
+        let query = self.query.clone();
+        self.fetch_with(&query, keep_alive).await
+    }
+    ///
+    /// Performs an API request with passed query and parameters specified in the constructor
+    pub async fn fetch_with(&mut self, query: &ApiQuery, keep_alive: bool) -> Result<Vec<u8>, SocketErr> {
+        self.query_id = (self.query_id % usize::MAX) + 1;
+        self.query = query.clone();
+        self.keep_alive = keep_alive;
+        let body = QueryBody {
+            id: self.query_id,
+            auth_token: &self.auth_token,
+            keep_alive: self.keep_alive,
+            debug: self.debug,
+            query: &self.query,
+        };
+        let query = match serde_json::to_vec(&body) {
+            Ok(query) => query,
+            Err(err) => {
+                let err = format!("{}.fetch | Serialize error: {:?}", self.dbgid, err);
+                log::warn!("{}", err);
+                return Err(err.into());
+            }
+        };
+        log::trace!("{}.fetch | query: {:#?}", self.dbgid, query);
+        self.msg_id = (self.msg_id % (Heartbeat::PONG.0 - 1)) + 1;
+        let id = FieldId(self.msg_id);
+        let deadline = self.timeouts.deadline;
+        let request = async {
+            match self.send(id, query).await {
+                Ok(_) => self.read().await,
+                Err(err) => Err(err),
+            }
+        };
+        let result = match deadline {
+            Some(deadline) => match timeout(deadline, request).await {
+                Ok(result) => result,
+                Err(_) => Err(SocketErr::Deadline(deadline)),
+            }
+            None => request.await,
+        };
+        if let Err(err) = &result {
+            log::warn!("{}", err);
+            self.connection = None;
+        }
+        match result? {
+            MsgKind::Bytes(bytes) => Ok(bytes),
+            MsgKind::Json(bytes) => Ok(bytes),
+            MsgKind::Any(bytes) => Ok(bytes),
+            msg => {
+                let err = format!("{}.fetch | Message kind error, expected Bytes, Json or Any, but found: {:?}", self.dbgid, msg);
+                log::warn!("{}", err);
+                Err(err.into())
+            }
+        }
+    }
+    ///
+    /// Returns the connection, opening the new one if required
+    async fn connect(&mut self) -> Result<&mut Framed<TcpStream, MessageCodec>, SocketErr> {
+        if self.connection.is_none() {
+            let stream = match timeout(self.timeouts.connect, TcpStream::connect(self.address)).await {
+                Ok(Ok(stream)) => stream,
+                Ok(Err(err)) => return Err(format!("{}.connect | Connection error: {:?}", self.dbgid, err).into()),
+                Err(_) => return Err(SocketErr::ConnectTimeout(self.timeouts.connect)),
+            };
+            log::debug!("{}.connect | connected to: \n\t{:?}", self.dbgid, stream);
+            let message = message_schema(&self.dbgid).message();
+            self.connection = Some(Framed::new(stream, MessageCodec::new(&self.dbgid, message)));
+        }
+        match self.connection.as_mut() {
+            Some(connection) => Ok(connection),
+            None => Err(format!("{}.connect | Not connected", self.dbgid).into()),
+        }
+    }
+    ///
+    /// Sends the `query` with message `id`
+    async fn send(&mut self, id: FieldId, query: Vec<u8>) -> Result<(), SocketErr> {
+        let dbgid = self.dbgid.clone();
+        let duration = self.timeouts.write;
+        let connection = self.connect().await?;
+        match timeout(duration, connection.send((id, MsgKind::Bytes(query)))).await {
+            Ok(Ok(_)) => Ok(()),
+            Ok(Err(err)) => Err(format!("{}.send | write to tcp stream error: {:?}", dbgid, err).into()),
+            Err(_) => Err(SocketErr::WriteTimeout(duration)),
+        }
+    }
+    ///
+    /// Reads the reply, answering and skipping the [Heartbeat] messages
+    async fn read(&mut self) -> Result<MsgKind, SocketErr> {
+        let dbgid = self.dbgid.clone();
+        let duration = self.timeouts.read;
+        let connection = self.connect().await?;
+        let reply = async {
+            loop {
+                match connection.next().await {
+                    Some(Ok((id, value))) if Heartbeat::is_ping(&id, &value.kind()) => {
+                        log::trace!("{}.read | Ping received", dbgid);
+                        if let Err(err) = connection.send((Heartbeat::PONG, MsgKind::Empty)).await {
+                            return Err(StrErr(format!("{}.read | Pong error: {}", dbgid, err)));
+                        }
+                    }
+                    Some(Ok((id, value))) if Heartbeat::is_pong(&id, &value.kind()) => {}
+                    Some(Ok((_, value))) => return Ok(value),
+                    Some(Err(err)) => return Err(StrErr(format!("{}.read | {}", dbgid, err))),
+                    None => return Err(StrErr(format!("{}.read | tcp stream closed", dbgid))),
+                }
+            }
+        };
+        match timeout(duration, reply).await {
+            Ok(result) => result.map_err(SocketErr::from),
+            Err(_) => Err(SocketErr::ReadTimeout(duration)),
+        }
+    }
+}
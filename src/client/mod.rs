@@ -1,5 +1,10 @@
+pub mod api_client;
+
 pub mod api_query;
 
 pub mod api_request;
 
+#[cfg(feature = "tokio")]
+pub mod async_api_request;
+
 // pub mod api_reply;
\ No newline at end of file
@@ -32,6 +32,11 @@ impl From<&str> for StrErr {
         StrErr(value.to_owned())
     }
 }
+impl From<std::io::Error> for StrErr {
+    fn from(value: std::io::Error) -> Self {
+        StrErr(format!("{:?}", value))
+    }
+}
 impl Into<Box<StrErr>> for &str {
     fn into(self) -> Box<StrErr> {
         Box::new(StrErr(self.to_owned()))
@@ -1 +1 @@
-// pub mod tcp_client_connect;
\ No newline at end of file
+pub mod tcp_client_connect;
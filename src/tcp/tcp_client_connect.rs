@@ -1,9 +1,11 @@
 #![allow(non_snake_case)]
 
-use std::{net::{TcpStream, SocketAddr, ToSocketAddrs}, time::Duration, sync::{Arc, Mutex, mpsc::{Sender, Receiver, self}}, thread};
+use std::{net::{TcpStream, SocketAddr, ToSocketAddrs}, time::{Duration, Instant}, sync::{Arc, Mutex, mpsc::{Sender, Receiver, self}}, thread};
 
 use log::{warn, LevelFilter, debug, info};
 
+use crate::api::socket::{reconnect::{ConnectEvent, ReconnectPolicy}, socket_err::SocketErr, timeouts::Timeouts};
+
 
 // #[derive(Debug, PartialEq)]
 // enum ConnectState {
@@ -37,8 +39,10 @@ use log::{warn, LevelFilter, debug, info};
 pub struct TcpClientConnect {
     id: String,
     addr: SocketAddr,
-    stream: Arc<Mutex<Vec<TcpStream>>>,
-    reconnect: Duration,
+    reconnect: ReconnectPolicy,
+    timeouts: Timeouts,
+    events: Option<Sender<ConnectEvent>>,
+    connected: bool,
     exitSend: Sender<bool>,
     exitRecv: Arc<Mutex<Receiver<bool>>>,
 }
@@ -47,6 +51,7 @@ pub struct TcpClientConnect {
 impl TcpClientConnect {
     ///
     /// Creates a new instance of TcpClientConnect
+    /// - `reconnect` - fixed delay between the failed attempts, see [TcpClientConnect::with_reconnect]
     pub fn new(parent: impl Into<String>, addr: impl ToSocketAddrs + std::fmt::Debug, reconnect: Duration) -> TcpClientConnect {
         let addr = match addr.to_socket_addrs() {
             Ok(mut addrIter) => {
@@ -61,54 +66,125 @@ impl TcpClientConnect {
         Self {
             id: format!("{}/TcpClientConnect", parent.into()),
             addr,
-            stream: Arc::new(Mutex::new(Vec::new())),
-            reconnect,
+            reconnect: ReconnectPolicy::fixed(reconnect),
+            timeouts: Timeouts::default(),
+            events: None,
+            connected: false,
             exitSend: send,
             exitRecv: Arc::new(Mutex::new(recv)),
         }
     }
     ///
+    /// Returns TcpClientConnect with specified delays between the failed attempts
+    /// - Connecting stops after the maximum number of attempts of the `reconnect` policy
+    pub fn with_reconnect(mut self, reconnect: ReconnectPolicy) -> Self {
+        self.reconnect = reconnect;
+        self
+    }
+    ///
+    /// Returns TcpClientConnect with specified [Timeouts] (default [Timeouts::default])
+    /// - `connect` - limits all the attempts of the single [TcpClientConnect::connect]
+    /// - `read` & `write` - are set to the connected stream
+    pub fn with_timeouts(mut self, timeouts: Timeouts) -> Self {
+        self.timeouts = timeouts;
+        self
+    }
+    ///
+    /// Returns TcpClientConnect sending the connection state changes into the `events`
+    pub fn with_events(mut self, events: Sender<ConnectEvent>) -> Self {
+        self.events = Some(events);
+        self
+    }
+    ///
     /// Opens a TCP connection to a remote host until succeed.
-    pub fn connect(&mut self) -> Option<TcpStream> {
+    /// - Failed attempts are repeated with the delay of the [ReconnectPolicy] until the `connect` timeout expires
+    /// - Returns [SocketErr::ConnectTimeout] if not connected in the `connect` timeout of the [Timeouts]
+    /// - Returns error if exit requested or the maximum number of attempts is reached
+    /// - The connection returned by the previous call is considered lost, if not reported by [TcpClientConnect::lost]
+    pub fn connect(&mut self) -> Result<TcpStream, SocketErr> {
+        self.lost("Reconnecting");
         info!("TcpClientConnect({}).connect | connecting...", self.id);
         let id = self.id.clone();
-        let addr = self.addr.clone();
+        let addr = self.addr;
         info!("TcpClientConnect({}).inner_connect | connecting to: {:?}...", id, addr);
-        let cycle = self.reconnect;
-        let selfStream = self.stream.clone();
+        let reconnect = self.reconnect.clone();
+        let timeouts = self.timeouts;
+        let events = self.events.clone();
+        let event = move |event: ConnectEvent| if let Some(events) = &events {
+            if let Err(err) = events.send(event) {
+                debug!("TcpClientConnect.event | Subscriber gone: {:?}", err);
+            }
+        };
         let exit = self.exitRecv.clone();
         let handle = thread::spawn(move || {
             let exit = exit.lock().unwrap();
-            loop {
-                cycle.start();
-                match TcpStream::connect(addr) {
+            let time = Instant::now();
+            let mut attempt = 0;
+            let result = loop {
+                let remaining = timeouts.connect.saturating_sub(time.elapsed());
+                if remaining.is_zero() {
+                    let err = SocketErr::ConnectTimeout(timeouts.connect);
+                    warn!("TcpClientConnect({}).inner_connect | {}", id, err);
+                    break Err(err);
+                }
+                attempt += 1;
+                event(ConnectEvent::Connecting { endpoint: addr.to_string(), attempt });
+                match TcpStream::connect_timeout(&addr, remaining) {
                     Ok(stream) => {
-                        selfStream.lock().unwrap().push(stream);
-                        info!("TcpClientConnect({}).inner_connect | connected to: \n\t{:?}", id, selfStream.lock().unwrap().first().unwrap());
-                        break;
+                        if let Err(err) = stream.set_read_timeout(Some(timeouts.read)) {
+                            warn!("TcpClientConnect({}).inner_connect | set_read_timeout error: \n\t{:?}", id, err);
+                        }
+                        if let Err(err) = stream.set_write_timeout(Some(timeouts.write)) {
+                            warn!("TcpClientConnect({}).inner_connect | set_write_timeout error: \n\t{:?}", id, err);
+                        }
+                        info!("TcpClientConnect({}).inner_connect | connected to: \n\t{:?}", id, stream);
+                        event(ConnectEvent::Connected(addr.to_string()));
+                        break Ok(stream);
                     },
+                    Err(err) if err.kind() == std::io::ErrorKind::TimedOut => {
+                        warn!("TcpClientConnect({}).inner_connect | Not connected in {:?}", id, remaining);
+                    }
                     Err(err) => {
                         if log::max_level() == LevelFilter::Debug {
                             warn!("TcpClientConnect({}).inner_connect | connection error: \n\t{:?}", id, err);
                         }
                     }
                 };
-                match exit.try_recv() {
-                    Ok(exit) => {
-                        debug!("TcpClientConnect({}).inner_connect | exit: {}", id, exit);
-                        if exit {
-                            break;
-                        }
-                    },
-                    Err(_) => {},
+                if let Ok(exit) = exit.try_recv() {
+                    debug!("TcpClientConnect({}).inner_connect | exit: {}", id, exit);
+                    if exit {
+                        break Err(format!("TcpClientConnect({}).inner_connect | Exit requested", id).into());
+                    }
                 }
-                cycle.wait();
-            }
+                match reconnect.delay(attempt) {
+                    Some(delay) => thread::sleep(delay.min(timeouts.connect.saturating_sub(time.elapsed()))),
+                    None => {
+                        let err = format!("TcpClientConnect({}).inner_connect | Not connected in {} attempts", id, attempt);
+                        warn!("{}", err);
+                        break Err(err.into());
+                    }
+                }
+            };
             debug!("TcpClientConnect({}).inner_connect | exit", id);
+            result
         });
-        handle.join().unwrap();
-        let mut tcpStream = self.stream.lock().unwrap();
-        tcpStream.pop()
+        let result = handle.join()
+            .unwrap_or_else(|err| Err(format!("TcpClientConnect({}).connect | Connecting thread error: {:?}", self.id, err).into()));
+        self.connected = result.is_ok();
+        result
+    }
+    ///
+    /// Reports the connection returned by [TcpClientConnect::connect] is failed or closed
+    /// - Sends [ConnectEvent::Lost] with the `reason` into the `events`, once per connection
+    pub fn lost(&mut self, reason: impl Into<String>) {
+        if self.connected {
+            self.connected = false;
+            if let Some(events) = &self.events {
+                if let Err(err) = events.send(ConnectEvent::Lost { endpoint: self.addr.to_string(), reason: reason.into() }) {
+                    debug!("TcpClientConnect({}).lost | Subscriber gone: {:?}", self.id, err);
+                }
+            }
+        }
     }
     ///
     /// Opens a TCP connection to a remote host with a timeout.
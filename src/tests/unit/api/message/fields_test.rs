@@ -0,0 +1,87 @@
+#[cfg(test)]
+
+mod fields {
+    use std::{sync::Once, time::Duration};
+    use testing::stuff::max_test_duration::TestDuration;
+    use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
+    use crate::{api::message::fields::{FieldCodec, FieldOrder, FieldWidth}, debug::dbg_id::DbgId};
+    ///
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+            // implement your initialisation code to be called only once for current test file
+        })
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Testing [FieldCodec.encode] & [FieldCodec.decode]
+    #[test]
+    fn codec() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        log::debug!("");
+        let dbgid = DbgId("test".to_owned());
+        log::debug!("\n{}", dbgid);
+        let test_duration = TestDuration::new(&dbgid, Duration::from_secs(1));
+        test_duration.run().unwrap();
+        let test_data = [
+            (00, FieldCodec::new(FieldWidth::U8, FieldOrder::Be), 0xab, vec![0xab]),
+            (01, FieldCodec::new(FieldWidth::U16, FieldOrder::Be), 0x1234, vec![0x12, 0x34]),
+            (02, FieldCodec::new(FieldWidth::U16, FieldOrder::Le), 0x1234, vec![0x34, 0x12]),
+            (03, FieldCodec::new(FieldWidth::U32, FieldOrder::Be), 0x12345678, vec![0x12, 0x34, 0x56, 0x78]),
+            (04, FieldCodec::new(FieldWidth::U32, FieldOrder::Le), 0x12345678, vec![0x78, 0x56, 0x34, 0x12]),
+            (05, FieldCodec::new(FieldWidth::Varint, FieldOrder::Be), 0, vec![0x00]),
+            (06, FieldCodec::new(FieldWidth::Varint, FieldOrder::Be), 127, vec![0x7f]),
+            (07, FieldCodec::new(FieldWidth::Varint, FieldOrder::Be), 300, vec![0xac, 0x02]),
+            (08, FieldCodec::new(FieldWidth::Varint, FieldOrder::Le), u32::MAX, vec![0xff, 0xff, 0xff, 0xff, 0x0f]),
+            (09, FieldCodec::default(), 1, vec![0, 0, 0, 1]),
+        ];
+        for (step, codec, value, target) in test_data {
+            let result = codec.encode(value);
+            log::debug!("{} | step: {},  result: {:?}", dbgid, step, result);
+            assert!(result == target, "step: {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+            let result = codec.decode(&[target.as_slice(), &[0xff]].concat()).unwrap();
+            assert!(result == Some((value, target.len())), "step: {} \nresult: {:?}\ntarget: {:?}", step, result, (value, target.len()));
+            let result = codec.decode(&target[..target.len() - 1]).unwrap();
+            assert!(result.is_none(), "step: {} \nresult: {:?}\ntarget: None", step, result);
+        }
+        let codec = FieldCodec::new(FieldWidth::Varint, FieldOrder::Be);
+        let result = codec.decode(&[0xff, 0xff, 0xff, 0xff, 0x1f]);
+        assert!(result.is_err(), "\nresult: {:?}\ntarget: Err", result);
+        test_duration.exit();
+    }
+    ///
+    /// Testing [FieldCodec.with_len] accepts 1, 2 & 4 bytes only
+    #[test]
+    fn with_len() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        log::debug!("");
+        let dbgid = DbgId("test".to_owned());
+        log::debug!("\n{}", dbgid);
+        let test_duration = TestDuration::new(&dbgid, Duration::from_secs(1));
+        test_duration.run().unwrap();
+        let test_data = [
+            (00, 1, Some(FieldWidth::U8)),
+            (01, 2, Some(FieldWidth::U16)),
+            (02, 4, Some(FieldWidth::U32)),
+            (03, 0, None),
+            (04, 3, None),
+            (05, 8, None),
+        ];
+        for (step, len, target) in test_data {
+            let result = FieldCodec::with_len(len).map(|codec| codec.width).ok();
+            log::debug!("{} | step: {},  result: {:?}", dbgid, step, result);
+            assert!(result == target, "step: {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+        test_duration.exit();
+    }
+}
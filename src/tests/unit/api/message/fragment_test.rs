@@ -0,0 +1,126 @@
+#[cfg(test)]
+
+mod fragment {
+    use std::{sync::Once, time::Duration};
+    use testing::stuff::max_test_duration::TestDuration;
+    use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
+    use crate::{api::message::{fields::FieldId, fragment::{Fragment, FragmentStream, Fragments, Reassembly}, message_kind::MessageKind, msg_kind::MsgKind}, debug::dbg_id::DbgId};
+    ///
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+            // implement your initialisation code to be called only once for current test file
+        })
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Returns fragments of the `payload`
+    fn fragments(payload: &[u8], len: usize) -> Vec<Fragment> {
+        Fragments::new(MessageKind::Bytes, payload, len).map(|value| match value.unwrap() {
+            MsgKind::Fragment(fragment) => fragment,
+            value => panic!("Fragment expected, but found {:?}", value),
+        }).collect()
+    }
+    ///
+    /// Testing [Fragments] splits the payload
+    #[test]
+    fn split() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        log::debug!("");
+        let dbgid = DbgId("test".to_owned());
+        log::debug!("\n{}", dbgid);
+        let test_duration = TestDuration::new(&dbgid, Duration::from_secs(1));
+        test_duration.run().unwrap();
+        let payload: Vec<u8> = (0..10).collect();
+        let test_data = [
+            (00, 4, vec![(0, false, vec![0, 1, 2, 3]), (1, false, vec![4, 5, 6, 7]), (2, true, vec![8, 9])]),
+            (01, 5, vec![(0, false, vec![0, 1, 2, 3, 4]), (1, true, vec![5, 6, 7, 8, 9])]),
+            (02, 10, vec![(0, true, payload.clone())]),
+            (03, 64, vec![(0, true, payload.clone())]),
+        ];
+        for (step, len, target) in test_data {
+            let result: Vec<(u32, bool, Vec<u8>)> = fragments(&payload, len).into_iter().map(|f| (f.index, f.last, f.bytes)).collect();
+            log::debug!("{} | step: {},  result: {:?}", dbgid, step, result);
+            assert!(result == target, "step: {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+        let result = fragments(&[], 4);
+        let target = vec![Fragment { index: 0, last: true, kind: MessageKind::Bytes, bytes: vec![] }];
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        // Fragment round trip
+        for fragment in fragments(&payload, 3) {
+            let value = MsgKind::Fragment(fragment);
+            let result = MsgKind::from_be_bytes(&MessageKind::Fragment, &value.to_be_bytes()).unwrap();
+            assert!(result == value, "\nresult: {:?}\ntarget: {:?}", result, value);
+        }
+        test_duration.exit();
+    }
+    ///
+    /// Testing [Reassembly] & [FragmentStream]
+    #[test]
+    fn reassembly() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        log::debug!("");
+        let dbgid = DbgId("test".to_owned());
+        log::debug!("\n{}", dbgid);
+        let test_duration = TestDuration::new(&dbgid, Duration::from_secs(1));
+        test_duration.run().unwrap();
+        let payload1: Vec<u8> = (0..10).collect();
+        let payload2: Vec<u8> = (100..107).collect();
+        let mut reassembly = Reassembly::new(&dbgid);
+        let mut fragments2 = fragments(&payload2, 4).into_iter();
+        let mut results = vec![];
+        // Fragments of the two payloads interleaved
+        for fragment in fragments(&payload1, 3) {
+            if let Some(result) = reassembly.push(&FieldId(1), fragment).unwrap() {
+                results.push((1, result));
+            }
+            if let Some(fragment) = fragments2.next() {
+                if let Some(result) = reassembly.push(&FieldId(2), fragment).unwrap() {
+                    results.push((2, result));
+                }
+            }
+        }
+        let target = vec![(2, MsgKind::Bytes(payload2.clone())), (1, MsgKind::Bytes(payload1.clone()))];
+        assert!(results == target, "\nresult: {:?}\ntarget: {:?}", results, target);
+        // Fragment missed
+        let mut fragments1 = fragments(&payload1, 3).into_iter();
+        reassembly.push(&FieldId(1), fragments1.next().unwrap()).unwrap();
+        fragments1.next();
+        let result = reassembly.push(&FieldId(1), fragments1.next().unwrap());
+        assert!(result.is_err(), "\nresult: {:?}\ntarget: Err", result);
+        let result = reassembly.push(&FieldId(1), fragments1.next().unwrap());
+        assert!(result.is_err(), "\nresult: {:?}\ntarget: Err", result);
+        // Maximum length exceeded
+        let mut reassembly = Reassembly::new(&dbgid).with_max(5);
+        let mut fragments1 = fragments(&payload1, 3).into_iter();
+        let result = reassembly.push(&FieldId(1), fragments1.next().unwrap());
+        assert!(result.is_ok(), "\nresult: {:?}\ntarget: Ok", result);
+        let result = reassembly.push(&FieldId(1), fragments1.next().unwrap());
+        assert!(result.is_err(), "\nresult: {:?}\ntarget: Err", result);
+        // Stream
+        let mut stream = FragmentStream::new();
+        let mut result = vec![];
+        for fragment in fragments(&payload1, 4) {
+            result.extend(stream.push(&FieldId(3), fragment).unwrap().bytes);
+        }
+        assert!(stream.is_done(), "\nresult: {:?}\ntarget: {:?}", stream.is_done(), true);
+        assert!(stream.received() == 10, "\nresult: {:?}\ntarget: {:?}", stream.received(), 10);
+        assert!(result == payload1, "\nresult: {:?}\ntarget: {:?}", result, payload1);
+        let mut stream = FragmentStream::new();
+        let mut fragments1 = fragments(&payload1, 4).into_iter();
+        stream.push(&FieldId(3), fragments1.next().unwrap()).unwrap();
+        let result = stream.push(&FieldId(4), fragments1.next().unwrap());
+        assert!(result.is_err(), "\nresult: {:?}\ntarget: Err", result);
+        test_duration.exit();
+    }
+}
@@ -4,7 +4,7 @@ mod message {
     use std::{sync::Once, time::Duration};
     use testing::stuff::max_test_duration::TestDuration;
     use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
-    use crate::{api::message::{fields::{FieldData, FieldId, FieldKind, FieldSize, FieldSyn}, message::{Bytes, Message, MessageField, MessageParse}, message_kind::MessageKind}, debug::dbg_id::DbgId, error::str_err::StrErr};
+    use crate::{api::message::{fields::{FieldData, FieldId, FieldKind, FieldSize, FieldSyn}, message::{Message, MessageField, MessageParse}, message_kind::MessageKind, msg_kind::MsgKind, parse_err::ParseErr}, debug::dbg_id::DbgId};
     ///
     ///
     static INIT: Once = Once::new();
@@ -62,14 +62,112 @@ mod message {
         );
         for (step, data, id, target) in test_data {
             log::debug!("{} | step: {},  id: {},  kind: {:?},  size: {},  data: {:?}", dbgid, step, id, target[1], target[6..].len(), data);
-            let result = message.build(data.as_bytes().to_owned().as_mut(), id);
+            let result = message.build(data.as_bytes().to_owned().as_mut(), id).unwrap();
             assert!(result == target, "step: {} \nresult: {:?}\ntarget: {:?}", step, result, target);
         }
         test_duration.exit();
     }
+    ///
+    /// Testing [Message].build_kind
+    #[test]
+    fn build_kind() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        log::debug!("");
+        let dbgid = DbgId("test".to_owned());
+        log::debug!("\n{}", dbgid);
+        let test_duration = TestDuration::new(&dbgid, Duration::from_secs(1));
+        test_duration.run().unwrap();
+        let test_data = [
+            (
+                00, MsgKind::String("012".to_owned()), 1u32,
+                vec![22, 0, 0, 0, 1, MessageKind::String as u8, 00, 00, 00, 03, 48, 49, 50],
+            ),
+            (
+                01, MsgKind::U64(0x0102030405060708), 2,
+                vec![22, 0, 0, 0, 2, MessageKind::U64 as u8, 00, 00, 00, 08, 1, 2, 3, 4, 5, 6, 7, 8],
+            ),
+            (
+                02, MsgKind::Bool(true), 3,
+                vec![22, 0, 0, 0, 3, MessageKind::Bool as u8, 00, 00, 00, 01, 1],
+            ),
+            (
+                03, MsgKind::Empty, 4,
+                vec![22, 0, 0, 0, 4, MessageKind::Empty as u8, 00, 00, 00, 00],
+            ),
+            (
+                04, MsgKind::I16(-2), 5,
+                vec![22, 0, 0, 0, 5, MessageKind::I16 as u8, 00, 00, 00, 02, 0xff, 0xfe],
+            ),
+        ];
+        let mut message = Message::new(
+            &dbgid,
+            vec![
+                MessageField::Syn(FieldSyn::default()),
+                MessageField::Id(FieldId(4)),
+                MessageField::Kind(FieldKind(MessageKind::Bytes)),
+                MessageField::Size(FieldSize(4)),
+                MessageField::Data(FieldData(vec![]))
+            ],
+            FakeParse {},
+        );
+        for (step, value, id, target) in test_data {
+            log::debug!("{} | step: {},  id: {},  value: {:?}", dbgid, step, id, value);
+            let result = message.build_kind(&value, id).unwrap();
+            assert!(result == target, "step: {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+        test_duration.exit();
+    }
+    ///
+    /// Testing [Message].build & [Message].build_kind returns error instead of the message which can't be built
+    #[test]
+    fn build_errors() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        log::debug!("");
+        let dbgid = DbgId("test".to_owned());
+        log::debug!("\n{}", dbgid);
+        let test_duration = TestDuration::new(&dbgid, Duration::from_secs(1));
+        test_duration.run().unwrap();
+        let mut message = Message::new(
+            &dbgid,
+            vec![
+                MessageField::Syn(FieldSyn::default()),
+                MessageField::Id(FieldId(4)),
+                MessageField::Kind(FieldKind(MessageKind::Bytes)),
+                MessageField::Size(FieldSize(1)),
+                MessageField::Data(FieldData(vec![]))
+            ],
+            FakeParse {},
+        );
+        let result = message.build(&[0; 255], 1).unwrap().len();
+        let target = 1 + 4 + 1 + 1 + 255;
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        let test_data = [
+            (00, MsgKind::Bytes(vec![0; 256])),
+            (01, MsgKind::String("0".repeat(300))),
+            (02, MsgKind::Array(MessageKind::U32, vec![MsgKind::U32(1), MsgKind::Bool(true)])),
+        ];
+        for (step, value) in test_data {
+            let result = message.build_kind(&value, 1);
+            log::debug!("{} | step: {},  result: {:?}", dbgid, step, result.as_ref().map(|bytes| bytes.len()));
+            assert!(result.is_err(), "step: {} \nresult: {:?}\ntarget: Err", step, result);
+        }
+        let result = message.build(&[0; 256], 1);
+        assert!(result.is_err(), "\nresult: {:?}\ntarget: Err", result);
+        test_duration.exit();
+    }
     struct FakeParse {}
     impl MessageParse<()> for FakeParse {
-        fn parse(&mut self, _: Bytes) -> Result<(), StrErr> {
+        fn parse(&mut self, _: &[u8]) -> Result<(), ParseErr> {
+            todo!()
+        }
+        fn read_from(&mut self, _: usize, _: &mut dyn FnMut(&mut [u8]) -> std::io::Result<usize>) -> std::io::Result<usize> {
+            todo!()
+        }
+        fn skipped(&mut self) -> usize {
             todo!()
         }
         fn reset(&mut self) {
@@ -109,7 +109,7 @@ mod message {
         for (step, messages, target_id, target_kind, target_size, target_bytes) in test_data {
             let mut result_bytes = vec![];
             for bytes in messages {
-                match message.parse(bytes) {
+                match message.parse(&bytes) {
                     Ok((id, kind, size, bytes)) => {
                         log::debug!("{} | step: {},  id: {:?},  kind: {:?},  size: {:?},  bytes: {:?}", dbgid, step, id, kind, size, bytes);
                         let result = id;
@@ -264,7 +264,7 @@ mod message {
         for (step, messages, (target_id, target_kind, target_size, target_bytes)) in test_data {
             let mut result_data = None;
             for bytes in messages {
-                match message.parse(bytes) {
+                match message.parse(&bytes) {
                     Ok((id, kind, size, bytes)) => {
                         log::debug!("{} | step: {},  id: {:?},  kind: {:?},  size: {:?},  data: {:?}", dbgid, step, id, kind, size, bytes);
                         let result = id;
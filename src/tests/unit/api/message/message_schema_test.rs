@@ -0,0 +1,200 @@
+#[cfg(test)]
+
+mod message_schema {
+    use std::{sync::Once, time::Duration};
+    use testing::stuff::max_test_duration::TestDuration;
+    use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
+    use crate::{api::message::{fields::{FieldCodec, FieldCrc, FieldData, FieldId, FieldKind, FieldOrder, FieldSize, FieldSyn, FieldWidth}, message::{MessageField, MessageParse}, message_kind::MessageKind, message_schema::MessageSchema, msg_kind::MsgKind, parse_err::ParseErr}, debug::dbg_id::DbgId};
+    ///
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+            // implement your initialisation code to be called only once for current test file
+        })
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Testing [MessageSchema.new] rejects invalid layouts
+    #[test]
+    fn new() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        log::debug!("");
+        let dbgid = DbgId("test".to_owned());
+        log::debug!("\n{}", dbgid);
+        let test_duration = TestDuration::new(&dbgid, Duration::from_secs(1));
+        test_duration.run().unwrap();
+        let syn = || MessageField::Syn(FieldSyn::default());
+        let id = || MessageField::Id(FieldId(4));
+        let kind = || MessageField::Kind(FieldKind(MessageKind::Bytes));
+        let size = || MessageField::Size(FieldSize(4));
+        let data = || MessageField::Data(FieldData(vec![]));
+        let crc = || MessageField::Crc(FieldCrc);
+        let test_data = [
+            (00, vec![syn(), id(), kind(), size(), data()], true),
+            (01, vec![syn(), id(), kind(), size(), data(), crc()], true),
+            (02, vec![id(), kind(), size(), data()], false),
+            (03, vec![syn(), id(), kind(), data(), size()], false),
+            (04, vec![syn(), id(), kind(), size()], false),
+            (05, vec![syn(), kind(), id(), size(), data()], false),
+            (06, vec![syn(), id(), id(), kind(), size(), data()], false),
+            (07, vec![syn(), id(), kind(), size(), crc(), data()], false),
+            (08, vec![syn(), id(), size(), data()], false),
+            (09, vec![], false),
+            (10, vec![syn(), MessageField::Id(FieldId(1)), kind(), MessageField::Size(FieldSize(2)), data()], true),
+            (11, vec![syn(), MessageField::Id(FieldId(3)), kind(), size(), data()], false),
+            (12, vec![syn(), MessageField::Id(FieldId(0)), kind(), size(), data()], false),
+            (13, vec![syn(), id(), kind(), MessageField::Size(FieldSize(8)), data()], false),
+        ];
+        for (step, fields, target) in test_data {
+            let result = MessageSchema::new(&dbgid, fields);
+            log::debug!("{} | step: {},  result: {:?}", dbgid, step, result);
+            assert!(result.is_ok() == target, "step: {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+        test_duration.exit();
+    }
+    ///
+    /// Testing message produced by [MessageSchema.message] parses what it builds
+    #[test]
+    fn message() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        log::debug!("");
+        let dbgid = DbgId("test".to_owned());
+        log::debug!("\n{}", dbgid);
+        let test_duration = TestDuration::new(&dbgid, Duration::from_secs(1));
+        test_duration.run().unwrap();
+        let fields = vec![
+            MessageField::Syn(FieldSyn::default()),
+            MessageField::Id(FieldId(4)),
+            MessageField::Kind(FieldKind(MessageKind::Bytes)),
+            MessageField::Size(FieldSize(4)),
+            MessageField::Data(FieldData(vec![])),
+        ];
+        let test_data = [
+            (00, fields.clone()),
+            (01, [fields, vec![MessageField::Crc(FieldCrc)]].concat()),
+        ];
+        for (step, fields) in test_data {
+            let mut message = MessageSchema::new(&dbgid, fields).unwrap().message();
+            let values = [MsgKind::Bytes(vec![1, 2, 3]), MsgKind::String("Hello".to_owned()), MsgKind::U32(123)];
+            let bytes: Vec<u8> = values.iter().enumerate().flat_map(|(id, value)| message.build_kind(value, id as u32).unwrap()).collect();
+            let result = message.parse_all(&bytes);
+            log::debug!("{} | step: {},  result: {:?}", dbgid, step, result);
+            assert!(result.len() == values.len(), "step: {} \nresult: {:?}\ntarget: {:?}", step, result, values);
+            for (id, (result, target)) in result.into_iter().zip(values).enumerate() {
+                let (result_id, kind, _, bytes) = result.unwrap();
+                let result = MsgKind::from_be_bytes(&kind, &bytes).unwrap();
+                assert!(result_id == FieldId(id as u32), "step: {} \nresult: {:?}\ntarget: {:?}", step, result_id, FieldId(id as u32));
+                assert!(result == target, "step: {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+            }
+        }
+        let mut message = MessageSchema::new(
+            &dbgid,
+            vec![
+                MessageField::Syn(FieldSyn::default()),
+                MessageField::Id(FieldId(4)),
+                MessageField::Kind(FieldKind(MessageKind::Bytes)),
+                MessageField::Size(FieldSize(4)),
+                MessageField::Data(FieldData(vec![])),
+            ],
+        ).unwrap().with_max_size(2).message();
+        let bytes = message.build(&[1, 2, 3], 1).unwrap();
+        let result = message.parse(&bytes);
+        assert!(matches!(result, Err(ParseErr::Size { size: 3, max: 2 })), "\nresult: {:?}\ntarget: Err(ParseErr::Size)", result);
+        test_duration.exit();
+    }
+    ///
+    /// Testing [MessageSchema] with configured `Id` & `Size` codecs builds and parses consistently
+    #[test]
+    fn codec() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        log::debug!("");
+        let dbgid = DbgId("test".to_owned());
+        log::debug!("\n{}", dbgid);
+        let test_duration = TestDuration::new(&dbgid, Duration::from_secs(1));
+        test_duration.run().unwrap();
+        let fields = |id: u32, size: u32| vec![
+            MessageField::Syn(FieldSyn::default()),
+            MessageField::Id(FieldId(id)),
+            MessageField::Kind(FieldKind(MessageKind::Bytes)),
+            MessageField::Size(FieldSize(size)),
+            MessageField::Data(FieldData(vec![])),
+            MessageField::Crc(FieldCrc),
+        ];
+        let test_data = [
+            (00, fields(1, 2), FieldCodec::new(FieldWidth::U8, FieldOrder::Be), FieldCodec::new(FieldWidth::U16, FieldOrder::Le), 1 + 1 + 1 + 2 + 4),
+            (01, fields(2, 1), FieldCodec::new(FieldWidth::U16, FieldOrder::Le), FieldCodec::new(FieldWidth::U8, FieldOrder::Be), 1 + 2 + 1 + 1 + 4),
+            (02, fields(4, 4), FieldCodec::new(FieldWidth::Varint, FieldOrder::Be), FieldCodec::new(FieldWidth::Varint, FieldOrder::Be), 1 + 1 + 1 + 1 + 4),
+            (03, fields(4, 4), FieldCodec::new(FieldWidth::U32, FieldOrder::Le), FieldCodec::new(FieldWidth::U32, FieldOrder::Le), 1 + 4 + 1 + 4 + 4),
+        ];
+        for (step, fields, id_codec, size_codec, overhead) in test_data {
+            let mut message = MessageSchema::new(&dbgid, fields).unwrap()
+                .with_id_codec(id_codec).unwrap()
+                .with_size_codec(size_codec).unwrap()
+                .message();
+            let values = [MsgKind::Bytes(vec![1, 2, 3]), MsgKind::String("Hello".to_owned()), MsgKind::U32(123)];
+            let frames: Vec<Vec<u8>> = values.iter().enumerate().map(|(id, value)| message.build_kind(value, id as u32).unwrap()).collect();
+            let result = frames[0].len();
+            let target = overhead + 3;
+            assert!(result == target, "step: {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+            let result = message.parse_all(&frames.concat());
+            log::debug!("{} | step: {},  result: {:?}", dbgid, step, result);
+            assert!(result.len() == values.len(), "step: {} \nresult: {:?}\ntarget: {:?}", step, result, values);
+            for (id, (result, target)) in result.into_iter().zip(values).enumerate() {
+                let (result_id, kind, _, bytes) = result.unwrap();
+                let result = MsgKind::from_be_bytes(&kind, &bytes).unwrap();
+                assert!(result_id == FieldId(id as u32), "step: {} \nresult: {:?}\ntarget: {:?}", step, result_id, FieldId(id as u32));
+                assert!(result == target, "step: {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+            }
+        }
+        test_duration.exit();
+    }
+    ///
+    /// Testing [MessageSchema.with_id_codec] & [MessageSchema.with_size_codec] reject the codec of other width than the field
+    #[test]
+    fn codec_width() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        log::debug!("");
+        let dbgid = DbgId("test".to_owned());
+        log::debug!("\n{}", dbgid);
+        let test_duration = TestDuration::new(&dbgid, Duration::from_secs(1));
+        test_duration.run().unwrap();
+        let schema = MessageSchema::new(
+            &dbgid,
+            vec![
+                MessageField::Syn(FieldSyn::default()),
+                MessageField::Id(FieldId(2)),
+                MessageField::Kind(FieldKind(MessageKind::Bytes)),
+                MessageField::Size(FieldSize(4)),
+                MessageField::Data(FieldData(vec![])),
+            ],
+        ).unwrap();
+        let test_data = [
+            (00, FieldCodec::new(FieldWidth::U16, FieldOrder::Le), FieldCodec::new(FieldWidth::U32, FieldOrder::Le), true),
+            (01, FieldCodec::new(FieldWidth::U16, FieldOrder::Be), FieldCodec::new(FieldWidth::Varint, FieldOrder::Be), true),
+            (02, FieldCodec::new(FieldWidth::U8, FieldOrder::Be), FieldCodec::new(FieldWidth::U32, FieldOrder::Be), false),
+            (03, FieldCodec::new(FieldWidth::U32, FieldOrder::Be), FieldCodec::new(FieldWidth::U32, FieldOrder::Be), false),
+            (04, FieldCodec::new(FieldWidth::Varint, FieldOrder::Be), FieldCodec::new(FieldWidth::U32, FieldOrder::Be), false),
+            (05, FieldCodec::new(FieldWidth::U16, FieldOrder::Be), FieldCodec::new(FieldWidth::U16, FieldOrder::Be), false),
+        ];
+        for (step, id_codec, size_codec, target) in test_data {
+            let result = schema.clone().with_id_codec(id_codec).and_then(|schema| schema.with_size_codec(size_codec));
+            log::debug!("{} | step: {},  result: {:?}", dbgid, step, result);
+            assert!(result.is_ok() == target, "step: {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+        test_duration.exit();
+    }
+}
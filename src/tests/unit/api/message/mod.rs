@@ -1,5 +1,10 @@
+mod fields_test;
+mod fragment_test;
 mod message_build_test;
 mod message_parse_test;
+mod message_schema_test;
+mod msg_kind_test;
+mod parse_crc_test;
 mod parse_data_test;
 mod parse_id_test;
 mod parse_kind_test;
@@ -0,0 +1,255 @@
+#[cfg(test)]
+
+mod msg_kind {
+    use std::{sync::Once, time::Duration};
+    use std::str::FromStr;
+    use bigdecimal::BigDecimal;
+    use chrono::{DateTime, FixedOffset, NaiveDate};
+    use uuid::Uuid;
+    use testing::stuff::max_test_duration::TestDuration;
+    use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
+    use crate::{api::message::{message_kind::MessageKind, msg_kind::MsgKind, msg_kind_err::MsgKindErr}, debug::dbg_id::DbgId};
+    ///
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+            // implement your initialisation code to be called only once for current test file
+        })
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Testing [MsgKind::from_be_bytes] is the mirror of the [MsgKind::to_be_bytes]
+    #[test]
+    fn round_trip() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        log::debug!("");
+        let dbgid = DbgId("test".to_owned());
+        log::debug!("\n{}", dbgid);
+        let test_duration = TestDuration::new(&dbgid, Duration::from_secs(1));
+        test_duration.run().unwrap();
+        let test_data = [
+            (00, MsgKind::Any(vec![1, 2, 3])),
+            (01, MsgKind::Empty),
+            (02, MsgKind::Bytes(vec![])),
+            (03, MsgKind::Bytes(vec![22, 0, 255])),
+            (04, MsgKind::Bool(false)),
+            (05, MsgKind::Bool(true)),
+            (06, MsgKind::U16(u16::MAX)),
+            (07, MsgKind::U32(u32::MAX)),
+            (08, MsgKind::U64(u64::MAX)),
+            (09, MsgKind::I16(i16::MIN)),
+            (10, MsgKind::I32(i32::MIN)),
+            (11, MsgKind::I64(i64::MIN)),
+            (12, MsgKind::F32(f32::MAX)),
+            (13, MsgKind::F64(-0.000123)),
+            (14, MsgKind::String("Привет, World!".to_owned())),
+            (15, MsgKind::Timestamp(DateTime::from_timestamp_micros(-1_000_001).unwrap())),
+            (16, MsgKind::Timestamp(DateTime::from_timestamp_micros(1_700_000_000_123_456).unwrap())),
+            (17, MsgKind::Duration(Duration::from_millis(12_345))),
+            (18, MsgKind::TimestampNs(DateTime::from_timestamp_nanos(1_700_000_000_123_456_789).fixed_offset())),
+            (19, MsgKind::TimestampNs(DateTime::from_timestamp_nanos(-1).with_timezone(&FixedOffset::east_opt(3 * 3600).unwrap()))),
+            (20, MsgKind::DurationNs(Duration::new(u64::MAX, 999_999_999))),
+            (21, MsgKind::DurationNs(Duration::from_nanos(1))),
+            (22, MsgKind::Array(MessageKind::F32, vec![MsgKind::F32(1.5), MsgKind::F32(-2.0)])),
+            (23, MsgKind::Array(MessageKind::U16, vec![])),
+            (24, MsgKind::Array(MessageKind::String, vec![MsgKind::String("a".to_owned()), MsgKind::String("".to_owned())])),
+            (25, MsgKind::Map(vec![
+                (MsgKind::String("a".to_owned()), MsgKind::I32(-1)),
+                (MsgKind::U16(2), MsgKind::Array(MessageKind::Bool, vec![MsgKind::Bool(true)])),
+            ])),
+            (26, MsgKind::Struct(vec![
+                ("name".to_owned(), MsgKind::String("Point".to_owned())),
+                ("inner".to_owned(), MsgKind::Struct(vec![("x".to_owned(), MsgKind::F64(1.0)), ("y".to_owned(), MsgKind::F64(-1.0))])),
+                ("empty".to_owned(), MsgKind::Empty),
+            ])),
+            (27, MsgKind::Decimal(BigDecimal::from_str("0").unwrap())),
+            (28, MsgKind::Decimal(BigDecimal::from_str("-123456789012345678901234567890.000000000000000000001").unwrap())),
+            (29, MsgKind::Decimal(BigDecimal::from_str("1.5e-400").unwrap())),
+            (30, MsgKind::Decimal(BigDecimal::from_str("12e300").unwrap())),
+            (31, MsgKind::Uuid(Uuid::from_str("67e55044-10b1-426f-9247-bb680e5fe0c8").unwrap())),
+            (32, MsgKind::Uuid(Uuid::nil())),
+            (33, MsgKind::Date(NaiveDate::from_ymd_opt(1970, 1, 1).unwrap())),
+            (34, MsgKind::Date(NaiveDate::from_ymd_opt(-4713, 11, 24).unwrap())),
+            (35, MsgKind::Date(NaiveDate::MAX)),
+            (36, MsgKind::DateTime(NaiveDate::from_ymd_opt(1969, 12, 31).unwrap().and_hms_nano_opt(23, 59, 59, 999_999_999).unwrap())),
+            (37, MsgKind::DateTime(NaiveDate::from_ymd_opt(2016, 12, 31).unwrap().and_hms_nano_opt(23, 59, 59, 1_500_000_000).unwrap())),
+        ];
+        for (step, target) in test_data {
+            let bytes = target.to_be_bytes();
+            let result = MsgKind::from_be_bytes(&target.kind(), &bytes);
+            log::debug!("{} | step: {},  bytes: {:?},  result: {:?}", dbgid, step, bytes, result);
+            let result = result.unwrap();
+            assert!(result == target, "step: {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+        // DateTime equality ignores the offset
+        let target = FixedOffset::east_opt(-(5 * 3600 + 30 * 60)).unwrap();
+        let value = MsgKind::TimestampNs(DateTime::from_timestamp_nanos(123).with_timezone(&target));
+        match MsgKind::from_be_bytes(&MessageKind::TimestampNs, &value.to_be_bytes()) {
+            Ok(MsgKind::TimestampNs(result)) => {
+                let result = *result.offset();
+                assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+            }
+            result => panic!("\nresult: {:?}\ntarget: {:?}", result, value),
+        }
+        // Decimal keeps the scale
+        let value = BigDecimal::from_str("1.2300").unwrap();
+        match MsgKind::from_be_bytes(&MessageKind::Decimal, &MsgKind::Decimal(value.clone()).to_be_bytes()) {
+            Ok(MsgKind::Decimal(result)) => {
+                let (result, target) = (result.fractional_digit_count(), value.fractional_digit_count());
+                assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+            }
+            result => panic!("\nresult: {:?}\ntarget: {:?}", result, value),
+        }
+        test_duration.exit();
+    }
+    ///
+    /// Testing [MsgKind::from_be_bytes] rejects invalid `Data`
+    #[test]
+    fn errors() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        log::debug!("");
+        let dbgid = DbgId("test".to_owned());
+        log::debug!("\n{}", dbgid);
+        let test_duration = TestDuration::new(&dbgid, Duration::from_secs(1));
+        test_duration.run().unwrap();
+        let test_data = [
+            (00, MessageKind::Empty, vec![0], MsgKindErr::Size { kind: MessageKind::Empty, expected: 0, found: 1 }),
+            (01, MessageKind::Bool, vec![], MsgKindErr::Size { kind: MessageKind::Bool, expected: 1, found: 0 }),
+            (02, MessageKind::Bool, vec![2], MsgKindErr::Bool(2)),
+            (03, MessageKind::U16, vec![1, 2, 3], MsgKindErr::Size { kind: MessageKind::U16, expected: 2, found: 3 }),
+            (04, MessageKind::I32, vec![1, 2, 3], MsgKindErr::Size { kind: MessageKind::I32, expected: 4, found: 3 }),
+            (05, MessageKind::F64, vec![0; 4], MsgKindErr::Size { kind: MessageKind::F64, expected: 8, found: 4 }),
+            (06, MessageKind::F32, vec![0; 8], MsgKindErr::Size { kind: MessageKind::F32, expected: 4, found: 8 }),
+            (07, MessageKind::Timestamp, i64::MAX.to_be_bytes().to_vec(), MsgKindErr::Timestamp(i64::MAX)),
+            (08, MessageKind::Duration, (-1.0f64).to_be_bytes().to_vec(), MsgKindErr::Duration(-1.0)),
+            (09, MessageKind::Array, vec![2, 0, 0, 0, 1], MsgKindErr::Size { kind: MessageKind::Array, expected: 9, found: 5 }),
+            (10, MessageKind::Array, vec![99, 0, 0, 0, 0], MsgKindErr::Kind(99)),
+            (11, MessageKind::Array, vec![16, 0, 0, 0, 1, 0, 0, 0, 3, 1, 2, 3], MsgKindErr::Size { kind: MessageKind::U16, expected: 2, found: 3 }),
+            (12, MessageKind::Map, vec![0, 0, 0, 0, 1], MsgKindErr::Size { kind: MessageKind::Map, expected: 4, found: 5 }),
+            (13, MessageKind::TimestampNs, vec![0; 10], MsgKindErr::Size { kind: MessageKind::TimestampNs, expected: 12, found: 10 }),
+            (14, MessageKind::TimestampNs, [0i64.to_be_bytes().as_slice(), &86_400i32.to_be_bytes()].concat(), MsgKindErr::Offset(86_400)),
+            (15, MessageKind::DurationNs, [0u64.to_be_bytes().as_slice(), &1_000_000_000u32.to_be_bytes()].concat(), MsgKindErr::Nanos(1_000_000_000)),
+            (16, MessageKind::Struct, vec![0, 0, 0, 1, 0, 0, 0, 1, b'a', 8, 0, 0, 0, 1, 2], MsgKindErr::Bool(2)),
+            (17, MessageKind::Decimal, vec![0; 8], MsgKindErr::Size { kind: MessageKind::Decimal, expected: 9, found: 8 }),
+            (18, MessageKind::Uuid, vec![0; 15], MsgKindErr::Size { kind: MessageKind::Uuid, expected: 16, found: 15 }),
+            (19, MessageKind::Date, i32::MAX.to_be_bytes().to_vec(), MsgKindErr::Date(i32::MAX)),
+            (20, MessageKind::DateTime, [i64::MAX.to_be_bytes().as_slice(), &0u32.to_be_bytes()].concat(), MsgKindErr::DateTime(i64::MAX)),
+            (21, MessageKind::DateTime, [0i64.to_be_bytes().as_slice(), &2_000_000_000u32.to_be_bytes()].concat(), MsgKindErr::Nanos(2_000_000_000)),
+        ];
+        for (step, kind, bytes, target) in test_data {
+            let result = MsgKind::from_be_bytes(&kind, &bytes);
+            log::debug!("{} | step: {},  kind: {:?},  result: {:?}", dbgid, step, kind, result);
+            assert!(result == Err(target.clone()), "step: {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+        let result = MsgKind::from_be_bytes(&MessageKind::String, &[0xff, 0xfe]);
+        assert!(matches!(result, Err(MsgKindErr::Utf8(_))), "\nresult: {:?}\ntarget: Utf8 error", result);
+        let mut nested = MsgKind::Array(MessageKind::Bool, vec![]);
+        for _ in 0..(MsgKind::MAX_DEPTH - 1) {
+            nested = MsgKind::Array(MessageKind::Array, vec![nested]);
+        }
+        let result = MsgKind::from_be_bytes(&MessageKind::Array, &nested.to_be_bytes());
+        assert!(result.as_ref() == Ok(&nested), "\nresult: {:?}\ntarget: {:?}", result, nested);
+        let nested = MsgKind::Array(MessageKind::Array, vec![nested]);
+        let result = MsgKind::from_be_bytes(&MessageKind::Array, &nested.to_be_bytes());
+        assert!(result == Err(MsgKindErr::Depth(MsgKind::MAX_DEPTH)), "\nresult: {:?}\ntarget: {:?}", result, MsgKindErr::Depth(MsgKind::MAX_DEPTH));
+        // Array must be homogeneous
+        let test_data = [
+            (01, MsgKind::Array(MessageKind::U32, vec![MsgKind::U32(1), MsgKind::String("2".to_owned())]), MessageKind::U32, MessageKind::String),
+            (02, MsgKind::Array(MessageKind::Array, vec![
+                MsgKind::Array(MessageKind::U32, vec![MsgKind::U32(1)]),
+                MsgKind::Array(MessageKind::Bool, vec![MsgKind::Bool(true)]),
+            ]), MessageKind::U32, MessageKind::Bool),
+            (03, MsgKind::Struct(vec![
+                ("values".to_owned(), MsgKind::Array(MessageKind::Bool, vec![MsgKind::Bool(true), MsgKind::U16(0)])),
+            ]), MessageKind::Bool, MessageKind::U16),
+        ];
+        for (step, value, expected, found) in test_data {
+            let target = MsgKindErr::Element { expected, found };
+            let result = value.encode();
+            assert!(result == Err(target.clone()), "step: {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+            // Mixed scalar elements are decoded under the element `Kind` of the array, so fail on their size
+            let result = MsgKind::from_be_bytes(&value.kind(), &value.to_be_bytes());
+            assert!(result.is_err(), "step: {} \nresult: {:?}\ntarget: Err", step, result);
+        }
+        let value = MsgKind::Array(MessageKind::Array, vec![
+            MsgKind::Array(MessageKind::U32, vec![]),
+            MsgKind::Array(MessageKind::Bool, vec![]),
+        ]);
+        let target = MsgKindErr::Element { expected: MessageKind::U32, found: MessageKind::Bool };
+        let result = MsgKind::from_be_bytes(&MessageKind::Array, &value.to_be_bytes());
+        assert!(result == Err(target.clone()), "\nresult: {:?}\ntarget: {:?}", result, target);
+        let value = MsgKind::Array(MessageKind::Array, vec![MsgKind::Array(MessageKind::U32, vec![MsgKind::U32(1)]), MsgKind::Array(MessageKind::U32, vec![])]);
+        let result = value.encode();
+        assert!(result == Ok(value.to_be_bytes()), "\nresult: {:?}\ntarget: {:?}", result, value.to_be_bytes());
+        // TimestampNs out of the i64 nanoseconds isn't encoded, the bounds are
+        let max = DateTime::from_timestamp_nanos(i64::MAX);
+        let min = DateTime::from_timestamp_nanos(i64::MIN);
+        let over = max + chrono::Duration::nanoseconds(1);
+        let under = min - chrono::Duration::nanoseconds(1);
+        let test_data = [
+            (01, MsgKind::TimestampNs(max.fixed_offset()), None),
+            (02, MsgKind::TimestampNs(min.fixed_offset()), None),
+            (03, MsgKind::TimestampNs(over.fixed_offset()), Some(MsgKindErr::TimestampNs(over.timestamp()))),
+            (04, MsgKind::TimestampNs(under.fixed_offset()), Some(MsgKindErr::TimestampNs(under.timestamp()))),
+            (05, MsgKind::Array(MessageKind::TimestampNs, vec![MsgKind::TimestampNs(over.fixed_offset())]), Some(MsgKindErr::TimestampNs(over.timestamp()))),
+        ];
+        for (step, value, target) in test_data {
+            let result = value.encode();
+            match target {
+                Some(target) => assert!(result == Err(target.clone()), "step: {} \nresult: {:?}\ntarget: {:?}", step, result, target),
+                None => {
+                    let result = result.map(|bytes| MsgKind::from_be_bytes(&MessageKind::TimestampNs, &bytes));
+                    assert!(result == Ok(Ok(value.clone())), "step: {} \nresult: {:?}\ntarget: {:?}", step, result, value);
+                }
+            }
+        }
+        test_duration.exit();
+    }
+    ///
+    /// Testing [MsgKind::deserialize] is the mirror of the `Json`, `MsgPack`, `Cbor` serialization
+    #[test]
+    fn serde() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        log::debug!("");
+        let dbgid = DbgId("test".to_owned());
+        log::debug!("\n{}", dbgid);
+        let test_duration = TestDuration::new(&dbgid, Duration::from_secs(1));
+        test_duration.run().unwrap();
+        #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct Point {
+            name: String,
+            xy: Vec<f64>,
+            valid: Option<bool>,
+        }
+        let target = Point { name: "Point".to_owned(), xy: vec![1.5, -2.0], valid: Some(true) };
+        let test_data = [
+            (00, MsgKind::json(&target).unwrap(), MessageKind::Json),
+            (01, MsgKind::msgpack(&target).unwrap(), MessageKind::MsgPack),
+            (02, MsgKind::cbor(&target).unwrap(), MessageKind::Cbor),
+        ];
+        for (step, value, kind) in test_data {
+            assert!(value.kind() == kind, "step: {} \nresult: {:?}\ntarget: {:?}", step, value.kind(), kind);
+            let value = MsgKind::from_be_bytes(&kind, &value.to_be_bytes()).unwrap();
+            let result: Point = value.deserialize().unwrap();
+            log::debug!("{} | step: {},  result: {:?}", dbgid, step, result);
+            assert!(result == target, "step: {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+        let result = MsgKind::Json(b"{\"name\": 1}".to_vec()).deserialize::<Point>();
+        assert!(matches!(result, Err(MsgKindErr::Serde { kind: MessageKind::Json, .. })), "\nresult: {:?}\ntarget: Serde error", result);
+        let result = MsgKind::Bytes(vec![]).deserialize::<Point>();
+        assert!(matches!(result, Err(MsgKindErr::Serde { kind: MessageKind::Bytes, .. })), "\nresult: {:?}\ntarget: Serde error", result);
+        test_duration.exit();
+    }
+}
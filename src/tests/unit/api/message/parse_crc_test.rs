@@ -0,0 +1,162 @@
+#[cfg(test)]
+
+mod parse_crc {
+    use std::{sync::Once, time::Duration};
+    use testing::stuff::max_test_duration::TestDuration;
+    use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
+    use crate::{
+        api::message::{
+            fields::{FieldCrc, FieldData, FieldId, FieldKind, FieldSize, FieldSyn},
+            message::{Message, MessageField, MessageParse}, message_kind::MessageKind,
+            parse_crc::ParseCrc, parse_data::ParseData, parse_err::ParseErr, parse_id::ParseId, parse_kind::ParseKind, parse_size::ParseSize, parse_syn::ParseSyn,
+        },
+        debug::dbg_id::DbgId,
+    };
+    ///
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+            // implement your initialisation code to be called only once for current test file
+        })
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Testing [FieldCrc::checksum] on the standard check value
+    #[test]
+    fn checksum() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let result = FieldCrc::checksum(b"123456789");
+        let target = 0xCBF43926;
+        assert!(result == target, "\nresult: {:#010x}\ntarget: {:#010x}", result, target);
+        let result = FieldCrc::update(FieldCrc::checksum(b"1234"), b"56789");
+        assert!(result == target, "\nresult: {:#010x}\ntarget: {:#010x}", result, target);
+    }
+    ///
+    /// Testing [ParseCrc.parse]
+    #[test]
+    fn parse() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        log::debug!("");
+        let dbgid = DbgId("test".to_owned());
+        log::debug!("\n{}", dbgid);
+        let test_duration = TestDuration::new(&dbgid, Duration::from_secs(1));
+        test_duration.run().unwrap();
+        let mut builder = Message::new(
+            &dbgid,
+            vec![
+                MessageField::Syn(FieldSyn::default()),
+                MessageField::Id(FieldId(4)),
+                MessageField::Kind(FieldKind(MessageKind::String)),
+                MessageField::Size(FieldSize(4)),
+                MessageField::Data(FieldData(vec![])),
+                MessageField::Crc(FieldCrc),
+            ],
+            FakeParse {},
+        );
+        let mut to_bytes = |data: &str, id: u32| builder.build(data.as_bytes(), id).unwrap();
+        let corrupted = {
+            let mut bytes = to_bytes("corrupted", 7);
+            bytes[12] ^= 0x01;
+            bytes
+        };
+        let test_data = [
+            (
+                00, vec![
+                    to_bytes("123", 1),
+                ],
+                Some((FieldId(1), "123".as_bytes().to_vec())),
+            ),
+            (
+                01, vec![
+                    vec![10, 11, 12],
+                    to_bytes("12345", 2)[..12].to_vec(),
+                    to_bytes("12345", 2)[12..16].to_vec(),
+                    to_bytes("12345", 2)[16..].to_vec(),
+                ],
+                Some((FieldId(2), "12345".as_bytes().to_vec())),
+            ),
+            (
+                02, vec![
+                    corrupted,
+                ],
+                None,
+            ),
+            (
+                03, vec![
+                    [to_bytes("3456", 3), to_bytes("4567", 4)].concat(),
+                ],
+                Some((FieldId(3), "3456".as_bytes().to_vec())),
+            ),
+            (
+                04, vec![
+                    vec![],
+                ],
+                Some((FieldId(4), "4567".as_bytes().to_vec())),
+            ),
+        ];
+        let mut message = ParseCrc::new(
+            &dbgid,
+            FieldCrc,
+            ParseData::new(
+                &dbgid,
+                ParseSize::new(
+                    &dbgid,
+                    FieldSize(4),
+                    ParseKind::new(
+                        &dbgid,
+                        FieldKind(MessageKind::Any),
+                        ParseId::new(
+                            &dbgid,
+                            FieldId(4),
+                            ParseSyn::new(
+                                &dbgid,
+                                FieldSyn::default(),
+                            ),
+                        ),
+                    ),
+                ),
+            ),
+        );
+        for (step, messages, target) in test_data {
+            let mut result = None;
+            for bytes in messages {
+                match message.parse(&bytes) {
+                    Ok((id, kind, size, bytes)) => {
+                        log::debug!("{} | step: {},  id: {:?},  kind: {:?},  size: {:?},  bytes: {:?}", dbgid, step, id, kind, size, bytes);
+                        result = Some((id, bytes));
+                    }
+                    Err(err) => {
+                        log::debug!("{} | {}", dbgid, err);
+                    }
+                }
+            }
+            assert!(result == target, "step: {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+        test_duration.exit();
+    }
+    struct FakeParse {}
+    impl MessageParse<()> for FakeParse {
+        fn parse(&mut self, _: &[u8]) -> Result<(), ParseErr> {
+            todo!()
+        }
+        fn read_from(&mut self, _: usize, _: &mut dyn FnMut(&mut [u8]) -> std::io::Result<usize>) -> std::io::Result<usize> {
+            todo!()
+        }
+        fn skipped(&mut self) -> usize {
+            todo!()
+        }
+        fn reset(&mut self) {
+            todo!()
+        }
+    }
+}
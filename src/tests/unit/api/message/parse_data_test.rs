@@ -4,7 +4,7 @@ mod parse_data {
     use std::{sync::Once, time::Duration};
     use testing::stuff::max_test_duration::TestDuration;
     use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
-    use crate::{api::message::{fields::{FieldId, FieldKind, FieldSize, FieldSyn}, message::MessageParse, message_kind::MessageKind, parse_data::ParseData, parse_id::ParseId, parse_kind::ParseKind, parse_size::ParseSize, parse_syn::ParseSyn}, debug::dbg_id::DbgId};
+    use crate::{api::message::{fields::{FieldId, FieldKind, FieldSize, FieldSyn}, message::MessageParse, message_kind::MessageKind, parse_data::ParseData, parse_err::ParseErr, parse_id::ParseId, parse_kind::ParseKind, parse_size::ParseSize, parse_syn::ParseSyn}, debug::dbg_id::DbgId};
     ///
     ///
     static INIT: Once = Once::new();
@@ -122,7 +122,7 @@ mod parse_data {
         for (step, messages, target_id, target_kind, target_size, target_bytes) in test_data {
             let mut result_bytes = vec![];
             for bytes in messages {
-                match message.parse(bytes) {
+                match message.parse(&bytes) {
                     Ok((id, kind, size, bytes)) => {
                         log::debug!("{} | step: {},  id: {:?},  kind: {:?},  size: {:?},  bytes: {:?}", dbgid, step, id, kind, size, bytes);
                         let result = id;
@@ -274,7 +274,7 @@ mod parse_data {
         for (step, messages, (target_id, target_kind, target_size, target_bytes)) in test_data {
             let mut result_data = None;
             for bytes in messages {
-                match message.parse(bytes) {
+                match message.parse(&bytes) {
                     Ok((id, kind, size, bytes)) => {
                         log::debug!("{} | step: {},  id: {:?},  kind: {:?},  size: {:?},  data: {:?}", dbgid, step, id, kind, size, bytes);
                         let result = id;
@@ -296,4 +296,268 @@ mod parse_data {
         }
         test_duration.exit();
     }
+    ///
+    /// Testing `ParseSize.with_max` discards oversized message and finds the next one
+    #[test]
+    fn parse_max_size() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        log::debug!("");
+        let dbgid = DbgId("test".to_owned());
+        log::debug!("\n{}", dbgid);
+        let test_duration = TestDuration::new(&dbgid, Duration::from_secs(1));
+        test_duration.run().unwrap();
+        fn to_bytes(data: &[u8], size: u32, id: u32) -> Vec<u8> {
+            [
+                FieldSyn::default().0.to_be_bytes().as_slice(),
+                FieldId(id).to_be_bytes().as_slice(),
+                MessageKind::Bytes.to_bytes(),
+                size.to_be_bytes().as_slice(),
+                data,
+            ].concat()
+        }
+        let mut message = ParseData::new(
+            &dbgid,
+            ParseSize::new(
+                &dbgid,
+                FieldSize(4),
+                ParseKind::new(
+                    &dbgid,
+                    FieldKind(MessageKind::Any),
+                    ParseId::new(
+                        &dbgid,
+                        FieldId(4),
+                        ParseSyn::new(
+                            &dbgid,
+                            FieldSyn::default(),
+                        ),
+                    ),
+                ),
+            ).with_max(8),
+        );
+        let oversized = to_bytes(&[1, 2, 3, 4, 5, 6, 7, 8, 9], u32::MAX, 1);
+        let valid = to_bytes(&[1, 2, 3, 4, 5, 6, 7, 8], 8, 2);
+        let result = message.parse(&[oversized, valid].concat());
+        log::debug!("{} | result: {:?}", dbgid, result);
+        assert!(matches!(result, Err(ParseErr::Size { size, max: 8 }) if size == u32::MAX as usize), "\nresult: {:?}\ntarget: Err(ParseErr::Size)", result);
+        let result = message.parse(&[]);
+        log::debug!("{} | result: {:?}", dbgid, result);
+        match result {
+            Ok((id, kind, size, bytes)) => {
+                assert!(id == FieldId(2), "\nresult: {:?}\ntarget: {:?}", id, FieldId(2));
+                assert!(kind == MessageKind::Bytes, "\nresult: {:?}\ntarget: {:?}", kind, MessageKind::Bytes);
+                assert!(size == FieldSize(8), "\nresult: {:?}\ntarget: {:?}", size, FieldSize(8));
+                assert!(bytes == vec![1, 2, 3, 4, 5, 6, 7, 8], "\nresult: {:?}\ntarget: {:?}", bytes, vec![1, 2, 3, 4, 5, 6, 7, 8]);
+            }
+            Err(err) => panic!("{} | Error: {:?}", dbgid, err),
+        }
+        test_duration.exit();
+    }
+    ///
+    /// Testing `ParseData` resynchronises on the next `Syn` after the corrupted header
+    #[test]
+    fn parse_resync() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        log::debug!("");
+        let dbgid = DbgId("test".to_owned());
+        log::debug!("\n{}", dbgid);
+        let test_duration = TestDuration::new(&dbgid, Duration::from_secs(1));
+        test_duration.run().unwrap();
+        fn to_bytes(data: &[u8], kind: u8, id: u32) -> Vec<u8> {
+            [
+                FieldSyn::default().0.to_be_bytes().as_slice(),
+                FieldId(id).to_be_bytes().as_slice(),
+                &[kind],
+                (data.len() as u32).to_be_bytes().as_slice(),
+                data,
+            ].concat()
+        }
+        let mut message = ParseData::new(
+            &dbgid,
+            ParseSize::new(
+                &dbgid,
+                FieldSize(4),
+                ParseKind::new(
+                    &dbgid,
+                    FieldKind(MessageKind::Any),
+                    ParseId::new(
+                        &dbgid,
+                        FieldId(4),
+                        ParseSyn::new(
+                            &dbgid,
+                            FieldSyn::default(),
+                        ),
+                    ),
+                ),
+            ),
+        );
+        let bytes = [
+            to_bytes(&[1, 2], MessageKind::Bytes as u8, 1),
+            to_bytes(&[7, 8], 99, 3),
+            to_bytes(&[5, 6], MessageKind::Bytes as u8, 2),
+        ].concat();
+        let test_data = [
+            (00, bytes, Some((FieldId(1), vec![1, 2]))),
+            (01, vec![], None),
+            (02, vec![], Some((FieldId(2), vec![5, 6]))),
+        ];
+        let mut skipped = 0;
+        for (step, bytes, target) in test_data {
+            let result = message.parse(&bytes);
+            skipped += message.skipped();
+            log::debug!("{} | step: {},  result: {:?}", dbgid, step, result);
+            match target {
+                Some((target_id, target_bytes)) => {
+                    let (id, _, _, bytes) = result.unwrap();
+                    assert!(id == target_id, "step: {} \nresult: {:?}\ntarget: {:?}", step, id, target_id);
+                    assert!(bytes == target_bytes, "step: {} \nresult: {:?}\ntarget: {:?}", step, bytes, target_bytes);
+                }
+                None => {
+                    assert!(matches!(result, Err(ParseErr::Field(_))), "step: {} \nresult: {:?}\ntarget: Err(ParseErr::Field)", step, result);
+                }
+            }
+        }
+        let target = 12;
+        assert!(skipped == target, "\nresult: {:?}\ntarget: {:?}", skipped, target);
+        test_duration.exit();
+    }
+    ///
+    /// Testing `ParseData.parse_all` returns all complete messages, keeps incomplete one
+    #[test]
+    fn parse_all() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        log::debug!("");
+        let dbgid = DbgId("test".to_owned());
+        log::debug!("\n{}", dbgid);
+        let test_duration = TestDuration::new(&dbgid, Duration::from_secs(1));
+        test_duration.run().unwrap();
+        fn to_bytes(data: &[u8], kind: u8, id: u32) -> Vec<u8> {
+            [
+                FieldSyn::default().0.to_be_bytes().as_slice(),
+                FieldId(id).to_be_bytes().as_slice(),
+                &[kind],
+                (data.len() as u32).to_be_bytes().as_slice(),
+                data,
+            ].concat()
+        }
+        let mut message = ParseData::new(
+            &dbgid,
+            ParseSize::new(
+                &dbgid,
+                FieldSize(4),
+                ParseKind::new(
+                    &dbgid,
+                    FieldKind(MessageKind::Any),
+                    ParseId::new(
+                        &dbgid,
+                        FieldId(4),
+                        ParseSyn::new(
+                            &dbgid,
+                            FieldSyn::default(),
+                        ),
+                    ),
+                ),
+            ),
+        );
+        let incomplete = to_bytes(&[7, 8, 9], MessageKind::Bytes as u8, 5);
+        let test_data = [
+            (
+                00,
+                [
+                    to_bytes(&[1], MessageKind::Bytes as u8, 1),
+                    to_bytes(&[2, 3], MessageKind::Bytes as u8, 2),
+                    to_bytes(&[0], 99, 3),
+                    to_bytes(&[4, 5, 6], MessageKind::Bytes as u8, 4),
+                    incomplete[..8].to_vec(),
+                ].concat(),
+                vec![Some((FieldId(1), vec![1])), Some((FieldId(2), vec![2, 3])), None, Some((FieldId(4), vec![4, 5, 6]))],
+            ),
+            (01, incomplete[8..].to_vec(), vec![Some((FieldId(5), vec![7, 8, 9]))]),
+            (02, vec![], vec![]),
+        ];
+        for (step, bytes, target) in test_data {
+            let result = message.parse_all(&bytes);
+            log::debug!("{} | step: {},  result: {:?}", dbgid, step, result);
+            assert!(result.len() == target.len(), "step: {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+            for (result, target) in result.into_iter().zip(target) {
+                match target {
+                    Some((target_id, target_bytes)) => {
+                        let (id, _, _, bytes) = result.unwrap();
+                        assert!(id == target_id, "step: {} \nresult: {:?}\ntarget: {:?}", step, id, target_id);
+                        assert!(bytes == target_bytes, "step: {} \nresult: {:?}\ntarget: {:?}", step, bytes, target_bytes);
+                    }
+                    None => {
+                        assert!(matches!(result, Err(ParseErr::Field(_))), "step: {} \nresult: {:?}\ntarget: Err(ParseErr::Field)", step, result);
+                    }
+                }
+            }
+        }
+        test_duration.exit();
+    }
+    ///
+    /// Testing `ParseData.read_from` reads bytes into the buffer, messages are taken by `parse_all` with no bytes
+    /// - Data taking the whole buffer is handed over, the following reads are going into the new buffer
+    #[test]
+    fn read_from() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        log::debug!("");
+        let dbgid = DbgId("test".to_owned());
+        log::debug!("\n{}", dbgid);
+        let test_duration = TestDuration::new(&dbgid, Duration::from_secs(1));
+        test_duration.run().unwrap();
+        fn to_bytes(data: &[u8], id: u32) -> Vec<u8> {
+            [
+                FieldSyn::default().0.to_be_bytes().as_slice(),
+                FieldId(id).to_be_bytes().as_slice(),
+                MessageKind::Bytes.to_bytes(),
+                (data.len() as u32).to_be_bytes().as_slice(),
+                data,
+            ].concat()
+        }
+        let mut message = ParseData::new(
+            &dbgid,
+            ParseSize::new(
+                &dbgid,
+                FieldSize(4),
+                ParseKind::new(
+                    &dbgid,
+                    FieldKind(MessageKind::Any),
+                    ParseId::new(
+                        &dbgid,
+                        FieldId(4),
+                        ParseSyn::new(
+                            &dbgid,
+                            FieldSyn::default(),
+                        ),
+                    ),
+                ),
+            ),
+        );
+        let large: Vec<u8> = (0..200).collect();
+        let test_data = [
+            (00, 64, to_bytes(&[1, 2, 3], 1), vec![(FieldId(1), vec![1, 2, 3])]),
+            (01, 8, to_bytes(&large, 2)[..8].to_vec(), vec![]),
+            (02, 256, to_bytes(&large, 2)[8..].to_vec(), vec![(FieldId(2), large.clone())]),
+            (03, 64, [to_bytes(&[4], 3), to_bytes(&[5, 6], 4)].concat(), vec![(FieldId(3), vec![4]), (FieldId(4), vec![5, 6])]),
+            (04, 64, vec![], vec![]),
+        ];
+        for (step, len, bytes, target) in test_data {
+            let mut input = bytes.as_slice();
+            let read = message.read_from(len, &mut |buf| std::io::Read::read(&mut input, buf)).unwrap();
+            assert!(read == bytes.len(), "step: {} \nresult: {:?}\ntarget: {:?}", step, read, bytes.len());
+            let result: Vec<(FieldId, Vec<u8>)> = message.parse_all(&[]).into_iter()
+                .map(|result| result.map(|(id, _, _, bytes)| (id, bytes)).unwrap())
+                .collect();
+            log::debug!("{} | step: {},  result: {:?}", dbgid, step, result);
+            assert!(result == target, "step: {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+        test_duration.exit();
+    }
 }
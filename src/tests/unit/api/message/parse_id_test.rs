@@ -77,7 +77,7 @@ mod parse_id {
         for (step, messages, target_id, target_bytes) in test_data {
             let mut result_bytes = vec![];
             for bytes in messages {
-                match message.parse(bytes) {
+                match message.parse(&bytes) {
                     Ok((id, bytes)) => {
                         log::debug!("{} | step: {},  id: {:?},  bytes: {:?}", dbgid, step, id, bytes);
                         let result = id;
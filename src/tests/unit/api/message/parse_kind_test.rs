@@ -81,7 +81,7 @@ mod parse_kind {
         for (step, messages, target_id, target_kind, target_bytes) in test_data {
             let mut result_bytes = vec![];
             for bytes in messages {
-                match message.parse(bytes) {
+                match message.parse(&bytes) {
                     Ok((id, kind, bytes)) => {
                         log::debug!("{} | step: {},  id: {:?},  kind: {:?},  bytes: {:?}", dbgid, step, id, kind, bytes);
                         let result = id;
@@ -105,7 +105,7 @@ mod parse_size {
         for (step, messages, target_id, target_kind, target_size, target_bytes) in test_data {
             let mut result_bytes = vec![];
             for bytes in messages {
-                match message.parse(bytes) {
+                match message.parse(&bytes) {
                     Ok((id, kind, size, bytes)) => {
                         log::debug!("{} | step: {},  id: {:?},  kind: {:?},  size: {:?},  bytes: {:?}", dbgid, step, id, kind, size, bytes);
                         let result = id;
@@ -73,7 +73,7 @@ mod parse_syn {
         for (step, messages, target) in test_data {
             let mut result = vec![];
             for bytes in messages {
-                match message.parse(bytes) {
+                match message.parse(&bytes) {
                     Ok(bytes) => {
                         log::debug!("{} | step: {},  bytes: {:?}", dbgid, step, bytes);
                         // assert!(result == target, "step: {} \nresult: {:?}\ntarget: {:?}", step, result, target);
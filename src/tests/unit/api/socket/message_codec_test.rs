@@ -0,0 +1,83 @@
+#[cfg(all(test, feature = "tokio"))]
+
+mod message_codec {
+    use std::{sync::Once, time::Duration};
+    use bytes::BytesMut;
+    use tokio_util::codec::{Decoder, Encoder};
+    use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
+    use testing::stuff::max_test_duration::TestDuration;
+    use crate::{
+        api::{
+            message::{
+                fields::{FieldData, FieldId, FieldKind, FieldSize, FieldSyn},
+                message::MessageField, message_kind::MessageKind, message_schema::MessageSchema, msg_kind::MsgKind,
+            },
+            socket::message_codec::MessageCodec,
+        },
+        debug::dbg_id::DbgId,
+    };
+    ///
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+            // implement your initialisation code to be called only once for current test file
+        })
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Testing [MessageCodec] encodes & decodes messages split into small chunks
+    #[test]
+    fn encode_decode() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbgid = DbgId("test MessageCodec".into());
+        log::debug!("\n{}", dbgid);
+        let test_duration = TestDuration::new(&dbgid, Duration::from_secs(1));
+        test_duration.run().unwrap();
+        let schema = MessageSchema::new(
+            &dbgid,
+            vec![
+                MessageField::Syn(FieldSyn::default()),
+                MessageField::Id(FieldId(4)),
+                MessageField::Kind(FieldKind(MessageKind::Bytes)),
+                MessageField::Size(FieldSize(4)),
+                MessageField::Data(FieldData(vec![])),
+            ],
+        ).unwrap();
+        let test_data = vec![
+            (FieldId(1), MsgKind::Bytes(vec![1, 2, 3])),
+            (FieldId(2), MsgKind::String("Hello".to_owned())),
+            (FieldId(3), MsgKind::Empty),
+            (FieldId(4), MsgKind::I64(-1234567890)),
+        ];
+        let mut encoder = MessageCodec::new(&dbgid, schema.message());
+        let mut bytes = BytesMut::new();
+        for (step, (id, value)) in test_data.iter().enumerate() {
+            // junk between the messages must be skipped
+            if step == 2 {
+                bytes.extend_from_slice(&[1, 2, 3]);
+            }
+            encoder.encode((id.clone(), value.clone()), &mut bytes).unwrap();
+        }
+        let mut decoder = MessageCodec::new(&dbgid, schema.message());
+        let mut src = BytesMut::new();
+        let mut result = vec![];
+        for chunk in bytes.chunks(3) {
+            src.extend_from_slice(chunk);
+            while let Some(item) = decoder.decode(&mut src).unwrap() {
+                result.push(item);
+            }
+        }
+        let result_eof = decoder.decode_eof(&mut src).unwrap();
+        assert!(result_eof.is_none(), "\nresult: {:?}\ntarget: None", result_eof);
+        assert!(result == test_data, "\nresult: {:?}\ntarget: {:?}", result, test_data);
+        test_duration.exit();
+    }
+}
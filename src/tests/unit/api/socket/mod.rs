@@ -1,3 +1,8 @@
+mod message_codec_test;
+mod reconnect_test;
 mod socket_close_test;
 mod socket_timeout_test;
 mod tcp_socket_test;
+mod timeouts_test;
+mod tls_transport_test;
+mod transport_test;
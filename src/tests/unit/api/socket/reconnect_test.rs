@@ -0,0 +1,59 @@
+#[cfg(test)]
+
+mod reconnect {
+    use std::{sync::Once, time::Duration};
+    use testing::stuff::max_test_duration::TestDuration;
+    use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
+    use crate::{api::socket::reconnect::ReconnectPolicy, debug::dbg_id::DbgId};
+    ///
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+            // implement your initialisation code to be called only once for current test file
+        })
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Testing [ReconnectPolicy.delay]
+    #[test]
+    fn delay() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        log::debug!("");
+        let dbgid = DbgId("test".to_owned());
+        log::debug!("\n{}", dbgid);
+        let test_duration = TestDuration::new(&dbgid, Duration::from_secs(1));
+        test_duration.run().unwrap();
+        let ms = Duration::from_millis;
+        let test_data = [
+            (00, ReconnectPolicy::fixed(ms(100)), vec![Some(ms(100)), Some(ms(100)), Some(ms(100))]),
+            (01, ReconnectPolicy::fixed(ms(100)).with_max_attempts(2), vec![Some(ms(100)), None, None]),
+            (02, ReconnectPolicy::exponential(ms(10), ms(1000)), vec![Some(ms(10)), Some(ms(20)), Some(ms(40)), Some(ms(80))]),
+            (03, ReconnectPolicy::exponential(ms(10), ms(30)), vec![Some(ms(10)), Some(ms(20)), Some(ms(30)), Some(ms(30))]),
+            (04, ReconnectPolicy::exponential(ms(10), ms(30)).with_max_attempts(1), vec![None]),
+        ];
+        for (step, policy, target) in test_data {
+            let result: Vec<Option<Duration>> = (1..=target.len() as u32).map(|attempt| policy.delay(attempt)).collect();
+            log::debug!("{} | step: {},  result: {:?}", dbgid, step, result);
+            assert!(result == target, "step: {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+        // Huge attempt number doesn't overflow
+        let result = ReconnectPolicy::exponential(ms(10), ms(1000)).delay(u32::MAX);
+        assert!(result == Some(ms(1000)), "\nresult: {:?}\ntarget: {:?}", result, Some(ms(1000)));
+        // Jitter stays in the range
+        let policy = ReconnectPolicy::fixed(ms(100)).with_jitter(0.5);
+        let delays: Vec<Duration> = (1..=100).filter_map(|attempt| policy.delay(attempt)).collect();
+        for delay in &delays {
+            assert!(*delay >= ms(50) && *delay <= ms(100), "\nresult: {:?}\ntarget: 50..=100 ms", delay);
+        }
+        assert!(delays.iter().any(|delay| *delay != delays[0]), "\nresult: {:?}\ntarget: randomized", delays);
+        test_duration.exit();
+    }
+}
@@ -1,17 +1,18 @@
 #[cfg(test)]
 
 mod tcp_socket {
-    use std::{io::{Read, Write}, net::TcpListener, sync::{atomic::{AtomicBool, AtomicUsize, Ordering}, Arc, Once}, thread, time::Duration};
+    use chrono::DateTime;
+    use std::{io::{Read, Write}, net::{SocketAddr, TcpListener}, sync::{atomic::{AtomicBool, AtomicUsize, Ordering}, mpsc, Arc, Once}, thread, time::{Duration, Instant}};
     use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
     use testing::{session::{teardown::Teardown, test_session::TestSession}, stuff::max_test_duration::TestDuration};
     use crate::{
         api::{
             message::{
-                fields::{FieldData, FieldId, FieldKind, FieldSize, FieldSyn},
-                message::MessageField, message_kind::MessageKind, msg_kind::MsgKind,
+                fields::{FieldData, FieldId, FieldKind, FieldSize, FieldSyn}, fragment::Fragment,
+                message::{MessageField, MessageParse}, message_kind::MessageKind, message_schema::MessageSchema, msg_kind::MsgKind,
                 parse_data::ParseData, parse_id::ParseId, parse_kind::ParseKind, parse_size::ParseSize, parse_syn::ParseSyn,
             },
-            socket::tcp_socket::{TcpMessage, TcpSocket},
+            socket::{handshake::Handshake, heartbeat::Heartbeat, memory_transport::MemoryEndpoint, reconnect::{ConnectEvent, ReconnectPolicy}, tcp_socket::{IoOutcome, TcpMessage, TcpSocket}},
         },
         debug::dbg_id::DbgId, error::str_err::StrErr,
     };
@@ -140,6 +141,848 @@ mod tcp_socket {
         test_duration.exit();
     }
     ///
+    /// Testing TcpSocket messaging of the mixed kinds
+    #[test]
+    fn send_kind() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        println!("");
+        let dbgid = DbgId("test TcpSocket.send_kind".into());
+        println!("{}", dbgid);
+        let test_duration = TestDuration::new(&dbgid, Duration::from_secs(20));
+        test_duration.run().unwrap();
+        let port = TestSession::free_tcp_port_str();
+        let addr = format!("0.0.0.0:{}", port);
+        let test_data = [
+            (00, MsgKind::Bytes(vec![49, 50, 51])),
+            (01, MsgKind::String("23456".to_owned())),
+            (02, MsgKind::U64(1234567890123)),
+            (03, MsgKind::F64(-0.5)),
+            (04, MsgKind::Bool(false)),
+            (05, MsgKind::Timestamp(DateTime::from_timestamp_micros(1_700_000_000_000_001).unwrap())),
+            (06, MsgKind::Empty),
+        ];
+        let mut socket = TcpSocket::new(
+            &dbgid,
+            &addr,
+            TcpMessage::new(
+                &dbgid,
+                vec![
+                    MessageField::Syn(FieldSyn::default()),
+                    MessageField::Id(FieldId(4)),
+                    MessageField::Kind(FieldKind(MessageKind::Bytes)),
+                    MessageField::Size(FieldSize(4)),
+                    MessageField::Data(FieldData(vec![]))
+                ],
+                ParseData::new(
+                    &dbgid,
+                    ParseSize::new(
+                        &dbgid,
+                        FieldSize(4),
+                        ParseKind::new(
+                            &dbgid,
+                            FieldKind(MessageKind::Bytes),
+                            ParseId::new(
+                                &dbgid,
+                                FieldId(4),
+                                ParseSyn::new(
+                                    &dbgid,
+                                    FieldSyn::default(),
+                                ),
+                            ),
+                        ),
+                    ),
+                ),
+            ),
+            None,
+        );
+        let exit = Arc::new(AtomicBool::new(false));
+        server(&addr, exit.clone());
+        thread::sleep(Duration::from_secs(1));
+        for (step, target) in test_data {
+            let target_id = socket.send_kind(&target, None).unwrap();
+            log::debug!("{} | step {}  Sent | id: {:?}", dbgid, step, target_id);
+            let (id, result) = socket.read().unwrap();
+            log::debug!("{} | step {} Recv | id: {:?} kind: {:?}", dbgid, step, id, result);
+            assert!(id == target_id, "step: {} \nresult: {:?}\ntarget: {:?}", step, id, target_id);
+            assert!(result == target, "step: {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+        exit.store(true, Ordering::SeqCst);
+        test_duration.exit();
+    }
+    ///
+    /// Testing TcpSocket.read decodes every kind of the message
+    #[test]
+    fn read_kinds() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        println!("");
+        let dbgid = DbgId("test TcpSocket.read_kinds".into());
+        println!("{}", dbgid);
+        let test_duration = TestDuration::new(&dbgid, Duration::from_secs(20));
+        test_duration.run().unwrap();
+        let port = TestSession::free_tcp_port_str();
+        let addr = format!("0.0.0.0:{}", port);
+        let test_data = [
+            (00, Ok(MsgKind::Any(vec![1, 2, 3]))),
+            (01, Ok(MsgKind::Empty)),
+            (02, Ok(MsgKind::Bytes(vec![4, 5, 6]))),
+            (03, Ok(MsgKind::Bool(true))),
+            (04, Ok(MsgKind::U16(u16::MAX))),
+            (05, Ok(MsgKind::U32(u32::MAX - 1))),
+            (06, Ok(MsgKind::U64(u64::MAX - 2))),
+            (07, Ok(MsgKind::I16(i16::MIN))),
+            (08, Ok(MsgKind::I32(-123456))),
+            (09, Ok(MsgKind::I64(i64::MIN + 1))),
+            (10, Ok(MsgKind::F32(-1.5))),
+            (11, Ok(MsgKind::F64(12345.6789))),
+            (12, Ok(MsgKind::String("Hello, World!".to_owned()))),
+            (13, Ok(MsgKind::Timestamp(DateTime::from_timestamp_micros(1_700_000_000_123_456).unwrap()))),
+            (14, Ok(MsgKind::Duration(Duration::from_micros(1_500_000)))),
+            (15, Err((MessageKind::F64, vec![0, 0, 0, 0]))),
+            (16, Err((MessageKind::Bool, vec![2]))),
+            (17, Err((MessageKind::String, vec![0xff, 0xfe]))),
+            (18, Err((MessageKind::Empty, vec![0]))),
+        ];
+        let frames: Vec<Vec<u8>> = test_data.iter().enumerate().map(|(id, (_, data))| {
+            let (kind, bytes) = match data {
+                Ok(value) => (value.kind(), value.to_be_bytes()),
+                Err((kind, bytes)) => (kind.clone(), bytes.clone()),
+            };
+            [
+                vec![FieldSyn::default().0],
+                FieldId(id as u32).to_be_bytes().to_vec(),
+                kind.to_bytes().to_vec(),
+                (bytes.len() as u32).to_be_bytes().to_vec(),
+                bytes,
+            ].concat()
+        }).collect();
+        let mut socket = TcpSocket::new(
+            &dbgid,
+            &addr,
+            TcpMessage::new(
+                &dbgid,
+                vec![
+                    MessageField::Syn(FieldSyn::default()),
+                    MessageField::Id(FieldId(4)),
+                    MessageField::Kind(FieldKind(MessageKind::Bytes)),
+                    MessageField::Size(FieldSize(4)),
+                    MessageField::Data(FieldData(vec![]))
+                ],
+                ParseData::new(
+                    &dbgid,
+                    ParseSize::new(
+                        &dbgid,
+                        FieldSize(4),
+                        ParseKind::new(
+                            &dbgid,
+                            FieldKind(MessageKind::Bytes),
+                            ParseId::new(
+                                &dbgid,
+                                FieldId(4),
+                                ParseSyn::new(
+                                    &dbgid,
+                                    FieldSyn::default(),
+                                ),
+                            ),
+                        ),
+                    ),
+                ),
+            ),
+            None,
+        );
+        let exit = Arc::new(AtomicBool::new(false));
+        writer(&addr, frames, exit.clone());
+        thread::sleep(Duration::from_secs(1));
+        for (id, (step, target)) in test_data.into_iter().enumerate() {
+            // Each frame is written separately, one frame per read
+            let result = socket.read();
+            log::debug!("{} | step {} Recv | result: {:?}", dbgid, step, result);
+            match target {
+                Ok(target) => {
+                    let (result_id, result) = result.unwrap();
+                    assert!(result_id == FieldId(id as u32), "step: {} \nresult: {:?}\ntarget: {:?}", step, result_id, FieldId(id as u32));
+                    assert!(result == target, "step: {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+                }
+                Err(_) => {
+                    assert!(result.is_err(), "step: {} \nresult: {:?}\ntarget: Err", step, result);
+                }
+            }
+        }
+        exit.store(true, Ordering::SeqCst);
+        test_duration.exit();
+    }
+    ///
+    /// Testing [TcpSocket.read] returns all messages received by the single read from the socket
+    #[test]
+    fn read_buffered() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        println!("");
+        let dbgid = DbgId("test TcpSocket.read_buffered".into());
+        println!("{}", dbgid);
+        let test_duration = TestDuration::new(&dbgid, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let port = TestSession::free_tcp_port_str();
+        let addr = format!("0.0.0.0:{}", port);
+        let test_data = [
+            (00, MsgKind::U16(123)),
+            (01, MsgKind::String("Hello".to_owned())),
+            (02, MsgKind::Bytes(vec![4, 5, 6])),
+            (03, MsgKind::Bool(false)),
+        ];
+        let mut builder = TcpMessage::new(
+            &dbgid,
+            vec![
+                MessageField::Syn(FieldSyn::default()),
+                MessageField::Id(FieldId(4)),
+                MessageField::Kind(FieldKind(MessageKind::Bytes)),
+                MessageField::Size(FieldSize(4)),
+                MessageField::Data(FieldData(vec![]))
+            ],
+            ParseData::new(
+                &dbgid,
+                ParseSize::new(
+                    &dbgid,
+                    FieldSize(4),
+                    ParseKind::new(
+                        &dbgid,
+                        FieldKind(MessageKind::Bytes),
+                        ParseId::new(
+                            &dbgid,
+                            FieldId(4),
+                            ParseSyn::new(
+                                &dbgid,
+                                FieldSyn::default(),
+                            ),
+                        ),
+                    ),
+                ),
+            ),
+        );
+        // All frames are written at once
+        let frames = vec![
+            test_data.iter().map(|(step, value)| builder.build_kind(value, *step).unwrap()).collect::<Vec<_>>().concat(),
+        ];
+        let mut socket = TcpSocket::new(&dbgid, &addr, builder, None);
+        let exit = Arc::new(AtomicBool::new(false));
+        writer(&addr, frames, exit.clone());
+        thread::sleep(Duration::from_secs(1));
+        let time = Instant::now();
+        for (step, target) in test_data {
+            let result = socket.read();
+            log::debug!("{} | step {} Recv | result: {:?}", dbgid, step, result);
+            let (result_id, result) = result.unwrap();
+            assert!(result_id == FieldId(step), "step: {} \nresult: {:?}\ntarget: {:?}", step, result_id, FieldId(step));
+            assert!(result == target, "step: {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+        let elapsed = time.elapsed();
+        assert!(elapsed < Duration::from_secs(1), "\nresult: {:?}\ntarget: < {:?}", elapsed, Duration::from_secs(1));
+        exit.store(true, Ordering::SeqCst);
+        test_duration.exit();
+    }
+    ///
+    /// Testing [TcpSocket] handshake on connect
+    #[test]
+    fn handshake() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        println!("");
+        let dbgid = DbgId("test TcpSocket.handshake".into());
+        println!("{}", dbgid);
+        let test_duration = TestDuration::new(&dbgid, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let port = TestSession::free_tcp_port_str();
+        let addr = format!("0.0.0.0:{}", port);
+        let schema = MessageSchema::new(
+            &dbgid,
+            vec![
+                MessageField::Syn(FieldSyn::default()),
+                MessageField::Id(FieldId(4)),
+                MessageField::Kind(FieldKind(MessageKind::Bytes)),
+                MessageField::Size(FieldSize(4)),
+                MessageField::Data(FieldData(vec![])),
+            ],
+        ).unwrap();
+        let listener = TcpListener::bind(&addr).unwrap();
+        let server = {
+            let dbgid = DbgId("Server".to_owned());
+            let addr = addr.clone();
+            let schema = schema.clone();
+            thread::spawn(move || {
+                let message = schema.message();
+                let (stream, _) = listener.accept().unwrap();
+                let mut socket = TcpSocket::new(&dbgid, &addr, message, Some(Arc::new(stream)));
+                let negotiated = socket.answer_handshake(Handshake::new().with_max_size(1024)).unwrap();
+                let value = socket.read().unwrap();
+                socket.send_kind(&value.1, Some(value.0.0)).unwrap();
+                negotiated
+            })
+        };
+        let kinds = vec![MessageKind::Bytes, MessageKind::String, MessageKind::Struct];
+        let mut socket = TcpSocket::new(&dbgid, &addr, schema.message(), None)
+            .with_handshake(Handshake::new().with_kinds(kinds.clone()));
+        socket.connect().unwrap();
+        let target = Handshake { version: Handshake::VERSION, kinds, max_size: 1024 };
+        let result = socket.negotiated().cloned();
+        assert!(result == Some(target.clone()), "\nresult: {:?}\ntarget: {:?}", result, target);
+        let result = socket.send_kind(&MsgKind::U16(1), None);
+        assert!(result.is_err(), "\nresult: {:?}\ntarget: Err", result);
+        let value = MsgKind::String("Hello".to_owned());
+        let id = socket.send_kind(&value, None).unwrap();
+        let result = socket.read().unwrap();
+        assert!(result == (id.clone(), value.clone()), "\nresult: {:?}\ntarget: {:?}", result, (id, value));
+        let result = server.join().unwrap();
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        test_duration.exit();
+    }
+    ///
+    /// Testing [TcpSocket] rejects the messages of the `Kind` or the `Size` not accepted by the peer
+    #[test]
+    fn handshake_limits() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        println!("");
+        let dbgid = DbgId("test TcpSocket.handshake_limits".into());
+        println!("{}", dbgid);
+        let test_duration = TestDuration::new(&dbgid, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let port = TestSession::free_tcp_port_str();
+        let addr = format!("0.0.0.0:{}", port);
+        let schema = MessageSchema::new(
+            &dbgid,
+            vec![
+                MessageField::Syn(FieldSyn::default()),
+                MessageField::Id(FieldId(4)),
+                MessageField::Kind(FieldKind(MessageKind::Bytes)),
+                MessageField::Size(FieldSize(4)),
+                MessageField::Data(FieldData(vec![])),
+            ],
+        ).unwrap();
+        // MsgKind::Bytes is sent by `send` with the configured `Kind`, other values by `send_kind`
+        let test_data = [
+            (vec![MessageKind::String, MessageKind::Struct], vec![
+                (01, MsgKind::Bytes(vec![0; 8]), false),
+                (02, MsgKind::String("a".repeat(16)), true),
+                (03, MsgKind::String("a".repeat(17)), false),
+                (04, MsgKind::U32(1), false),
+            ]),
+            (Handshake::new().kinds, vec![
+                (05, MsgKind::Bytes(vec![0; 16]), true),
+                (06, MsgKind::Bytes(vec![0; 17]), false),
+                (07, MsgKind::U32(1), true),
+                (08, MsgKind::Bytes(vec![]), true),
+            ]),
+        ];
+        let listener = TcpListener::bind(&addr).unwrap();
+        let server = {
+            let dbgid = DbgId("Server".to_owned());
+            let addr = addr.clone();
+            let schema = schema.clone();
+            let connections = test_data.len();
+            thread::spawn(move || {
+                let mut received = vec![];
+                for _ in 0..connections {
+                    let (stream, _) = listener.accept().unwrap();
+                    let mut socket = TcpSocket::new(&dbgid, &addr, schema.message(), Some(Arc::new(stream)));
+                    socket.answer_handshake(Handshake::new().with_max_size(16)).unwrap();
+                    let mut values = vec![];
+                    while let Ok((_, value)) = socket.read() {
+                        values.push(value);
+                    }
+                    received.push(values);
+                }
+                received
+            })
+        };
+        let mut target = vec![];
+        for (kinds, steps) in test_data {
+            let mut socket = TcpSocket::new(&dbgid, &addr, schema.message(), None)
+                .with_handshake(Handshake::new().with_kinds(kinds));
+            let mut sent = vec![];
+            for (step, value, ok) in steps {
+                let result = match &value {
+                    MsgKind::Bytes(bytes) => socket.send(bytes, None),
+                    _ => socket.send_kind(&value, None),
+                };
+                assert!(result.is_ok() == ok, "step: {} \nresult: {:?}\ntarget: {}", step, result, if ok {"Ok"} else {"Err"});
+                if ok {
+                    sent.push(value);
+                }
+            }
+            socket.close().unwrap();
+            target.push(sent);
+        }
+        let result = server.join().unwrap();
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        test_duration.exit();
+    }
+    ///
+    /// Testing [TcpSocket] heartbeat keeps the idle connection and detects the dead peer
+    #[test]
+    fn heartbeat() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        println!("");
+        let dbgid = DbgId("test TcpSocket.heartbeat".into());
+        println!("{}", dbgid);
+        let test_duration = TestDuration::new(&dbgid, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let schema = MessageSchema::new(
+            &dbgid,
+            vec![
+                MessageField::Syn(FieldSyn::default()),
+                MessageField::Id(FieldId(4)),
+                MessageField::Kind(FieldKind(MessageKind::Bytes)),
+                MessageField::Size(FieldSize(4)),
+                MessageField::Data(FieldData(vec![])),
+            ],
+        ).unwrap();
+        let heartbeat = Heartbeat::new(Duration::from_millis(100), Duration::from_millis(200));
+        // Peer answers the pings, then sends the message
+        let port = TestSession::free_tcp_port_str();
+        let addr = format!("0.0.0.0:{}", port);
+        let listener = TcpListener::bind(&addr).unwrap();
+        let target = MsgKind::String("Hello".to_owned());
+        let server = {
+            let schema = schema.clone();
+            let target = target.clone();
+            thread::spawn(move || {
+                let mut message = schema.message();
+                let (mut stream, _) = listener.accept().unwrap();
+                stream.set_read_timeout(Some(Duration::from_millis(50))).unwrap();
+                let time = Instant::now();
+                let mut pings = 0;
+                let mut buf = vec![0; 4096];
+                while time.elapsed() < Duration::from_millis(700) {
+                    if let Ok(len) = stream.read(&mut buf) {
+                        for (id, kind, _, _) in message.parse_all(&buf[..len]).into_iter().flatten() {
+                            if Heartbeat::is_ping(&id, &kind) {
+                                pings += 1;
+                                stream.write_all(&message.build_kind(&MsgKind::Empty, Heartbeat::PONG.0).unwrap()).unwrap();
+                            }
+                        }
+                    }
+                }
+                stream.write_all(&message.build_kind(&target, 1).unwrap()).unwrap();
+                thread::sleep(Duration::from_millis(100));
+                pings
+            })
+        };
+        let mut socket = TcpSocket::new(&dbgid, &addr, schema.message(), None).with_heartbeat(heartbeat.clone());
+        let result = socket.read().unwrap();
+        assert!(result == (FieldId(1), target.clone()), "\nresult: {:?}\ntarget: {:?}", result, (FieldId(1), target));
+        let pings = server.join().unwrap();
+        assert!(pings >= 2, "\nresult: {:?}\ntarget: >= 2", pings);
+        // Peer never answers
+        let port = TestSession::free_tcp_port_str();
+        let addr = format!("0.0.0.0:{}", port);
+        let exit = Arc::new(AtomicBool::new(false));
+        writer(&addr, vec![], exit.clone());
+        thread::sleep(Duration::from_millis(100));
+        let mut socket = TcpSocket::new(&dbgid, &addr, schema.message(), None).with_heartbeat(heartbeat);
+        let time = Instant::now();
+        let result = socket.read();
+        let elapsed = time.elapsed();
+        assert!(result.is_err(), "\nresult: {:?}\ntarget: Err", result);
+        assert!(elapsed < Duration::from_secs(2), "\nresult: {:?}\ntarget: < {:?}", elapsed, Duration::from_secs(2));
+        exit.store(true, Ordering::SeqCst);
+        test_duration.exit();
+    }
+    ///
+    /// Testing [TcpSocket] heartbeat keeps the connection used for the sending only
+    #[test]
+    fn heartbeat_send_only() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        println!("");
+        let dbgid = DbgId("test TcpSocket.heartbeat_send_only".into());
+        println!("{}", dbgid);
+        let test_duration = TestDuration::new(&dbgid, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let schema = MessageSchema::new(
+            &dbgid,
+            vec![
+                MessageField::Syn(FieldSyn::default()),
+                MessageField::Id(FieldId(4)),
+                MessageField::Kind(FieldKind(MessageKind::Bytes)),
+                MessageField::Size(FieldSize(4)),
+                MessageField::Data(FieldData(vec![])),
+            ],
+        ).unwrap();
+        let heartbeat = Heartbeat::new(Duration::from_millis(100), Duration::from_millis(200));
+        // Peer answers the pings & counts the messages until the connection closed
+        let port = TestSession::free_tcp_port_str();
+        let addr = format!("0.0.0.0:{}", port);
+        let listener = TcpListener::bind(&addr).unwrap();
+        let server = {
+            let schema = schema.clone();
+            thread::spawn(move || {
+                let mut message = schema.message();
+                let (mut stream, _) = listener.accept().unwrap();
+                stream.set_read_timeout(Some(Duration::from_millis(50))).unwrap();
+                let mut pings = 0;
+                let mut received = 0;
+                let mut buf = vec![0; 4096];
+                loop {
+                    match stream.read(&mut buf) {
+                        Ok(0) => break,
+                        Ok(len) => for (id, kind, _, _) in message.parse_all(&buf[..len]).into_iter().flatten() {
+                            if Heartbeat::is_ping(&id, &kind) {
+                                pings += 1;
+                                stream.write_all(&message.build_kind(&MsgKind::Empty, Heartbeat::PONG.0).unwrap()).unwrap();
+                            } else {
+                                received += 1;
+                            }
+                        }
+                        Err(err) if IoOutcome::from(err.kind()) == IoOutcome::Timeout => {}
+                        Err(_) => break,
+                    }
+                }
+                (pings, received)
+            })
+        };
+        let (events, events_recv) = mpsc::channel();
+        let mut socket = TcpSocket::new(&dbgid, &addr, schema.message(), None)
+            .with_heartbeat(heartbeat)
+            .with_events(events);
+        let count = 20;
+        for step in 0..count {
+            let result = socket.send_kind(&MsgKind::U32(step), None);
+            assert!(result.is_ok(), "step: {} \nresult: {:?}\ntarget: Ok", step, result);
+            thread::sleep(Duration::from_millis(50));
+        }
+        socket.close().unwrap();
+        let (pings, received) = server.join().unwrap();
+        assert!(pings >= 2, "\nresult: {:?}\ntarget: >= 2", pings);
+        assert!(received == count, "\nresult: {:?}\ntarget: {:?}", received, count);
+        let lost: Vec<ConnectEvent> = events_recv.try_iter().filter(|event| matches!(event, ConnectEvent::Lost { .. })).collect();
+        assert!(lost.is_empty(), "\nresult: {:?}\ntarget: []", lost);
+        test_duration.exit();
+    }
+    ///
+    /// Testing [TcpSocket.connect] retries according to the [ReconnectPolicy] and emits [ConnectEvent]'s
+    #[test]
+    fn reconnect() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        println!("");
+        let dbgid = DbgId("test TcpSocket.reconnect".into());
+        println!("{}", dbgid);
+        let test_duration = TestDuration::new(&dbgid, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let port = TestSession::free_tcp_port_str();
+        let addr = format!("127.0.0.1:{}", port);
+        let socket_addr: SocketAddr = addr.parse().unwrap();
+        let message = || MessageSchema::new(
+            &dbgid,
+            vec![
+                MessageField::Syn(FieldSyn::default()),
+                MessageField::Id(FieldId(4)),
+                MessageField::Kind(FieldKind(MessageKind::Bytes)),
+                MessageField::Size(FieldSize(4)),
+                MessageField::Data(FieldData(vec![])),
+            ],
+        ).unwrap().message();
+        // Maximum attempts reached, nobody listening
+        let (send, recv) = mpsc::channel();
+        let mut socket = TcpSocket::new(&dbgid, &addr, message(), None)
+            .with_reconnect(ReconnectPolicy::fixed(Duration::from_millis(50)).with_max_attempts(3))
+            .with_events(send);
+        let time = Instant::now();
+        let result = socket.connect();
+        let elapsed = time.elapsed();
+        assert!(result.is_err(), "\nresult: {:?}\ntarget: Err", result);
+        assert!(elapsed >= Duration::from_millis(100), "\nresult: {:?}\ntarget: >= 100 ms", elapsed);
+        let result: Vec<ConnectEvent> = recv.try_iter().collect();
+        let target: Vec<ConnectEvent> = (1..=3).map(|attempt| ConnectEvent::Connecting { endpoint: socket_addr.to_string(), attempt }).collect();
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        // Server started later, then closes the connection
+        let (send, recv) = mpsc::channel();
+        let mut socket = TcpSocket::new(&dbgid, &addr, message(), None)
+            .with_reconnect(ReconnectPolicy::fixed(Duration::from_millis(50)))
+            .with_events(send);
+        let server = {
+            let addr = addr.clone();
+            thread::spawn(move || {
+                thread::sleep(Duration::from_millis(300));
+                let listener = TcpListener::bind(addr).unwrap();
+                let (stream, _) = listener.accept().unwrap();
+                thread::sleep(Duration::from_millis(100));
+                drop(stream);
+            })
+        };
+        socket.connect().unwrap();
+        let result = socket.read();
+        assert!(result.is_err(), "\nresult: {:?}\ntarget: Err", result);
+        server.join().unwrap();
+        let result: Vec<ConnectEvent> = recv.try_iter().collect();
+        log::debug!("{} | events: {:?}", dbgid, result);
+        let connecting = result.iter().filter(|event| matches!(event, ConnectEvent::Connecting { .. })).count();
+        assert!(connecting > 1, "\nresult: {:?}\ntarget: > 1", connecting);
+        let result: Vec<&ConnectEvent> = result.iter().filter(|event| !matches!(event, ConnectEvent::Connecting { .. })).collect();
+        assert!(
+            matches!(result.as_slice(), [ConnectEvent::Connected(_), ConnectEvent::Lost { .. }]),
+            "\nresult: {:?}\ntarget: [Connected, Lost]", result,
+        );
+        test_duration.exit();
+    }
+    ///
+    /// Testing [TcpSocket] pipelined requests, replied out of order
+    #[test]
+    fn pipelining() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        println!("");
+        let dbgid = DbgId("test TcpSocket.pipelining".into());
+        println!("{}", dbgid);
+        let test_duration = TestDuration::new(&dbgid, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let schema = MessageSchema::new(
+            &dbgid,
+            vec![
+                MessageField::Syn(FieldSyn::default()),
+                MessageField::Id(FieldId(4)),
+                MessageField::Kind(FieldKind(MessageKind::Bytes)),
+                MessageField::Size(FieldSize(4)),
+                MessageField::Data(FieldData(vec![])),
+            ],
+        ).unwrap();
+        let (endpoint, accept) = MemoryEndpoint::new("pipelining");
+        let test_data = [
+            MsgKind::String("first".to_owned()),
+            MsgKind::String("second".to_owned()),
+            MsgKind::String("third".to_owned()),
+        ];
+        let count = test_data.len();
+        let server = {
+            let dbgid = DbgId("Server".to_owned());
+            let endpoint = endpoint.clone();
+            let schema = schema.clone();
+            thread::spawn(move || {
+                let transport = accept.recv().unwrap();
+                let mut socket = TcpSocket::from_endpoint(&dbgid, endpoint, schema.message(), Some(Arc::new(transport)));
+                let mut requests = vec![];
+                for _ in 0..count {
+                    requests.push(socket.read().unwrap());
+                }
+                // Replies in reverse order
+                for (id, value) in requests.into_iter().rev() {
+                    socket.send_kind(&value, Some(id.0)).unwrap();
+                }
+                // Reply to nothing
+                socket.send_kind(&MsgKind::String("unknown".to_owned()), Some(12345)).unwrap();
+                socket.read().unwrap();
+            })
+        };
+        let mut socket = TcpSocket::from_endpoint(&dbgid, endpoint, schema.message(), None).with_pipelining();
+        let ids: Vec<FieldId> = test_data.iter().map(|value| socket.send_kind(value, None).unwrap()).collect();
+        assert!(socket.pending() == count, "\nresult: {:?}\ntarget: {:?}", socket.pending(), count);
+        let result = socket.send_kind(&MsgKind::Bool(true), Some(ids[0].0));
+        assert!(result.is_err(), "\nresult: {:?}\ntarget: Err", result);
+        for step in [0, 2, 1] {
+            let result = socket.reply(&ids[step]).unwrap();
+            let target = test_data[step].clone();
+            assert!(result == target, "step {}\nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+        assert!(socket.pending() == 0, "\nresult: {:?}\ntarget: {:?}", socket.pending(), 0);
+        let result = socket.reply(&ids[0]);
+        assert!(result.is_err(), "\nresult: {:?}\ntarget: Err", result);
+        let id = socket.send_kind(&MsgKind::Bool(true), None).unwrap();
+        let result = socket.reply(&id);
+        assert!(result.is_err(), "\nresult: {:?}\ntarget: Err", result);
+        server.join().unwrap();
+        test_duration.exit();
+    }
+    ///
+    /// Testing fragmented requests & replies in the pipelined mode
+    #[test]
+    fn pipelining_fragments() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        println!("");
+        let dbgid = DbgId("test TcpSocket.pipelining_fragments".into());
+        println!("{}", dbgid);
+        let test_duration = TestDuration::new(&dbgid, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let schema = MessageSchema::new(
+            &dbgid,
+            vec![
+                MessageField::Syn(FieldSyn::default()),
+                MessageField::Id(FieldId(4)),
+                MessageField::Kind(FieldKind(MessageKind::Bytes)),
+                MessageField::Size(FieldSize(4)),
+                MessageField::Data(FieldData(vec![])),
+            ],
+        ).unwrap();
+        let fragment = |index, last, bytes: &[u8]| MsgKind::Fragment(Fragment { index, last, kind: MessageKind::Bytes, bytes: bytes.to_vec() });
+        let (endpoint, accept) = MemoryEndpoint::new("pipelining_fragments");
+        let count = 4;
+        let server = {
+            let dbgid = DbgId("Server".to_owned());
+            let endpoint = endpoint.clone();
+            let schema = schema.clone();
+            thread::spawn(move || {
+                let transport = accept.recv().unwrap();
+                let mut socket = TcpSocket::from_endpoint(&dbgid, endpoint, schema.message(), Some(Arc::new(transport)));
+                // Echo as received, fragments of the reply interleaved with the other reply
+                for _ in 0..count {
+                    let (id, value) = socket.read().unwrap();
+                    socket.send_kind(&value, Some(id.0)).unwrap();
+                }
+            })
+        };
+        let mut socket = TcpSocket::from_endpoint(&dbgid, endpoint, schema.message(), None).with_pipelining();
+        let first = [fragment(0, false, &[1, 2]), fragment(1, false, &[3, 4]), fragment(2, true, &[5])];
+        let second = MsgKind::String("second".to_owned());
+        let first_id = socket.send_kind(&first[0], None).unwrap();
+        socket.send_kind(&first[1], Some(first_id.0)).unwrap();
+        let second_id = socket.send_kind(&second, None).unwrap();
+        socket.send_kind(&first[2], Some(first_id.0)).unwrap();
+        assert!(socket.pending() == 2, "\nresult: {:?}\ntarget: {:?}", socket.pending(), 2);
+        // The first fragment of the new request with the pending Id
+        let result = socket.send_kind(&fragment(0, true, &[]), Some(first_id.0));
+        assert!(result.is_err(), "\nresult: {:?}\ntarget: Err", result);
+        // The next fragment of no pending request
+        let result = socket.send_kind(&fragment(1, true, &[]), Some(12345));
+        assert!(result.is_err(), "\nresult: {:?}\ntarget: Err", result);
+        let result = socket.reply(&second_id).unwrap();
+        assert!(result == second, "\nresult: {:?}\ntarget: {:?}", result, second);
+        for target in first {
+            let result = socket.reply(&first_id).unwrap();
+            assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        }
+        assert!(socket.pending() == 0, "\nresult: {:?}\ntarget: {:?}", socket.pending(), 0);
+        server.join().unwrap();
+        test_duration.exit();
+    }
+    ///
+    /// Testing `Id`s of the sent messages & [Heartbeat] fit the 1 byte `Id` field, internal `Id` wraps before the reserved ones
+    #[test]
+    fn narrow_id() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        println!("");
+        let dbgid = DbgId("test TcpSocket.narrow_id".into());
+        println!("{}", dbgid);
+        let test_duration = TestDuration::new(&dbgid, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let schema = MessageSchema::new(
+            &dbgid,
+            vec![
+                MessageField::Syn(FieldSyn::default()),
+                MessageField::Id(FieldId(1)),
+                MessageField::Kind(FieldKind(MessageKind::Bytes)),
+                MessageField::Size(FieldSize(4)),
+                MessageField::Data(FieldData(vec![])),
+            ],
+        ).unwrap();
+        let max_id = u8::MAX as u32;
+        let result = schema.message().max_id();
+        assert!(result == max_id, "\nresult: {:?}\ntarget: {:?}", result, max_id);
+        let (endpoint, accept) = MemoryEndpoint::new("narrow_id");
+        let count = 600;
+        let server = {
+            let dbgid = DbgId("Server".to_owned());
+            let endpoint = endpoint.clone();
+            let schema = schema.clone();
+            thread::spawn(move || {
+                let transport = accept.recv().unwrap();
+                let mut socket = TcpSocket::from_endpoint(&dbgid, endpoint, schema.message(), Some(Arc::new(transport)));
+                // Ping of the 1 byte `Id`, the pong is skipped by the next read
+                socket.send_kind(&MsgKind::Empty, Some(Heartbeat::ping_id(max_id).0)).unwrap();
+                let mut ids = vec![];
+                for _ in 0..count {
+                    let (id, value) = socket.read().unwrap();
+                    ids.push(id.0);
+                    socket.send_kind(&value, Some(id.0)).unwrap();
+                }
+                ids
+            })
+        };
+        let mut socket = TcpSocket::from_endpoint(&dbgid, endpoint, schema.message(), None);
+        for step in 0..count {
+            let target = MsgKind::U32(step);
+            let id = socket.send_kind(&target, None).unwrap();
+            let (reply_id, result) = socket.read().unwrap();
+            assert!(reply_id == id, "step: {} \nresult: {:?}\ntarget: {:?}", step, reply_id, id);
+            assert!(result == target, "step: {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+        let result = server.join().unwrap();
+        let target: Vec<u32> = (0..count).map(|step| step % (Heartbeat::pong_id(max_id).0 - 1) + 1).collect();
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        // `Id` can't be encoded in the 1 byte field
+        let result = socket.send_kind(&MsgKind::Empty, Some(max_id + 1));
+        assert!(result.is_err(), "\nresult: {:?}\ntarget: Err", result);
+        test_duration.exit();
+    }
+    ///
+    /// Testing [IoOutcome] of the IO errors, nothing panics
+    #[test]
+    fn io_outcome() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        println!("");
+        let dbgid = DbgId("test TcpSocket.io_outcome".into());
+        println!("{}", dbgid);
+        let test_duration = TestDuration::new(&dbgid, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let test_data = [
+            (01, std::io::ErrorKind::Interrupted, IoOutcome::Retry),
+            (02, std::io::ErrorKind::WouldBlock, IoOutcome::Timeout),
+            (03, std::io::ErrorKind::TimedOut, IoOutcome::Timeout),
+            (04, std::io::ErrorKind::ConnectionReset, IoOutcome::Closed),
+            (05, std::io::ErrorKind::ConnectionAborted, IoOutcome::Closed),
+            (06, std::io::ErrorKind::NotConnected, IoOutcome::Closed),
+            (07, std::io::ErrorKind::BrokenPipe, IoOutcome::Closed),
+            (08, std::io::ErrorKind::AlreadyExists, IoOutcome::Closed),
+            (09, std::io::ErrorKind::InvalidInput, IoOutcome::Closed),
+            (10, std::io::ErrorKind::InvalidData, IoOutcome::Closed),
+            (11, std::io::ErrorKind::WriteZero, IoOutcome::Closed),
+            (12, std::io::ErrorKind::Unsupported, IoOutcome::Closed),
+            (13, std::io::ErrorKind::UnexpectedEof, IoOutcome::Closed),
+            (14, std::io::ErrorKind::OutOfMemory, IoOutcome::Closed),
+            (15, std::io::ErrorKind::Other, IoOutcome::Closed),
+            (16, std::io::ErrorKind::NotFound, IoOutcome::Closed),
+        ];
+        for (step, kind, target) in test_data {
+            let result = IoOutcome::from(kind);
+            assert!(result == target, "step {} {:?}\nresult: {:?}\ntarget: {:?}", step, kind, result, target);
+        }
+        test_duration.exit();
+    }
+    ///
+    /// Server side, writes prepared `frames` one by one
+    fn writer(addr: &str, frames: Vec<Vec<u8>>, exit: Arc<AtomicBool>) {
+        let dbgid = DbgId("Writer".to_owned());
+        let addr = addr.to_owned();
+        let _ = thread::Builder::new().name(format!("{}.run", &dbgid)).spawn(move || {
+            let listener = TcpListener::bind(addr).unwrap();
+            let (mut stream, addr) = listener.accept().unwrap();
+            log::debug!("{}.run | connection: {:?}", dbgid, addr);
+            for frame in frames {
+                stream.write_all(&frame).unwrap();
+                thread::sleep(Duration::from_millis(50));
+            }
+            while !exit.load(Ordering::SeqCst) {
+                thread::sleep(Duration::from_millis(10));
+            }
+            log::debug!("{}.run | Exit", dbgid);
+        });
+    }
+    ///
     /// Server side
     fn server(addr: &str, exit: Arc<AtomicBool>) {
         let dbgid = DbgId("Server".to_owned());
@@ -0,0 +1,139 @@
+#[cfg(test)]
+
+mod timeouts {
+    use std::{sync::{atomic::AtomicUsize, Arc, Once}, thread, time::{Duration, Instant}};
+    use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
+    use testing::{session::teardown::Teardown, stuff::max_test_duration::TestDuration};
+    use crate::{
+        api::{
+            message::msg_kind::MsgKind,
+            socket::{
+                memory_transport::MemoryEndpoint, reconnect::ReconnectPolicy, socket_err::SocketErr,
+                tcp_socket::TcpSocket, timeouts::Timeouts, transport::Transport,
+            },
+        },
+        client::api_request::message_schema,
+        debug::dbg_id::DbgId,
+    };
+    ///
+    static INIT: Once = Once::new();
+    static TEARDOWN_COUNT: AtomicUsize = AtomicUsize::new(0);
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+                // implement your initialisation code to be called only once for current test file
+        });
+    }
+    ///
+    /// Once called after all tests
+    fn teardown_once() {
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Testing [SocketErr::ConnectTimeout] if the endpoint isn't accepting
+    #[test]
+    fn connect_timeout() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        println!("");
+        let dbgid = DbgId("test Timeouts.connect".into());
+        println!("{}", dbgid);
+        let test_duration = TestDuration::new(&dbgid, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let _teardown_once = || {
+            teardown_once();
+        };
+        let _teardown = Teardown::new(&TEARDOWN_COUNT, &|| {}, &_teardown_once);
+        let (endpoint, accept) = MemoryEndpoint::new("refused");
+        drop(accept);
+        let timeout = Duration::from_millis(300);
+        let mut socket = TcpSocket::from_endpoint(&dbgid, endpoint, message_schema(&dbgid).message(), None)
+            .with_reconnect(ReconnectPolicy::fixed(Duration::from_millis(50)))
+            .with_timeouts(Timeouts::new(Duration::from_secs(3)).with_connect(timeout));
+        let time = Instant::now();
+        let result = socket.send_kind(&MsgKind::U32(1), None);
+        let elapsed = time.elapsed();
+        assert!(matches!(result, Err(SocketErr::ConnectTimeout(t)) if t == timeout), "\nresult: {:?}\ntarget: {:?}", result, SocketErr::ConnectTimeout(timeout));
+        assert!(elapsed >= timeout && elapsed < timeout * 2, "\nresult: {:?}\ntarget: {:?}", elapsed, timeout);
+        test_duration.exit();
+    }
+    ///
+    /// Testing [SocketErr::ReadTimeout] if the peer is silent, connection is kept outside of the request
+    #[test]
+    fn read_timeout() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        println!("");
+        let dbgid = DbgId("test Timeouts.read".into());
+        println!("{}", dbgid);
+        let test_duration = TestDuration::new(&dbgid, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let (endpoint, accept) = MemoryEndpoint::new("silent");
+        let server = thread::spawn(move || {
+            let transport = accept.recv().unwrap();
+            let mut buf = [0; 64];
+            // Reading until the client closed, never replying
+            while transport.read(&mut buf).map_or(false, |len| len > 0) {}
+        });
+        let timeout = Duration::from_millis(200);
+        let mut socket = TcpSocket::from_endpoint(&dbgid, endpoint, message_schema(&dbgid).message(), None)
+            .with_timeouts(Timeouts::new(Duration::from_secs(3)).with_read(timeout));
+        socket.send_kind(&MsgKind::U32(1), None).unwrap();
+        let time = Instant::now();
+        let result = socket.read();
+        let elapsed = time.elapsed();
+        assert!(matches!(result, Err(SocketErr::ReadTimeout(t)) if t == timeout), "\nresult: {:?}\ntarget: {:?}", result, SocketErr::ReadTimeout(timeout));
+        assert!(elapsed >= timeout && elapsed < timeout * 3, "\nresult: {:?}\ntarget: {:?}", elapsed, timeout);
+        let result = socket.send_kind(&MsgKind::U32(2), None);
+        assert!(result.is_ok(), "\nresult: {:?}\ntarget: Ok", result);
+        socket.close().unwrap();
+        server.join().unwrap();
+        test_duration.exit();
+    }
+    ///
+    /// Testing [SocketErr::Deadline] of the request, connection is closed after the request timed out
+    #[test]
+    fn deadline() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        println!("");
+        let dbgid = DbgId("test Timeouts.deadline".into());
+        println!("{}", dbgid);
+        let test_duration = TestDuration::new(&dbgid, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let (endpoint, accept) = MemoryEndpoint::new("slow");
+        let server = thread::spawn(move || {
+            let transport = accept.recv().unwrap();
+            let mut buf = [0; 64];
+            let mut received = 0;
+            while let Ok(len) = transport.read(&mut buf) {
+                if len == 0 {
+                    break;
+                }
+                received += len;
+            }
+            received
+        });
+        let deadline = Duration::from_millis(300);
+        let mut socket = TcpSocket::from_endpoint(&dbgid, endpoint, message_schema(&dbgid).message(), None)
+            .with_timeouts(Timeouts::new(Duration::from_secs(3)).with_deadline(deadline));
+        let time = Instant::now();
+        socket.start_request();
+        socket.send_kind(&MsgKind::U32(1), None).unwrap();
+        let result = socket.read();
+        socket.finish_request();
+        let elapsed = time.elapsed();
+        assert!(matches!(result, Err(SocketErr::Deadline(t)) if t == deadline), "\nresult: {:?}\ntarget: {:?}", result, SocketErr::Deadline(deadline));
+        assert!(elapsed >= deadline && elapsed < deadline * 3, "\nresult: {:?}\ntarget: {:?}", elapsed, deadline);
+        let result = server.join().unwrap();
+        assert!(result > 0, "\nresult: {:?}\ntarget: > 0", result);
+        test_duration.exit();
+    }
+}
@@ -0,0 +1,219 @@
+#[cfg(test)]
+
+mod tls_transport {
+    use std::{net::TcpListener, sync::{atomic::AtomicUsize, Arc, Once}, thread, time::Duration};
+    use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
+    use rcgen::{BasicConstraints, CertificateParams, IsCa, KeyPair};
+    use testing::{session::{teardown::Teardown, test_session::TestSession}, stuff::max_test_duration::TestDuration};
+    use crate::{
+        api::{
+            message::{message_schema::MessageSchema, msg_kind::MsgKind},
+            socket::{
+                tcp_socket::TcpSocket,
+                tls_transport::{TlsConfig, TlsEndpoint, TlsIdentity, TlsServerConfig, TlsVerify},
+                transport::{Endpoint, TcpEndpoint, Transport},
+            },
+        },
+        client::api_request::message_schema,
+        debug::dbg_id::DbgId,
+    };
+    ///    
+    static INIT: Once = Once::new();
+    static TEARDOWN_COUNT: AtomicUsize = AtomicUsize::new(0);
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+                // implement your initialisation code to be called only once for current test file
+        });
+    }
+    ///
+    /// Once called after all tests
+    fn teardown_once() {
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Returns PEM encoded CA certificate & key
+    fn ca_cert() -> (String, KeyPair, rcgen::Certificate) {
+        let key = KeyPair::generate().unwrap();
+        let mut params = CertificateParams::new(Vec::<String>::new()).unwrap();
+        params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+        let cert = params.self_signed(&key).unwrap();
+        (cert.pem(), key, cert)
+    }
+    ///
+    /// Returns PEM encoded certificate & key for the `name`, signed by the CA
+    fn signed(name: &str, ca_key: &KeyPair, ca_cert: &rcgen::Certificate) -> (String, String) {
+        let key = KeyPair::generate().unwrap();
+        let cert = CertificateParams::new(vec![name.to_owned()]).unwrap()
+            .signed_by(&key, ca_cert, ca_key).unwrap();
+        (cert.pem(), key.serialize_pem())
+    }
+    ///
+    /// Returns PEM encoded self signed certificate & key for the `name`
+    fn self_signed(name: &str) -> (String, String) {
+        let certified = rcgen::generate_simple_self_signed(vec![name.to_owned()]).unwrap();
+        (certified.cert.pem(), certified.key_pair.serialize_pem())
+    }
+    ///
+    /// Runs the server echoing `count` messages over TLS
+    fn echo_server(addr: &str, config: TlsServerConfig, schema: MessageSchema, count: usize) -> thread::JoinHandle<Result<(), String>> {
+        let listener = TcpListener::bind(addr).unwrap();
+        let addr = addr.to_owned();
+        thread::spawn(move || {
+            let dbgid = DbgId("Server".to_owned());
+            let (stream, _) = listener.accept().unwrap();
+            let transport = config.accept(stream, Duration::from_secs(5)).map_err(|err| format!("{:?}", err))?;
+            let mut socket = TcpSocket::from_endpoint(&dbgid, TcpEndpoint::new(addr), schema.message(), Some(Arc::new(transport)));
+            for _ in 0..count {
+                let (id, value) = socket.read().map_err(|err| err.to_string())?;
+                socket.send_kind(&value, Some(id.0)).map_err(|err| err.to_string())?;
+            }
+            Ok(())
+        })
+    }
+    ///
+    /// Testing [TcpSocket] messaging over TLS, server verified by the CA
+    #[test]
+    fn ca() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        println!("");
+        let dbgid = DbgId("test TlsTransport.ca".into());
+        println!("{}", dbgid);
+        let test_duration = TestDuration::new(&dbgid, Duration::from_secs(20));
+        test_duration.run().unwrap();
+        let _teardown_once = || {
+            teardown_once();
+        };
+        let _teardown = Teardown::new(&TEARDOWN_COUNT, &|| {}, &_teardown_once);
+        let addr = format!("127.0.0.1:{}", TestSession::free_tcp_port_str());
+        let schema = message_schema(&dbgid);
+        let (ca_pem, ca_key, ca_cert) = ca_cert();
+        let (cert_pem, key_pem) = signed("localhost", &ca_key, &ca_cert);
+        let server_config = TlsServerConfig::new(TlsIdentity::from_pem(cert_pem.as_bytes(), key_pem.as_bytes()).unwrap(), None).unwrap();
+        let test_data = [
+            MsgKind::Bytes(vec![1, 2, 3]),
+            MsgKind::String("SELECT 1;".to_owned()),
+        ];
+        let server = echo_server(&addr, server_config, schema.clone(), test_data.len());
+        let config = TlsConfig::new(TlsVerify::ca(ca_pem.as_bytes()).unwrap(), None).unwrap();
+        let endpoint = TlsEndpoint::new(&addr, "localhost", config).unwrap();
+        let mut socket = TcpSocket::from_endpoint(&dbgid, endpoint, schema.message(), None);
+        for value in test_data {
+            let id = socket.send_kind(&value, None).unwrap();
+            let result = socket.read().unwrap();
+            assert!(result == (id.clone(), value.clone()), "\nresult: {:?}\ntarget: {:?}", result, (id, value));
+        }
+        let result = server.join().unwrap();
+        assert!(result.is_ok(), "\nresult: {:?}\ntarget: Ok", result);
+        // Server name not matching the certificate
+        let server_config = TlsServerConfig::new(TlsIdentity::from_pem(cert_pem.as_bytes(), key_pem.as_bytes()).unwrap(), None).unwrap();
+        let addr = format!("127.0.0.1:{}", TestSession::free_tcp_port_str());
+        let server = echo_server(&addr, server_config, schema.clone(), 0);
+        let config = TlsConfig::new(TlsVerify::ca(ca_pem.as_bytes()).unwrap(), None).unwrap();
+        let result = TlsEndpoint::new(&addr, "example.com", config).unwrap().connect(Duration::from_secs(3));
+        assert!(result.is_err(), "\nresult: {:?}\ntarget: Err", result);
+        let result = server.join().unwrap();
+        assert!(result.is_err(), "\nresult: {:?}\ntarget: Err", result);
+        // Address can't be resolved
+        let config = TlsConfig::new(TlsVerify::ca(ca_pem.as_bytes()).unwrap(), None).unwrap();
+        let result = TlsEndpoint::new("127.0.0.1", "localhost", config);
+        assert!(result.is_err(), "\nresult: {:?}\ntarget: Err", result);
+        test_duration.exit();
+    }
+    ///
+    /// Testing TLS connection to the server with pinned self signed certificate
+    #[test]
+    fn pinned() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        println!("");
+        let dbgid = DbgId("test TlsTransport.pinned".into());
+        println!("{}", dbgid);
+        let test_duration = TestDuration::new(&dbgid, Duration::from_secs(20));
+        test_duration.run().unwrap();
+        let schema = message_schema(&dbgid);
+        let (cert_pem, key_pem) = self_signed("localhost");
+        let (other_pem, _) = self_signed("localhost");
+        let test_data = [
+            (cert_pem.clone(), true),
+            (other_pem, false),
+        ];
+        for (pinned, ok) in test_data {
+            let addr = format!("127.0.0.1:{}", TestSession::free_tcp_port_str());
+            let server_config = TlsServerConfig::new(TlsIdentity::from_pem(cert_pem.as_bytes(), key_pem.as_bytes()).unwrap(), None).unwrap();
+            let server = echo_server(&addr, server_config, schema.clone(), if ok {1} else {0});
+            let config = TlsConfig::new(TlsVerify::pinned(pinned.as_bytes()).unwrap(), None).unwrap();
+            let endpoint = TlsEndpoint::new(&addr, "127.0.0.1", config).unwrap();
+            let result = endpoint.connect(Duration::from_secs(3));
+            assert!(result.is_ok() == ok, "\nresult: {:?}\ntarget: {}", result, if ok {"Ok"} else {"Err"});
+            if let Ok(transport) = result {
+                let mut socket = TcpSocket::from_endpoint(&dbgid, endpoint, schema.message(), Some(transport));
+                let value = MsgKind::Bytes(vec![7, 8, 9]);
+                let id = socket.send_kind(&value, None).unwrap();
+                let result = socket.read().unwrap();
+                assert!(result == (id.clone(), value.clone()), "\nresult: {:?}\ntarget: {:?}", result, (id, value));
+            }
+            let result = server.join().unwrap();
+            assert!(result.is_ok() == ok, "\nresult: {:?}\ntarget: {}", result, if ok {"Ok"} else {"Err"});
+        }
+        test_duration.exit();
+    }
+    ///
+    /// Testing the server requiring the client certificate
+    #[test]
+    fn client_cert() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        println!("");
+        let dbgid = DbgId("test TlsTransport.client_cert".into());
+        println!("{}", dbgid);
+        let test_duration = TestDuration::new(&dbgid, Duration::from_secs(20));
+        test_duration.run().unwrap();
+        let schema = message_schema(&dbgid);
+        let (ca_pem, ca_key, ca_cert) = ca_cert();
+        let (server_pem, server_key) = signed("localhost", &ca_key, &ca_cert);
+        let (client_pem, client_key) = signed("client", &ca_key, &ca_cert);
+        let test_data = [
+            (Some(TlsIdentity::from_pem(client_pem.as_bytes(), client_key.as_bytes()).unwrap()), true),
+            (None, false),
+        ];
+        for (identity, ok) in test_data {
+            let addr = format!("127.0.0.1:{}", TestSession::free_tcp_port_str());
+            let server_config = TlsServerConfig::new(
+                TlsIdentity::from_pem(server_pem.as_bytes(), server_key.as_bytes()).unwrap(),
+                Some(ca_pem.as_bytes()),
+            ).unwrap();
+            let server = echo_server(&addr, server_config, schema.clone(), 1);
+            let config = TlsConfig::new(TlsVerify::ca(ca_pem.as_bytes()).unwrap(), identity).unwrap();
+            let endpoint = TlsEndpoint::new(&addr, "localhost", config).unwrap();
+            match ok {
+                true => {
+                    let mut socket = TcpSocket::from_endpoint(&dbgid, endpoint, schema.message(), None);
+                    let value = MsgKind::String("authorized".to_owned());
+                    let id = socket.send_kind(&value, None).unwrap();
+                    let result = socket.read().unwrap();
+                    assert!(result == (id.clone(), value.clone()), "\nresult: {:?}\ntarget: {:?}", result, (id, value));
+                }
+                false => {
+                    // With TLS 1.3 the client certificate is rejected after the client handshake is completed
+                    let result = endpoint.connect(Duration::from_secs(3)).and_then(|transport| {
+                        let mut buf = [0; 16];
+                        transport.read(&mut buf)
+                    });
+                    assert!(result.is_err(), "\nresult: {:?}\ntarget: Err", result);
+                }
+            }
+            let result = server.join().unwrap();
+            assert!(result.is_ok() == ok, "\nresult: {:?}\ntarget: {}", result, if ok {"Ok"} else {"Err"});
+        }
+        test_duration.exit();
+    }
+}
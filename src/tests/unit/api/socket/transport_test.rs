@@ -0,0 +1,156 @@
+#[cfg(test)]
+
+mod transport {
+    use std::{sync::{atomic::AtomicUsize, Arc, Once}, thread, time::{Duration, Instant}};
+    use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
+    use testing::{session::teardown::Teardown, stuff::max_test_duration::TestDuration};
+    use crate::{
+        api::{
+            message::msg_kind::MsgKind,
+            socket::{memory_transport::{MemoryEndpoint, MemoryTransport}, tcp_socket::TcpSocket, transport::Transport},
+        },
+        client::api_request::message_schema,
+        debug::dbg_id::DbgId,
+    };
+    ///    
+    static INIT: Once = Once::new();
+    static TEARDOWN_COUNT: AtomicUsize = AtomicUsize::new(0);
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+                // implement your initialisation code to be called only once for current test file
+        });
+    }
+    ///
+    /// Once called after all tests
+    fn teardown_once() {
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Testing [MemoryTransport] read / write / close
+    #[test]
+    fn memory_pair() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        println!("");
+        let dbgid = DbgId("test MemoryTransport".into());
+        println!("{}", dbgid);
+        let test_duration = TestDuration::new(&dbgid, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let _teardown_once = || {
+            teardown_once();
+        };
+        let _teardown = Teardown::new(&TEARDOWN_COUNT, &|| {}, &_teardown_once);
+        let (left, right) = MemoryTransport::pair();
+        left.write_all(&[1, 2, 3]).unwrap();
+        left.write_all(&[4, 5]).unwrap();
+        let mut buf = [0; 4];
+        let result = right.read(&mut buf).unwrap();
+        assert!(result == 4, "\nresult: {:?}\ntarget: {:?}", result, 4);
+        assert!(buf == [1, 2, 3, 4], "\nresult: {:?}\ntarget: {:?}", buf, [1, 2, 3, 4]);
+        let result = right.read(&mut buf).unwrap();
+        assert!(result == 1 && buf[0] == 5, "\nresult: {:?}\ntarget: {:?}", &buf[..result], [5]);
+        right.set_read_timeout(Some(Duration::from_millis(50))).unwrap();
+        let time = Instant::now();
+        let result = right.read(&mut buf).map_err(|err| err.kind());
+        assert!(result == Err(std::io::ErrorKind::WouldBlock), "\nresult: {:?}\ntarget: {:?}", result, std::io::ErrorKind::WouldBlock);
+        assert!(time.elapsed() >= Duration::from_millis(50), "\nresult: {:?}\ntarget: >= 50 ms", time.elapsed());
+        let result = right.set_read_timeout(Some(Duration::ZERO)).map_err(|err| err.kind());
+        assert!(result == Err(std::io::ErrorKind::InvalidInput), "\nresult: {:?}\ntarget: {:?}", result, std::io::ErrorKind::InvalidInput);
+        drop(left);
+        let result = right.read(&mut buf).unwrap();
+        assert!(result == 0, "\nresult: {:?}\ntarget: {:?}", result, 0);
+        let result = right.write_all(&[1]).map_err(|err| err.kind());
+        assert!(result == Err(std::io::ErrorKind::BrokenPipe), "\nresult: {:?}\ntarget: {:?}", result, std::io::ErrorKind::BrokenPipe);
+        test_duration.exit();
+    }
+    ///
+    /// Testing [TcpSocket] messaging over the [MemoryEndpoint], without binding ports
+    #[test]
+    fn memory_socket() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        println!("");
+        let dbgid = DbgId("test TcpSocket over MemoryTransport".into());
+        println!("{}", dbgid);
+        let test_duration = TestDuration::new(&dbgid, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let schema = message_schema(&dbgid);
+        let (endpoint, accept) = MemoryEndpoint::new("server");
+        let test_data = [
+            MsgKind::Bytes(vec![1, 2, 3]),
+            MsgKind::String("Hello".to_owned()),
+            MsgKind::U32(123456),
+        ];
+        let server = {
+            let dbgid = DbgId("Server".to_owned());
+            let endpoint = endpoint.clone();
+            let schema = schema.clone();
+            let count = test_data.len();
+            thread::spawn(move || {
+                let transport = accept.recv().unwrap();
+                let mut socket = TcpSocket::from_endpoint(&dbgid, endpoint, schema.message(), Some(Arc::new(transport)));
+                for _ in 0..count {
+                    let (id, value) = socket.read().unwrap();
+                    socket.send_kind(&value, Some(id.0)).unwrap();
+                }
+                socket.close().unwrap();
+            })
+        };
+        let mut socket = TcpSocket::from_endpoint(&dbgid, endpoint, schema.message(), None);
+        for value in test_data {
+            let id = socket.send_kind(&value, None).unwrap();
+            let result = socket.read().unwrap();
+            assert!(result == (id.clone(), value.clone()), "\nresult: {:?}\ntarget: {:?}", result, (id, value));
+        }
+        server.join().unwrap();
+        let result = socket.read();
+        assert!(result.is_err(), "\nresult: {:?}\ntarget: Err", result);
+        test_duration.exit();
+    }
+    ///
+    /// Testing [TcpSocket] messaging over the Unix domain socket
+    #[cfg(unix)]
+    #[test]
+    fn uds_socket() {
+        use std::os::unix::net::UnixListener;
+        use crate::api::socket::transport::UdsEndpoint;
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        println!("");
+        let dbgid = DbgId("test TcpSocket over UnixStream".into());
+        println!("{}", dbgid);
+        let test_duration = TestDuration::new(&dbgid, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let schema = message_schema(&dbgid);
+        let path = std::env::temp_dir().join(format!("api-tools-transport-{}.sock", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let listener = UnixListener::bind(&path).unwrap();
+        let server = {
+            let dbgid = DbgId("Server".to_owned());
+            let path = path.clone();
+            let schema = schema.clone();
+            thread::spawn(move || {
+                let (stream, _) = listener.accept().unwrap();
+                let mut socket = TcpSocket::from_endpoint(&dbgid, UdsEndpoint::new(path), schema.message(), Some(Arc::new(stream)));
+                let (id, value) = socket.read().unwrap();
+                socket.send_kind(&value, Some(id.0)).unwrap();
+            })
+        };
+        let mut socket = TcpSocket::from_endpoint(&dbgid, UdsEndpoint::new(&path), schema.message(), None);
+        let value = MsgKind::String("Hello over UDS".to_owned());
+        let id = socket.send_kind(&value, None).unwrap();
+        let result = socket.read().unwrap();
+        assert!(result == (id.clone(), value.clone()), "\nresult: {:?}\ntarget: {:?}", result, (id, value));
+        server.join().unwrap();
+        std::fs::remove_file(&path).unwrap();
+        test_duration.exit();
+    }
+}
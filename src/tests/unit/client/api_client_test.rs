@@ -0,0 +1,332 @@
+#[cfg(test)]
+
+mod api_client {
+    use std::{net::TcpListener, sync::{atomic::AtomicUsize, Arc, Once}, thread, time::Duration};
+    use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
+    use testing::{session::{teardown::Teardown, test_session::TestSession}, stuff::max_test_duration::TestDuration};
+    use crate::{
+        api::{
+            message::{
+                fields::{FieldData, FieldId, FieldKind, FieldSize, FieldSyn}, fragment::Fragment,
+                message::MessageField, message_kind::MessageKind, message_schema::MessageSchema, msg_kind::MsgKind,
+            },
+            socket::{
+                memory_transport::MemoryEndpoint, reconnect::ReconnectPolicy, socket_err::SocketErr, tcp_socket::TcpSocket, timeouts::Timeouts,
+                tls_transport::{TlsConfig, TlsEndpoint, TlsIdentity, TlsServerConfig, TlsVerify}, transport::TcpEndpoint,
+            },
+        },
+        client::{api_client::ApiClient, api_query::{ApiQuery, ApiQueryKind, ApiQuerySql}, api_request::message_schema},
+        debug::dbg_id::DbgId,
+    };
+    ///    
+    static INIT: Once = Once::new();
+    static TEARDOWN_COUNT: AtomicUsize = AtomicUsize::new(0);
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+                // implement your initialisation code to be called only once for current test file
+        });
+    }
+    ///
+    /// Once called after all tests
+    fn teardown_once() {
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Testing [ApiClient] shared by many threads, replies received out of order
+    #[test]
+    fn shared() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        println!("");
+        let dbgid = DbgId("test ApiClient".into());
+        println!("{}", dbgid);
+        let test_duration = TestDuration::new(&dbgid, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let _teardown_once = || {
+            teardown_once();
+        };
+        let _teardown = Teardown::new(&TEARDOWN_COUNT, &|| {}, &_teardown_once);
+        let count = 8;
+        let (endpoint, accept) = MemoryEndpoint::new("api-server");
+        let server = {
+            let dbgid = DbgId("Server".to_owned());
+            let endpoint = endpoint.clone();
+            thread::spawn(move || {
+                let schema = MessageSchema::new(
+                    &dbgid,
+                    vec![
+                        MessageField::Syn(FieldSyn::default()),
+                        MessageField::Id(FieldId(4)),
+                        MessageField::Kind(FieldKind(MessageKind::Bytes)),
+                        MessageField::Size(FieldSize(4)),
+                        MessageField::Data(FieldData(vec![])),
+                    ],
+                ).unwrap();
+                let transport = accept.recv().unwrap();
+                let mut socket = TcpSocket::from_endpoint(&dbgid, endpoint, schema.message(), Some(Arc::new(transport)));
+                let mut requests = vec![];
+                for _ in 0..count {
+                    requests.push(socket.read().unwrap());
+                }
+                // Echo in reverse order
+                for (id, value) in requests.into_iter().rev() {
+                    socket.send_kind(&value, Some(id.0)).unwrap();
+                }
+                // Reply to nothing, must be dropped
+                socket.send_kind(&MsgKind::Bytes(vec![]), Some(12345)).unwrap();
+            })
+        };
+        // Single connection attempt, the server isn't accepting after done
+        let client = ApiClient::builder(&dbgid, endpoint, "token", false)
+            .with_reconnect(ReconnectPolicy::fixed(Duration::from_millis(10)).with_max_attempts(1))
+            .build();
+        let handles: Vec<_> = (0..count).map(|step| {
+            let client = client.clone();
+            thread::spawn(move || {
+                let sql = format!("SELECT {};", step);
+                let query = ApiQuery::new(ApiQueryKind::Sql(ApiQuerySql::new("database", &sql)), true);
+                let reply = client.fetch(&query, true).unwrap();
+                let reply: serde_json::Value = serde_json::from_slice(&reply).unwrap();
+                (sql, reply)
+            })
+        }).collect();
+        for handle in handles {
+            let (target, reply) = handle.join().unwrap();
+            let result = reply["sql"]["sql"].as_str().unwrap_or_default();
+            assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+            let result = reply["authToken"].as_str().unwrap_or_default();
+            assert!(result == "token", "\nresult: {:?}\ntarget: {:?}", result, "token");
+        }
+        server.join().unwrap();
+        // Server closed the connection
+        let query = ApiQuery::new(ApiQueryKind::Sql(ApiQuerySql::new("database", "SELECT 0;")), true);
+        let result = client.fetch(&query, true);
+        assert!(result.is_err(), "\nresult: {:?}\ntarget: Err", result);
+        test_duration.exit();
+    }
+    ///
+    /// Testing [ApiClient] opens the new connection for the query following the connection closed by the server
+    #[test]
+    fn reconnect() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        println!("");
+        let dbgid = DbgId("test ApiClient.reconnect".into());
+        println!("{}", dbgid);
+        let test_duration = TestDuration::new(&dbgid, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let count = 3;
+        let (endpoint, accept) = MemoryEndpoint::new("api-server-reconnect");
+        let server = {
+            let dbgid = DbgId("Server".to_owned());
+            let endpoint = endpoint.clone();
+            thread::spawn(move || {
+                let schema = MessageSchema::new(
+                    &dbgid,
+                    vec![
+                        MessageField::Syn(FieldSyn::default()),
+                        MessageField::Id(FieldId(4)),
+                        MessageField::Kind(FieldKind(MessageKind::Bytes)),
+                        MessageField::Size(FieldSize(4)),
+                        MessageField::Data(FieldData(vec![])),
+                    ],
+                ).unwrap();
+                // Single reply on each connection, then the connection is closed
+                for _ in 0..count {
+                    let transport = accept.recv().unwrap();
+                    let mut socket = TcpSocket::from_endpoint(&dbgid, endpoint.clone(), schema.message(), Some(Arc::new(transport)));
+                    let (id, value) = socket.read().unwrap();
+                    socket.send_kind(&value, Some(id.0)).unwrap();
+                    socket.close().unwrap();
+                }
+            })
+        };
+        let client = ApiClient::builder(&dbgid, endpoint, "token", false)
+            .with_timeouts(Timeouts::new(Duration::from_secs(1)))
+            .build();
+        for step in 0..count {
+            let sql = format!("SELECT {};", step);
+            let query = ApiQuery::new(ApiQueryKind::Sql(ApiQuerySql::new("database", &sql)), true);
+            let result = client.fetch(&query, true);
+            assert!(result.is_ok(), "step: {} \nresult: {:?}\ntarget: Ok", step, result);
+            let reply: serde_json::Value = serde_json::from_slice(&result.unwrap()).unwrap();
+            let result = reply["sql"]["sql"].as_str().unwrap_or_default();
+            assert!(result == sql, "step: {} \nresult: {:?}\ntarget: {:?}", step, result, sql);
+            // The reader notices the closed connection
+            thread::sleep(Duration::from_millis(100));
+        }
+        server.join().unwrap();
+        test_duration.exit();
+    }
+    ///
+    /// Testing [ApiClient] returns the replies sent in [MsgKind::Fragment]'s reassembled, fragments of the replies interleaved
+    #[test]
+    fn fragmented_reply() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        println!("");
+        let dbgid = DbgId("test ApiClient.fragmented_reply".into());
+        println!("{}", dbgid);
+        let test_duration = TestDuration::new(&dbgid, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let count = 4;
+        let (endpoint, accept) = MemoryEndpoint::new("api-server-fragments");
+        let server = {
+            let dbgid = DbgId("Server".to_owned());
+            let endpoint = endpoint.clone();
+            thread::spawn(move || {
+                let transport = accept.recv().unwrap();
+                let mut socket = TcpSocket::from_endpoint(&dbgid, endpoint, message_schema(&dbgid).message(), Some(Arc::new(transport)));
+                let mut requests = vec![];
+                for _ in 0..count {
+                    match socket.read().unwrap() {
+                        (id, MsgKind::Bytes(bytes)) => requests.push((id, bytes)),
+                        (id, value) => panic!("Server | Unexpected request {:?}: {:?}", id, value),
+                    }
+                }
+                // Echo in halves, the first halves of all replies, then the second ones
+                for (index, last) in [(0, false), (1, true)] {
+                    for (id, bytes) in &requests {
+                        let (first, second) = bytes.split_at(bytes.len() / 2);
+                        let bytes = if last {second} else {first};
+                        let fragment = Fragment { index, last, kind: MessageKind::Bytes, bytes: bytes.to_vec() };
+                        socket.send_kind(&MsgKind::Fragment(fragment), Some(id.0)).unwrap();
+                    }
+                }
+            })
+        };
+        let client = ApiClient::builder(&dbgid, endpoint, "token", false)
+            .with_reconnect(ReconnectPolicy::fixed(Duration::from_millis(10)).with_max_attempts(1))
+            .build();
+        let handles: Vec<_> = (0..count).map(|step| {
+            let client = client.clone();
+            thread::spawn(move || {
+                let sql = format!("SELECT {};", step);
+                let query = ApiQuery::new(ApiQueryKind::Sql(ApiQuerySql::new("database", &sql)), true);
+                let reply = client.fetch(&query, true).unwrap();
+                let reply: serde_json::Value = serde_json::from_slice(&reply).unwrap();
+                (sql, reply)
+            })
+        }).collect();
+        for handle in handles {
+            let (target, reply) = handle.join().unwrap();
+            let result = reply["sql"]["sql"].as_str().unwrap_or_default();
+            assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        }
+        server.join().unwrap();
+        test_duration.exit();
+    }
+    ///
+    /// Testing [ApiClient] over TLS, the reader waiting for the reply doesn't block the writer
+    #[test]
+    fn tls() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        println!("");
+        let dbgid = DbgId("test ApiClient.tls".into());
+        println!("{}", dbgid);
+        let test_duration = TestDuration::new(&dbgid, Duration::from_secs(20));
+        test_duration.run().unwrap();
+        let count = 3;
+        let addr = format!("127.0.0.1:{}", TestSession::free_tcp_port_str());
+        let certified = rcgen::generate_simple_self_signed(vec!["localhost".to_owned()]).unwrap();
+        let (cert_pem, key_pem) = (certified.cert.pem(), certified.key_pair.serialize_pem());
+        let server_config = TlsServerConfig::new(TlsIdentity::from_pem(cert_pem.as_bytes(), key_pem.as_bytes()).unwrap(), None).unwrap();
+        let listener = TcpListener::bind(&addr).unwrap();
+        let server = {
+            let dbgid = DbgId("Server".to_owned());
+            let addr = addr.clone();
+            thread::spawn(move || {
+                let (stream, _) = listener.accept().unwrap();
+                let transport = server_config.accept(stream, Duration::from_secs(5)).unwrap();
+                let mut socket = TcpSocket::from_endpoint(&dbgid, TcpEndpoint::new(addr), message_schema(&dbgid).message(), Some(Arc::new(transport)));
+                for _ in 0..count {
+                    let (id, value) = socket.read().unwrap();
+                    socket.send_kind(&value, Some(id.0)).unwrap();
+                }
+            })
+        };
+        let config = TlsConfig::new(TlsVerify::pinned(cert_pem.as_bytes()).unwrap(), None).unwrap();
+        let endpoint = TlsEndpoint::new(&addr, "localhost", config).unwrap();
+        let client = ApiClient::builder(&dbgid, endpoint, "token", false)
+            .with_timeouts(Timeouts::new(Duration::from_secs(3)))
+            .build();
+        for step in 0..count {
+            let sql = format!("SELECT {};", step);
+            let query = ApiQuery::new(ApiQueryKind::Sql(ApiQuerySql::new("database", &sql)), true);
+            let result = client.fetch(&query, true);
+            assert!(result.is_ok(), "step: {} \nresult: {:?}\ntarget: Ok", step, result);
+            let reply: serde_json::Value = serde_json::from_slice(&result.unwrap()).unwrap();
+            let result = reply["sql"]["sql"].as_str().unwrap_or_default();
+            assert!(result == sql, "step: {} \nresult: {:?}\ntarget: {:?}", step, result, sql);
+        }
+        server.join().unwrap();
+        test_duration.exit();
+    }
+    ///
+    /// Testing [ApiClient] returns [SocketErr::ReadTimeout] counted from the query written
+    /// and [SocketErr::Deadline] of the whole request, the connection isn't closed by the expired one
+    #[test]
+    fn timeouts() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        println!("");
+        let dbgid = DbgId("test ApiClient.timeouts".into());
+        println!("{}", dbgid);
+        let test_duration = TestDuration::new(&dbgid, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let read = Duration::from_millis(200);
+        let deadline = Duration::from_millis(100);
+        let test_data = [
+            (00, Timeouts::new(Duration::from_secs(3)).with_read(read), "ReadTimeout"),
+            (01, Timeouts::new(Duration::from_secs(3)).with_deadline(deadline), "Deadline"),
+        ];
+        let (endpoint, accept) = MemoryEndpoint::new("api-server-timeouts");
+        let server = {
+            let dbgid = DbgId("Server".to_owned());
+            let endpoint = endpoint.clone();
+            let count = test_data.len();
+            thread::spawn(move || {
+                // The first query on each connection isn't replied, the second one is
+                for _ in 0..count {
+                    let transport = accept.recv().unwrap();
+                    let mut socket = TcpSocket::from_endpoint(&dbgid, endpoint.clone(), message_schema(&dbgid).message(), Some(Arc::new(transport)));
+                    socket.read().unwrap();
+                    let (id, value) = socket.read().unwrap();
+                    socket.send_kind(&value, Some(id.0)).unwrap();
+                }
+            })
+        };
+        for (step, timeouts, target) in test_data {
+            let client = ApiClient::builder(&dbgid, endpoint.clone(), "token", false)
+                .with_timeouts(timeouts)
+                .build();
+            let sql = format!("SELECT {};", step);
+            let query = ApiQuery::new(ApiQueryKind::Sql(ApiQuerySql::new("database", &sql)), true);
+            let result = match client.fetch(&query, true) {
+                Err(SocketErr::ReadTimeout(timeout)) if timeout == read => "ReadTimeout",
+                Err(SocketErr::Deadline(timeout)) if timeout == deadline => "Deadline",
+                _ => "Unexpected",
+            };
+            assert!(result == target, "step: {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+            // Expired query doesn't close the connection, the next one is replied
+            let result = client.fetch(&query, true);
+            assert!(result.is_ok(), "step: {} \nresult: {:?}\ntarget: Ok", step, result);
+            let reply: serde_json::Value = serde_json::from_slice(&result.unwrap()).unwrap();
+            let result = reply["sql"]["sql"].as_str().unwrap_or_default();
+            assert!(result == sql, "step: {} \nresult: {:?}\ntarget: {:?}", step, result, sql);
+        }
+        server.join().unwrap();
+        test_duration.exit();
+    }
+}
@@ -1,12 +1,16 @@
 #[cfg(test)]
 
 mod api_request {
-    use std::{collections::HashMap, process::Command, sync::{atomic::AtomicUsize, Once}, thread, time::{Duration, Instant}};
+    use std::{collections::HashMap, process::Command, sync::{atomic::AtomicUsize, Arc, Once}, thread, time::{Duration, Instant}};
     use serde_json::json;
     use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
     use testing::{session::teardown::Teardown, stuff::max_test_duration::TestDuration};
     use crate::{
-        api::reply::api_reply::ApiReply, client::{api_query::{ApiQuery, ApiQueryExecutable, ApiQueryKind, ApiQueryPython, ApiQuerySql}, api_request::ApiRequest},
+        api::{
+            message::{fragment::Fragment, message_kind::MessageKind, msg_kind::MsgKind}, reply::api_reply::ApiReply,
+            socket::{memory_transport::MemoryEndpoint, tcp_socket::TcpSocket},
+        },
+        client::{api_query::{ApiQuery, ApiQueryExecutable, ApiQueryKind, ApiQueryPython, ApiQuerySql}, api_request::{message_schema, ApiRequest}},
         debug::dbg_id::DbgId, tests::unit::client::prepare_postgres::TestDatabasePostgres,
     };
     ///    
@@ -70,6 +74,147 @@ mod api_request {
     ///  - ...
     fn init_each() -> () {}
     ///
+    /// Testing [ApiRequest] accepts the reply of Bytes, Json & Any kinds, no API server required
+    #[test]
+    fn reply_kinds() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_each();
+        println!("");
+        let dbgid = DbgId("test ApiRequest.reply_kinds".into());
+        println!("{}", dbgid);
+        let test_duration = TestDuration::new(&dbgid, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let test_data = [
+            (01, MsgKind::Bytes(b"[1]".to_vec()), Some(b"[1]".to_vec())),
+            (02, MsgKind::Json(b"[2]".to_vec()), Some(b"[2]".to_vec())),
+            (03, MsgKind::Any(b"[3]".to_vec()), Some(b"[3]".to_vec())),
+            (04, MsgKind::String("[4]".to_owned()), None),
+        ];
+        let (endpoint, accept) = MemoryEndpoint::new("api-server");
+        let server = {
+            let dbgid = DbgId("Server".to_owned());
+            let endpoint = endpoint.clone();
+            let replies: Vec<MsgKind> = test_data.iter().map(|(_, reply, _)| reply.clone()).collect();
+            thread::spawn(move || {
+                let transport = accept.recv().unwrap();
+                let mut socket = TcpSocket::from_endpoint(&dbgid, endpoint, message_schema(&dbgid).message(), Some(Arc::new(transport)));
+                for reply in replies {
+                    let (id, _) = socket.read().unwrap();
+                    socket.send_kind(&reply, Some(id.0)).unwrap();
+                }
+            })
+        };
+        let query = ApiQuery::new(ApiQueryKind::Sql(ApiQuerySql::new("database", "SELECT 1;")), true);
+        let mut request = ApiRequest::with_endpoint(&dbgid, endpoint, "token", query, true, false);
+        for (step, _, target) in test_data {
+            let result = request.fetch(true).ok();
+            assert!(result == target, "step {}\nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+        server.join().unwrap();
+        test_duration.exit();
+    }
+    ///
+    /// Testing [ApiRequest] sends the query of Bytes kind by default and of Json kind if opted in, no API server required
+    #[test]
+    fn query_kind() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_each();
+        println!("");
+        let dbgid = DbgId("test ApiRequest.query_kind".into());
+        println!("{}", dbgid);
+        let test_duration = TestDuration::new(&dbgid, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let test_data = [
+            (01, false, MessageKind::Bytes),
+            (02, true, MessageKind::Json),
+        ];
+        let (endpoint, accept) = MemoryEndpoint::new("api-server-query-kind");
+        let server = {
+            let dbgid = DbgId("Server".to_owned());
+            let endpoint = endpoint.clone();
+            let count = test_data.len();
+            thread::spawn(move || {
+                let mut kinds = vec![];
+                for _ in 0..count {
+                    let transport = accept.recv().unwrap();
+                    let mut socket = TcpSocket::from_endpoint(&dbgid, endpoint.clone(), message_schema(&dbgid).message(), Some(Arc::new(transport)));
+                    let (id, value) = socket.read().unwrap();
+                    kinds.push(value.kind());
+                    socket.send_kind(&MsgKind::Bytes(b"[]".to_vec()), Some(id.0)).unwrap();
+                }
+                kinds
+            })
+        };
+        let query = ApiQuery::new(ApiQueryKind::Sql(ApiQuerySql::new("database", "SELECT 1;")), true);
+        for (step, json, _) in &test_data {
+            let request = ApiRequest::with_endpoint(&dbgid, endpoint.clone(), "token", query.clone(), true, false);
+            let mut request = match json {
+                true => request.with_json_kind(),
+                false => request,
+            };
+            let result = request.fetch(true);
+            assert!(result.is_ok(), "step {}\nresult: {:?}\ntarget: Ok", step, result);
+        }
+        let result = server.join().unwrap();
+        let target: Vec<MessageKind> = test_data.into_iter().map(|(_, _, kind)| kind).collect();
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        test_duration.exit();
+    }
+    ///
+    /// Testing [ApiRequest] returns the reply sent in [MsgKind::Fragment]'s reassembled, no API server required
+    /// - In the pipelined mode fragments are interleaved with the other reply
+    #[test]
+    fn fragmented_reply() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_each();
+        println!("");
+        let dbgid = DbgId("test ApiRequest.fragmented_reply".into());
+        println!("{}", dbgid);
+        let test_duration = TestDuration::new(&dbgid, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let fragment = |index, last, bytes: &[u8]| MsgKind::Fragment(Fragment { index, last, kind: MessageKind::Json, bytes: bytes.to_vec() });
+        let (endpoint, accept) = MemoryEndpoint::new("api-server-fragments");
+        let server = {
+            let dbgid = DbgId("Server".to_owned());
+            let endpoint = endpoint.clone();
+            thread::spawn(move || {
+                // Pipelined requests
+                let transport = accept.recv().unwrap();
+                let mut socket = TcpSocket::from_endpoint(&dbgid, endpoint.clone(), message_schema(&dbgid).message(), Some(Arc::new(transport)));
+                let (first, _) = socket.read().unwrap();
+                let (second, _) = socket.read().unwrap();
+                socket.send_kind(&fragment(0, false, b"[1,"), Some(first.0)).unwrap();
+                socket.send_kind(&MsgKind::Bytes(b"[3]".to_vec()), Some(second.0)).unwrap();
+                socket.send_kind(&fragment(1, true, b"2]"), Some(first.0)).unwrap();
+                // Single request
+                let transport = accept.recv().unwrap();
+                let mut socket = TcpSocket::from_endpoint(&dbgid, endpoint, message_schema(&dbgid).message(), Some(Arc::new(transport)));
+                let (id, _) = socket.read().unwrap();
+                socket.send_kind(&fragment(0, false, b"[4,"), Some(id.0)).unwrap();
+                socket.send_kind(&fragment(1, true, b"5]"), Some(id.0)).unwrap();
+            })
+        };
+        let query = ApiQuery::new(ApiQueryKind::Sql(ApiQuerySql::new("database", "SELECT 1;")), true);
+        let mut request = ApiRequest::with_endpoint(&dbgid, endpoint.clone(), "token", query.clone(), true, false).with_pipelining();
+        let first = request.send(&query, true).unwrap();
+        let second = request.send(&query, true).unwrap();
+        let test_data = [
+            (01, second, b"[3]".to_vec()),
+            (02, first, b"[1,2]".to_vec()),
+        ];
+        for (step, id, target) in test_data {
+            let result = request.reply(&id).unwrap();
+            assert!(result == target, "step {}\nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+        drop(request);
+        let mut request = ApiRequest::with_endpoint(&dbgid, endpoint, "token", query, true, false);
+        let result = request.fetch(true).unwrap();
+        let target = b"[4,5]".to_vec();
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        server.join().unwrap();
+        test_duration.exit();
+    }
+    ///
     /// 
     #[test]
     fn debug_false() {
@@ -0,0 +1,71 @@
+#[cfg(all(test, feature = "tokio"))]
+
+mod async_api_request {
+    use std::{sync::Once, time::{Duration, Instant}};
+    use futures_util::{SinkExt, StreamExt};
+    use tokio::net::TcpListener;
+    use tokio_util::codec::Framed;
+    use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
+    use testing::stuff::max_test_duration::TestDuration;
+    use crate::{
+        api::socket::message_codec::MessageCodec,
+        client::{api_query::{ApiQuery, ApiQueryKind, ApiQuerySql}, api_request::message_schema, async_api_request::AsyncApiRequest},
+        debug::dbg_id::DbgId,
+    };
+    ///
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+            // implement your initialisation code to be called only once for current test file
+        })
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Testing [AsyncApiRequest.fetch] against the echo server, then the server not replying
+    #[tokio::test]
+    async fn fetch() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbgid = DbgId("test AsyncApiRequest".into());
+        log::debug!("\n{}", dbgid);
+        let test_duration = TestDuration::new(&dbgid, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = {
+            let dbgid = dbgid.clone();
+            tokio::spawn(async move {
+                let (stream, _) = listener.accept().await.unwrap();
+                let mut framed = Framed::new(stream, MessageCodec::new(&dbgid, message_schema(&dbgid).message()));
+                // Echo the first request
+                let (id, value) = framed.next().await.unwrap().unwrap();
+                framed.send((id, value)).await.unwrap();
+                // Keep silent on the second one
+                let _ = framed.next().await;
+            })
+        };
+        let query = ApiQuery::new(ApiQueryKind::Sql(ApiQuerySql::new("database", "SELECT 1;")), true);
+        let mut request = AsyncApiRequest::new(&dbgid, addr, "token", query, true, false)
+            .with_timeout(Duration::from_millis(500));
+        let reply = request.fetch(true).await.unwrap();
+        let reply: serde_json::Value = serde_json::from_slice(&reply).unwrap();
+        let result = reply["sql"]["sql"].as_str().unwrap_or_default();
+        assert!(result == "SELECT 1;", "\nresult: {:?}\ntarget: {:?}", result, "SELECT 1;");
+        let result = reply["authToken"].as_str().unwrap_or_default();
+        assert!(result == "token", "\nresult: {:?}\ntarget: {:?}", result, "token");
+        let time = Instant::now();
+        let result = request.fetch(true).await;
+        assert!(result.is_err(), "\nresult: {:?}\ntarget: Err", result);
+        let elapsed = time.elapsed();
+        assert!(elapsed >= Duration::from_millis(500) && elapsed < Duration::from_secs(5), "\nresult: {:?}\ntarget: 500 ms", elapsed);
+        server.await.unwrap();
+        test_duration.exit();
+    }
+}
@@ -1,7 +1,11 @@
+mod api_client_test;
+
 mod api_query_test;
 
 mod api_request_test;
 
+mod async_api_request_test;
+
 mod prepare_postgres;
 
 // mod api_reply_test;
\ No newline at end of file
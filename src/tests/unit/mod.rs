@@ -2,3 +2,4 @@ mod error;
 mod server;
 mod client;
 mod api;
+mod tcp;
@@ -0,0 +1 @@
+mod tcp_client_connect_test;
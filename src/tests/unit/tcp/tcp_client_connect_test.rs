@@ -0,0 +1,103 @@
+#[cfg(test)]
+
+mod tcp_client_connect {
+    use std::{net::TcpListener, sync::{mpsc, Once}, time::{Duration, Instant}};
+    use testing::{session::test_session::TestSession, stuff::max_test_duration::TestDuration};
+    use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
+    use crate::{
+        api::socket::{reconnect::{ConnectEvent, ReconnectPolicy}, socket_err::SocketErr, timeouts::Timeouts},
+        debug::dbg_id::DbgId, tcp::tcp_client_connect::TcpClientConnect,
+    };
+    ///
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+            // implement your initialisation code to be called only once for current test file
+        })
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Testing [TcpClientConnect] emits [ConnectEvent::Lost] once per the lost connection
+    #[test]
+    fn lost() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        log::debug!("");
+        let dbgid = DbgId("test TcpClientConnect.lost".to_owned());
+        log::debug!("\n{}", dbgid);
+        let test_duration = TestDuration::new(&dbgid, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let port = TestSession::free_tcp_port_str();
+        let addr = format!("127.0.0.1:{}", port);
+        let _listener = TcpListener::bind(&addr).unwrap();
+        let endpoint = addr.parse::<std::net::SocketAddr>().unwrap().to_string();
+        let (events, events_recv) = mpsc::channel();
+        let mut connect = TcpClientConnect::new(dbgid.to_string(), &addr, Duration::from_millis(100)).with_events(events);
+        let connected = vec![
+            ConnectEvent::Connecting { endpoint: endpoint.clone(), attempt: 1 },
+            ConnectEvent::Connected(endpoint.clone()),
+        ];
+        let lost = |reason: &str| ConnectEvent::Lost { endpoint: endpoint.clone(), reason: reason.to_owned() };
+        let test_data = [
+            (00, None, true, connected.clone()),
+            // Reported by the owner of the stream, once per connection
+            (01, Some("Closed by peer"), true, [vec![lost("Closed by peer")], connected.clone()].concat()),
+            (02, Some("Closed by peer"), false, vec![lost("Closed by peer")]),
+            (03, Some("Closed by peer"), false, vec![]),
+            // Not reported, the previous connection is lost by the next connect
+            (04, None, true, connected.clone()),
+            (05, None, true, [vec![lost("Reconnecting")], connected.clone()].concat()),
+        ];
+        for (step, reason, reconnect, target) in test_data {
+            if let Some(reason) = reason {
+                connect.lost(reason);
+            }
+            if reconnect {
+                let result = connect.connect();
+                assert!(result.is_ok(), "step: {} \nresult: {:?}\ntarget: Ok", step, result);
+            }
+            let result: Vec<ConnectEvent> = events_recv.try_iter().collect();
+            log::debug!("{} | step: {},  result: {:?}", dbgid, step, result);
+            assert!(result == target, "step: {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+        test_duration.exit();
+    }
+    ///
+    /// Testing [TcpClientConnect] returns [SocketErr::ConnectTimeout] if not connected in the `connect` timeout
+    #[test]
+    fn connect_timeout() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        log::debug!("");
+        let dbgid = DbgId("test TcpClientConnect.connect_timeout".to_owned());
+        log::debug!("\n{}", dbgid);
+        let test_duration = TestDuration::new(&dbgid, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        // Nobody is listening
+        let port = TestSession::free_tcp_port_str();
+        let addr = format!("127.0.0.1:{}", port);
+        let timeout = Duration::from_millis(300);
+        let mut connect = TcpClientConnect::new(dbgid.to_string(), &addr, Duration::from_millis(50))
+            .with_timeouts(Timeouts::new(Duration::from_secs(3)).with_connect(timeout));
+        let time = Instant::now();
+        let result = connect.connect();
+        let elapsed = time.elapsed();
+        assert!(matches!(result, Err(SocketErr::ConnectTimeout(t)) if t == timeout), "\nresult: {:?}\ntarget: {:?}", result, SocketErr::ConnectTimeout(timeout));
+        assert!(elapsed >= timeout && elapsed < timeout * 2, "\nresult: {:?}\ntarget: {:?}", elapsed, timeout);
+        // Maximum number of attempts reached before the timeout
+        let mut connect = TcpClientConnect::new(dbgid.to_string(), &addr, Duration::from_millis(50))
+            .with_reconnect(ReconnectPolicy::fixed(Duration::from_millis(50)).with_max_attempts(2))
+            .with_timeouts(Timeouts::new(Duration::from_secs(3)));
+        let result = connect.connect();
+        assert!(matches!(result, Err(SocketErr::Other(_))), "\nresult: {:?}\ntarget: Err(SocketErr::Other)", result);
+        test_duration.exit();
+    }
+}